@@ -14,16 +14,76 @@
 
 extern crate gl_generator;
 
-use gl_generator::{Api, Fallbacks, GlobalGenerator, Profile, Registry};
+use gl_generator::{
+    Api, DebugStructGenerator, Fallbacks, GeneratorOptions, GlobalGenerator, Prelude, Profile,
+    Registry, StructGenerator,
+};
 use std::env;
 use std::fs::File;
 use std::path::Path;
 
+/// The features selecting the version of the bindings, which is 4.6 without any of them.
+const VERSIONS: &[(&str, (u8, u8))] = &[("core33", (3, 3)), ("core41", (4, 1)), ("core45", (4, 5))];
+
+/// The features adding an extension to the bindings.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("ext_arb_debug_output", "GL_ARB_debug_output"),
+    (
+        "ext_ext_texture_filter_anisotropic",
+        "GL_EXT_texture_filter_anisotropic",
+    ),
+    ("ext_khr_debug", "GL_KHR_debug"),
+];
+
+/// Returns `true` if the feature `name` of the crate is enabled.
+fn has_feature(name: &str) -> bool {
+    let name = name.to_uppercase().replace('-', "_");
+    env::var_os(format!("CARGO_FEATURE_{}", name)).is_some()
+}
+
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
-    let mut file = File::create(&Path::new(&dest).join("bindings.rs")).unwrap();
+    let mut file = File::create(Path::new(&dest).join("bindings.rs")).unwrap();
+
+    // Conflicting features are reported by `compile_error!` in `lib.rs`, so the first one wins
+    // here.
+    let version = VERSIONS
+        .iter()
+        .find(|&&(feature, _)| has_feature(feature))
+        .map_or((4, 6), |&(_, version)| version);
+    let profile = if has_feature("compat") {
+        Profile::Compatibility
+    } else {
+        Profile::Core
+    };
+    let extensions: Vec<_> = EXTENSIONS
+        .iter()
+        .filter(|&&(feature, _)| has_feature(feature))
+        .map(|&(_, extension)| extension)
+        .collect();
+
+    // With `debug-trace`, the traced form of the commands is compiled instead of the plain one.
+    let options = GeneratorOptions {
+        prelude: if has_feature("prelude") {
+            Some(Prelude::TypesAndCommands)
+        } else {
+            None
+        },
+        debug_feature: if has_feature("debug-trace") {
+            Some("debug-trace".to_string())
+        } else {
+            None
+        },
+        ..Default::default()
+    };
 
-    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
-        .write_bindings(GlobalGenerator, &mut file)
-        .unwrap();
+    let registry = Registry::new(Api::Gl, version, profile, Fallbacks::All, extensions);
+    if has_feature("debug_api") {
+        registry.write_bindings_with_options(DebugStructGenerator, &options, &mut file)
+    } else if has_feature("struct_api") {
+        registry.write_bindings_with_options(StructGenerator, &options, &mut file)
+    } else {
+        registry.write_bindings_with_options(GlobalGenerator, &options, &mut file)
+    }
+    .unwrap();
 }
@@ -25,7 +25,7 @@ use std::str;
 static VERTEX_DATA: [GLfloat; 6] = [0.0, 0.5, 0.5, -0.5, -0.5, -0.5];
 
 // Shader sources
-static VS_SRC: &'static str = "
+static VS_SRC: &str = "
 #version 150
 in vec2 position;
 
@@ -33,7 +33,7 @@ void main() {
     gl_Position = vec4(position, 0.0, 1.0);
 }";
 
-static FS_SRC: &'static str = "
+static FS_SRC: &str = "
 #version 150
 out vec4 out_color;
 
@@ -58,19 +58,17 @@ fn compile_shader(src: &str, ty: GLenum) -> GLuint {
         if status != (gl::TRUE as GLint) {
             let mut len = 0;
             gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut len);
-            let mut buf = Vec::with_capacity(len as usize);
-            buf.set_len((len as usize) - 1); // subtract 1 to skip the trailing null character
+            let mut buf = vec![0u8; len as usize];
             gl::GetShaderInfoLog(
                 shader,
                 len,
                 ptr::null_mut(),
                 buf.as_mut_ptr() as *mut GLchar,
             );
+            buf.pop(); // skip the trailing null character
             panic!(
                 "{}",
-                str::from_utf8(&buf)
-                    .ok()
-                    .expect("ShaderInfoLog not valid utf8")
+                str::from_utf8(&buf).expect("ShaderInfoLog not valid utf8")
             );
         }
     }
@@ -91,19 +89,17 @@ fn link_program(vs: GLuint, fs: GLuint) -> GLuint {
         if status != (gl::TRUE as GLint) {
             let mut len: GLint = 0;
             gl::GetProgramiv(program, gl::INFO_LOG_LENGTH, &mut len);
-            let mut buf = Vec::with_capacity(len as usize);
-            buf.set_len((len as usize) - 1); // subtract 1 to skip the trailing null character
+            let mut buf = vec![0u8; len as usize];
             gl::GetProgramInfoLog(
                 program,
                 len,
                 ptr::null_mut(),
                 buf.as_mut_ptr() as *mut GLchar,
             );
+            buf.pop(); // skip the trailing null character
             panic!(
                 "{}",
-                str::from_utf8(&buf)
-                    .ok()
-                    .expect("ProgramInfoLog not valid utf8")
+                str::from_utf8(&buf).expect("ProgramInfoLog not valid utf8")
             );
         }
         program
@@ -142,7 +138,7 @@ fn main() {
         gl::BufferData(
             gl::ARRAY_BUFFER,
             (VERTEX_DATA.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-            mem::transmute(&VERTEX_DATA[0]),
+            VERTEX_DATA.as_ptr() as *const _,
             gl::STATIC_DRAW,
         );
 
@@ -157,7 +153,7 @@ fn main() {
             pos_attr as GLuint,
             2,
             gl::FLOAT,
-            gl::FALSE as GLboolean,
+            gl_bool(false),
             0,
             ptr::null(),
         );
@@ -168,20 +164,20 @@ fn main() {
         use glutin::event_loop::ControlFlow;
         *control_flow = ControlFlow::Wait;
         match event {
-            Event::LoopDestroyed => return,
-            Event::WindowEvent { event, .. } => match event {
-                WindowEvent::CloseRequested => {
-                    // Cleanup
-                    unsafe {
-                        gl::DeleteProgram(program);
-                        gl::DeleteShader(fs);
-                        gl::DeleteShader(vs);
-                        gl::DeleteBuffers(1, &vbo);
-                        gl::DeleteVertexArrays(1, &vao);
-                    }
-                    *control_flow = ControlFlow::Exit
-                },
-                _ => (),
+            Event::LoopDestroyed => (),
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => {
+                // Cleanup
+                unsafe {
+                    gl::DeleteProgram(program);
+                    gl::DeleteShader(fs);
+                    gl::DeleteShader(vs);
+                    gl::DeleteBuffers(1, &vbo);
+                    gl::DeleteVertexArrays(1, &vao);
+                }
+                *control_flow = ControlFlow::Exit
             },
             Event::RedrawRequested(_) => {
                 unsafe {
@@ -14,63 +14,138 @@
 
 //! # Usage
 //!
-//! You can import the pointer style loader and type aliases like so:
+//! The bindings are generated for OpenGL 4.6 core with the global generator by default. Cargo
+//! features of the crate select other bindings:
 //!
-//! ~~~no_run
-//! # #![allow(unused_imports)]
-//! extern crate gl;
-//! # fn main() {
-//! // include the OpenGL type aliases
-//! use gl::types::*;
-//! # }
-//! ~~~
+//! - `core33`, `core41` or `core45` for OpenGL 3.3, 4.1 or 4.5.
+//! - `compat` for the compatibility profile.
+//! - `struct_api` for a `gl::Gl` structure holding the function pointers, or `debug_api` for one
+//!   that also logs every call, instead of global functions.
+//! - `debug-trace` for logging every call of the bindings, with the same items, such as with
+//!   `cargo run --features gl/debug-trace`.
+//! - `prelude` for a `gl::prelude` module re-exporting the type aliases and the commands, or the
+//!   `Gl` structure.
+//! - `ext_arb_debug_output`, `ext_ext_texture_filter_anisotropic` and `ext_khr_debug` for the
+//!   corresponding extensions.
 //!
-//! You must load the function pointers into their respective function pointers
-//! using the `load_with` function. You must supply a loader function from your
-//! context library. This is how it would look using [glfw-rs](https://github.com/PistonDevelopers/glfw-rs):
-//!
-//! ~~~ignore
-//! // the supplied function must be of the type:
-//! // `&fn(symbol: &'static str) -> *const std::os::raw::c_void`
-//! // `window` is a glfw::Window
-//! gl::load_with(|s| window.get_proc_address(s) as *const _);
-//!
-//! // loading a specific function pointer
-//! gl::Viewport::load_with(|s| window.get_proc_address(s) as *const _);
-//! ~~~
-//!
-//! Calling a function that has not been loaded will result in a failure like:
-//! `panic!("gl::Viewport was not loaded")`, which avoids a segfault. This feature
-//! does not cause any run time overhead because the failing functions are
-//! assigned only when `load_with` is called.
-//!
-//! All OpenGL function calls are `unsafe`.
-//!
-//! ~~~no_run
-//! # #![allow(path_statement)]
-//! # extern crate gl;
-//! # fn main() {
-//! // accessing an enum
-//! gl::TEXTURE_2D;
-//!
-//! // calling a function
-//! unsafe { gl::DrawArrays(gl::TRIANGLES, 0, 3) };
-//! # }
-//! ~~~
-//!
-//! Each function pointer has an associated boolean value allowing you to
-//! check if a function has been loaded at run time. The function accesses a
-//! corresponding global boolean that is set when `load_with` is called, so there
-//! shouldn't be much overhead.
-//!
-//! ~~~no_run
-//! if gl::Viewport::is_loaded() {
-//!     // do something...
-//! }
-//! ~~~
+//! ```toml
+//! [dependencies]
+//! gl = { version = "0.15.0", features = ["core33", "struct_api"] }
+//! ```
 //!
+#![cfg_attr(
+    not(any(feature = "struct_api", feature = "debug_api")),
+    doc = r#"
+You can import the pointer style loader and type aliases like so:
+
+~~~no_run
+# #![allow(unused_imports)]
+extern crate gl;
+# fn main() {
+// include the OpenGL type aliases
+use gl::types::*;
+# }
+~~~
+
+With the `prelude` feature, `use gl::prelude::*;` brings the type aliases and the commands into
+scope at once.
+
+You must load the function pointers into their respective function pointers
+using the `load_with` function. You must supply a loader function from your
+context library. This is how it would look using [glfw-rs](https://github.com/PistonDevelopers/glfw-rs):
+
+~~~ignore
+// the supplied function must be of the type:
+// `&fn(symbol: &'static str) -> *const std::os::raw::c_void`
+// `window` is a glfw::Window
+gl::load_with(|s| window.get_proc_address(s) as *const _);
+
+// loading a specific function pointer
+gl::Viewport::load_with(|s| window.get_proc_address(s) as *const _);
+~~~
 
+Calling a function that has not been loaded will result in a failure like:
+`panic!("gl::Viewport was not loaded")`, which avoids a segfault. This feature
+does not cause any run time overhead because the failing functions are
+assigned only when `load_with` is called.
+
+All OpenGL function calls are `unsafe`.
+
+~~~no_run
+# #![allow(path_statement)]
+# extern crate gl;
+# fn main() {
+// accessing an enum
+gl::TEXTURE_2D;
+
+// calling a function
+unsafe { gl::DrawArrays(gl::TRIANGLES, 0, 3) };
+# }
+~~~
+
+Each function has an `is_loaded` function to check at run time whether it
+has been loaded. It compares the function pointer with the panicking stub that
+stands in for the functions that `load_with` couldn't find, so there is no
+separate flag to keep in sync and checking costs a single comparison.
+
+~~~no_run
+if gl::Viewport::is_loaded() {
+    // do something...
+}
+~~~
+"#
+)]
+#![cfg_attr(
+    any(feature = "struct_api", feature = "debug_api"),
+    doc = r#"
+You can import the type aliases like so:
+
+~~~no_run
+# #![allow(unused_imports)]
+extern crate gl;
+# fn main() {
+// include the OpenGL type aliases
+use gl::types::*;
+# }
+~~~
+
+With the `prelude` feature, `use gl::prelude::*;` brings the type aliases and the `Gl` structure
+into scope at once.
+
+The function pointers are loaded into a `gl::Gl` with its `load_with` function, which takes a
+loader function from your context library, and the commands are its methods. All OpenGL function
+calls are `unsafe`.
+
+~~~no_run
+# extern crate gl;
+# use std::os::raw;
+# fn get_proc_address(_: &str) -> *const raw::c_void { std::ptr::null() }
+# fn main() {
+let gl = gl::Gl::load_with(|s| get_proc_address(s));
+
+unsafe {
+    gl.ClearColor(0.0, 0.0, 0.0, 1.0);
+    gl.Clear(gl::COLOR_BUFFER_BIT);
+}
+
+if gl.Viewport.is_loaded() {
+    // do something...
+}
+# }
+~~~
+"#
+)]
 #![crate_name = "gl"]
 #![crate_type = "lib"]
 
+#[cfg(any(
+    all(feature = "core33", feature = "core41"),
+    all(feature = "core33", feature = "core45"),
+    all(feature = "core41", feature = "core45")
+))]
+compile_error!("only one of the `core33`, `core41` and `core45` features of `gl` can be enabled");
+
+#[cfg(all(feature = "struct_api", feature = "debug_api"))]
+compile_error!("the `struct_api` and `debug_api` features of `gl` can't be enabled together");
+
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
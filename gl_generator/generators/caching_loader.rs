@@ -0,0 +1,72 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `CachingLoader` type emitted when `GeneratorOptions::caching_loader` is set.
+
+use std::io;
+
+/// Writes the `CachingLoader` type, which wraps a load function and remembers its results.
+pub fn write_caching_loader<W>(std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        /// A load function adapter that remembers the result of each symbol, including the symbols
+        /// that could not be found, so that the wrapped function is called once per symbol.
+        ///
+        /// The adapter can be shared between threads, and between the bindings of several APIs
+        /// that are loaded from the same context.
+        ///
+        /// ~~~ignore
+        /// let loader = CachingLoader::new(|s| glfw.get_proc_address(s));
+        /// gl::load_with(|s| loader.load(s));
+        /// ~~~
+        #[allow(dead_code)]
+        pub struct CachingLoader<F> {{
+            inner: {std}::sync::Mutex<(F, {std}::collections::HashMap<&'static str, usize>)>,
+        }}
+
+        #[allow(dead_code)]
+        impl<F> CachingLoader<F> where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            /// Wraps `loadfn`, with nothing cached yet.
+            pub fn new(loadfn: F) -> CachingLoader<F> {{
+                CachingLoader {{
+                    inner: {std}::sync::Mutex::new((loadfn, {std}::collections::HashMap::new())),
+                }}
+            }}
+
+            /// Returns the address of `symbol`, calling the wrapped load function only the first
+            /// time that the symbol is requested.
+            pub fn load(&self, symbol: &'static str) -> *const __gl_imports::raw::c_void {{
+                let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+                let inner = &mut *inner;
+                if let Some(&ptr) = inner.1.get(symbol) {{
+                    return ptr as *const __gl_imports::raw::c_void;
+                }}
+                let ptr = (inner.0)(symbol);
+                inner.1.insert(symbol, ptr as usize);
+                ptr
+            }}
+
+            /// Forgets every result, for example after the context has been recreated.
+            pub fn clear(&self) {{
+                self.inner.lock().unwrap_or_else(|e| e.into_inner()).1.clear();
+            }}
+        }}
+        "#,
+        std = std_path,
+    )
+}
@@ -0,0 +1,224 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `Capabilities` structure emitted when `GeneratorOptions::capabilities` is set.
+
+use super::helpers::Dispatch;
+use registry::Registry;
+use std::io;
+
+/// The limits that `Capabilities` has a field for when the registry has their enum, as the name
+/// of the field, the enum queried with `GetIntegerv`, the number of values it returns and the
+/// documentation of the field.
+const LIMITS: &[(&str, &str, usize, &str)] = &[
+    (
+        "max_texture_size",
+        "MAX_TEXTURE_SIZE",
+        1,
+        "The largest width and height of a texture.",
+    ),
+    (
+        "max_3d_texture_size",
+        "MAX_3D_TEXTURE_SIZE",
+        1,
+        "The largest width, height and depth of a 3D texture.",
+    ),
+    (
+        "max_cube_map_texture_size",
+        "MAX_CUBE_MAP_TEXTURE_SIZE",
+        1,
+        "The largest width and height of a cube map texture.",
+    ),
+    (
+        "max_array_texture_layers",
+        "MAX_ARRAY_TEXTURE_LAYERS",
+        1,
+        "The largest number of layers of an array texture.",
+    ),
+    (
+        "max_renderbuffer_size",
+        "MAX_RENDERBUFFER_SIZE",
+        1,
+        "The largest width and height of a renderbuffer.",
+    ),
+    (
+        "max_viewport_dims",
+        "MAX_VIEWPORT_DIMS",
+        2,
+        "The largest width and height of the viewport.",
+    ),
+    (
+        "max_texture_image_units",
+        "MAX_TEXTURE_IMAGE_UNITS",
+        1,
+        "The number of texture units that the fragment shader can access.",
+    ),
+    (
+        "max_combined_texture_image_units",
+        "MAX_COMBINED_TEXTURE_IMAGE_UNITS",
+        1,
+        "The number of texture units that all the shaders can access together.",
+    ),
+    (
+        "max_vertex_attribs",
+        "MAX_VERTEX_ATTRIBS",
+        1,
+        "The number of vertex attributes.",
+    ),
+    (
+        "max_vertex_uniform_vectors",
+        "MAX_VERTEX_UNIFORM_VECTORS",
+        1,
+        "The number of four-component uniform vectors of the vertex shader.",
+    ),
+    (
+        "max_fragment_uniform_vectors",
+        "MAX_FRAGMENT_UNIFORM_VECTORS",
+        1,
+        "The number of four-component uniform vectors of the fragment shader.",
+    ),
+    (
+        "max_varying_vectors",
+        "MAX_VARYING_VECTORS",
+        1,
+        "The number of four-component vectors passed from the vertex to the fragment shader.",
+    ),
+    (
+        "max_draw_buffers",
+        "MAX_DRAW_BUFFERS",
+        1,
+        "The number of buffers that the fragment shader can write to.",
+    ),
+    (
+        "max_color_attachments",
+        "MAX_COLOR_ATTACHMENTS",
+        1,
+        "The number of color attachments of a framebuffer.",
+    ),
+    (
+        "max_samples",
+        "MAX_SAMPLES",
+        1,
+        "The largest number of samples of a multisampled renderbuffer.",
+    ),
+    (
+        "max_uniform_block_size",
+        "MAX_UNIFORM_BLOCK_SIZE",
+        1,
+        "The largest size of a uniform block, in bytes.",
+    ),
+    (
+        "max_uniform_buffer_bindings",
+        "MAX_UNIFORM_BUFFER_BINDINGS",
+        1,
+        "The number of uniform buffer binding points.",
+    ),
+    (
+        "max_compute_work_group_invocations",
+        "MAX_COMPUTE_WORK_GROUP_INVOCATIONS",
+        1,
+        "The largest number of invocations of a compute work group.",
+    ),
+    (
+        "max_compute_shared_memory_size",
+        "MAX_COMPUTE_SHARED_MEMORY_SIZE",
+        1,
+        "The largest size of the shared memory of a compute shader, in bytes.",
+    ),
+];
+
+/// Writes the `Capabilities` structure, with a field for each of the `LIMITS` whose enum is in
+/// the registry, and its `query` function, which takes the bindings for `Dispatch::Method`.
+/// Nothing is written if the registry has no `GetIntegerv`, as for the window-system APIs.
+pub fn write_capabilities<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !registry
+        .cmds
+        .iter()
+        .any(|cmd| cmd.proto.ident == "GetIntegerv")
+    {
+        return Ok(());
+    }
+    let limits: Vec<_> = LIMITS
+        .iter()
+        .filter(|&&(_, name, _, _)| registry.enums.iter().any(|enm| enm.ident == name))
+        .collect();
+
+    writeln!(
+        dest,
+        "/// Limits of the implementation, queried at once by `Capabilities::query`.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct Capabilities {{"
+    )?;
+    for &&(field, name, count, doc) in &limits {
+        writeln!(
+            dest,
+            "/// {doc} Queried with `{name}`.
+            pub {field}: {ty},",
+            doc = doc,
+            name = name,
+            field = field,
+            ty = match count {
+                1 => "types::GLint".to_string(),
+                n => format!("[types::GLint; {}]", n),
+            },
+        )?;
+    }
+
+    let (params, target) = match dispatch {
+        Dispatch::Free => (String::new(), ""),
+        Dispatch::Method => (
+            format!("gl: &{}", super::gen_struct_name(registry.api)),
+            "gl.",
+        ),
+    };
+    writeln!(
+        dest,
+        "}}
+
+        impl Capabilities {{
+            /// Queries the limits of the current context with `GetIntegerv`. The limits that the
+            /// context doesn't know are left at `0`, and make it record an `INVALID_ENUM` error.
+            #[allow(dead_code, unused_mut)]
+            pub fn query({params}) -> Capabilities {{
+                let mut capabilities = Capabilities::default();
+                unsafe {{",
+        params = params,
+    )?;
+    for &&(field, name, count, _) in &limits {
+        writeln!(
+            dest,
+            "{target}GetIntegerv({name}, {ptr});",
+            target = target,
+            name = name,
+            ptr = match count {
+                1 => format!("&mut capabilities.{}", field),
+                _ => format!("capabilities.{}.as_mut_ptr()", field),
+            },
+        )?;
+    }
+    writeln!(
+        dest,
+        "}}
+                capabilities
+            }}
+        }}"
+    )
+}
@@ -0,0 +1,71 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `command_version` function emitted when `GeneratorOptions::command_versions` is set.
+
+use registry::Registry;
+use std::collections::BTreeMap;
+use std::io;
+
+/// Writes the `command_version` function, which matches the symbol of each command that a version
+/// of the registry introduced to the number of that version.
+pub fn write_command_version<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    // The versions of a merged registry aren't necessarily sorted, and a command is only matched
+    // by the first version that introduced it so that no arm is unreachable.
+    let mut introduced = BTreeMap::new();
+    for version in &registry.versions {
+        for cmd in &version.cmds {
+            let number = introduced.entry(&cmd[..]).or_insert(version.number);
+            *number = (*number).min(version.number);
+        }
+    }
+    let mut by_version = BTreeMap::new();
+    for (cmd, number) in introduced {
+        by_version
+            .entry(number)
+            .or_insert_with(Vec::new)
+            .push(format!("{:?}", super::gen_symbol_name(registry.api, cmd)));
+    }
+
+    writeln!(
+        dest,
+        "/// Returns the version of the API that introduced the command with the symbol `name`,
+        /// such as `{example}`, or `None` if the command only comes from extensions or is not part
+        /// of the bindings.
+        #[allow(dead_code)]
+        pub fn command_version(name: &str) -> Option<(u8, u8)> {{
+            match name {{",
+        example = super::gen_symbol_name(registry.api, "DrawArrays"),
+    )?;
+
+    for ((major, minor), symbols) in by_version {
+        writeln!(
+            dest,
+            "{} => Some(({}, {})),",
+            symbols.join(" | "),
+            major,
+            minor
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "_ => None,
+            }}
+        }}"
+    )
+}
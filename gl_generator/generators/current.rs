@@ -0,0 +1,63 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The thread-local bindings emitted when `GeneratorOptions::thread_local_current` is set.
+
+use registry::Registry;
+use std::io;
+
+/// Writes the thread-local storage of the structure of the struct generators, the
+/// `make_current` method installing it and the `with_current` and `try_with_current` functions
+/// accessing it.
+pub fn write_current<W>(registry: &Registry, std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        {std}::thread_local!(static CURRENT: {std}::cell::RefCell<Option<{api}>> = const {{ {std}::cell::RefCell::new(None) }});
+
+        impl {api} {{
+            /// Installs the bindings as the current ones of this thread, for `with_current`,
+            /// returning the bindings that were current before.
+            ///
+            /// Panics if it is called from the closure of `with_current`.
+            #[allow(dead_code)]
+            pub fn make_current(self) -> Option<{api}> {{
+                CURRENT.with(|current| current.borrow_mut().replace(self))
+            }}
+        }}
+
+        /// Calls `f` with the bindings installed on this thread by `{api}::make_current`.
+        ///
+        /// Panics if no bindings have been installed on this thread.
+        #[allow(dead_code)]
+        pub fn with_current<R, F: FnOnce(&{api}) -> R>(f: F) -> R {{
+            match try_with_current(f) {{
+                Some(r) => r,
+                None => panic!("no `{api}` has been made current on this thread"),
+            }}
+        }}
+
+        /// Calls `f` with the bindings installed on this thread by `{api}::make_current`, or
+        /// returns `None` if no bindings have been installed on this thread.
+        #[allow(dead_code)]
+        pub fn try_with_current<R, F: FnOnce(&{api}) -> R>(f: F) -> Option<R> {{
+            CURRENT.with(|current| current.borrow().as_ref().map(f))
+        }}"#,
+        api = super::gen_struct_name(registry.api),
+        std = std_path,
+    )
+}
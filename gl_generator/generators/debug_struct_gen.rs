@@ -12,31 +12,202 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use registry::Registry;
+use registry::{Api, Cmd, Registry};
+use std::collections::BTreeSet;
 use std::io;
 
 #[allow(missing_copy_implementations)]
 pub struct DebugStructGenerator;
 
 impl super::Generator for DebugStructGenerator {
-    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
-    where
-        W: io::Write,
-    {
-        write_header(dest)?;
-        write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
-        write_fnptr_struct_def(dest)?;
-        write_panicking_fns(registry, dest)?;
-        write_struct(registry, dest)?;
-        write_impl(registry, dest)?;
-        Ok(())
+    fn name(&self) -> &'static str {
+        "DebugStructGenerator"
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        self.write_with_options(registry, &Default::default(), dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &super::GeneratorOptions,
+        dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        if !options.gpu_timing.is_empty() {
+            super::gpu_timing::check_registry(registry)?;
+        }
+
+        let mut dest = io::BufWriter::with_capacity(super::WRITE_BUFFER_SIZE, dest);
+        let dest = &mut dest;
+
+        super::write_sections(SECTIONS, options, dest, |section, mut dest| {
+            write_section(registry, options, section, &mut dest)
+        })?;
+        io::Write::flush(dest)
+    }
+}
+
+/// The number of sections that `write_section` writes.
+const SECTIONS: usize = 7;
+
+/// Writes one of the sections of the bindings, which don't depend on each other and are written
+/// in order.
+fn write_section<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    section: usize,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    match section {
+        0 => {
+            write_header(registry, options, dest)?;
+            write_type_aliases(registry, options, dest)?;
+            Ok(())
+        },
+        1 => {
+            write_enums(registry, dest)?;
+            if options.int_enums {
+                super::gen_int_enums(registry, dest)?;
+            }
+            Ok(())
+        },
+        2 => {
+            write_pfn_types(registry, dest)?;
+            if options.typed_fn_ptrs {
+                write_typed_fn_ptr(dest)?;
+            } else {
+                write_fnptr_struct_def(super::std_path(options), dest)?;
+            }
+            Ok(())
+        },
+        3 => {
+            write_panicking_fns(registry, options, dest)?;
+            if options.interceptor {
+                super::interceptor::write_interceptor(super::std_path(options), dest)?;
+            }
+            if !options.gpu_timing.is_empty() {
+                super::gpu_timing::write_gpu_timing(super::std_path(options), dest)?;
+            }
+            Ok(())
+        },
+        4 => {
+            write_struct(registry, options, dest)?;
+            Ok(())
+        },
+        5 => {
+            write_impl(registry, options, dest)?;
+            writeln!(dest, "{}", super::gen_try_load_error(registry.api, options))?;
+            Ok(())
+        },
+        6 => {
+            if options.pfn_table {
+                super::pfn_table::write_pfn_table(registry, options, dest)?;
+            }
+            if let Some(ref path) = options.global_bridge {
+                super::global_bridge::write_global_bridge(registry, options, path, dest)?;
+            }
+            if options.thread_local_current {
+                super::current::write_current(registry, super::std_path(options), dest)?;
+            }
+            if options.helpers
+                || options.typed_get_error
+                || options.nonzero_create
+                || options.alias_shims
+            {
+                super::helpers::write_helpers(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.capabilities {
+                super::capabilities::write_capabilities(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    dest,
+                )?;
+            }
+            if options.handle_types {
+                super::handles::write_typed_commands(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.dispatch_table {
+                super::dispatch_table::write_dispatch_table(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.caching_loader {
+                super::caching_loader::write_caching_loader(super::std_path(options), dest)?;
+            }
+            if options.loader_trait {
+                super::loader_trait::write_loader_trait(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.signature_test {
+                super::signature_test::write_signature_test(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    "unsafe fn",
+                    dest,
+                )?;
+            }
+            if options.command_versions {
+                super::command_version::write_command_version(registry, dest)?;
+            }
+            if registry.api == Api::Wgl {
+                super::wgl::write_wgl_loader(registry, super::helpers::Dispatch::Method, dest)?;
+            }
+            if registry.api == Api::Egl {
+                super::egl::write_egl_loader(registry, dest)?;
+            }
+            if options.platform_loader {
+                super::platform::write_platform_loader(
+                    registry,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            if let Some(prelude) = options.prelude {
+                super::prelude::write_prelude(
+                    registry,
+                    prelude,
+                    super::helpers::Dispatch::Method,
+                    options,
+                    dest,
+                )?;
+            }
+            Ok(())
+        },
+        _ => unreachable!(),
     }
 }
 
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
-///  bindings.
-fn write_header<W>(dest: &mut W) -> io::Result<()>
+///  bindings, followed by the `GENERATED_FOR` constant if `super::GeneratorOptions::version_assertions`
+///  is set.
+fn write_header<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -44,18 +215,28 @@ where
         dest,
         r#"
         mod __gl_imports {{
-            pub use std::mem;
-            pub use std::marker::Send;
-            pub use std::os::raw;
+            pub use {std}::ffi::CStr;
+            #[allow(unused_imports)] // Only used by some commands and options
+            pub use {std}::mem;
+            pub use {std}::os::raw;
         }}
-    "#
-    )
+    "#,
+        std = super::std_path(options)
+    )?;
+    if options.version_assertions {
+        super::gen_generated_for(registry, options, dest)?;
+    }
+    Ok(())
 }
 
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_types`.
-fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_type_aliases<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -63,11 +244,14 @@ where
         dest,
         r#"
         pub mod types {{
-            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations, clippy::upper_case_acronyms)]
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry.api, options, dest)?;
+    if options.handle_types {
+        super::handles::write_handle_types(registry, dest)?;
+    }
 
     writeln!(dest, "}}")
 }
@@ -84,8 +268,23 @@ where
     Ok(())
 }
 
+/// Creates a `PFN...PROC` type alias for each command, which is its function pointer type.
+fn write_pfn_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for cmd in &registry.cmds {
+        super::gen_pfn_item(registry.api, cmd, dest)?;
+    }
+
+    Ok(())
+}
+
 /// Creates a `FnPtr` structure which contains the store for a single binding.
-fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
+///
+/// `FnPtr` is `Send` and `Sync`, so that the structure is as well unless its other fields prevent
+/// it.
+fn write_fnptr_struct_def<W>(std_path: &str, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -95,22 +294,19 @@ where
         #[allow(dead_code, missing_copy_implementations)]
         #[derive(Clone)]
         pub struct FnPtr {{
-            /// The function pointer that will be used when calling the function.
+            /// The function pointer that will be used when calling the function, which points to
+            /// `missing_fn_panic` if it hasn't been loaded.
             f: *const __gl_imports::raw::c_void,
-            /// True if the pointer points to a real function, false if points to a `panic!` fn.
-            is_loaded: bool,
         }}
 
+        #[allow(dead_code)]
         impl FnPtr {{
             /// Creates a `FnPtr` from a load attempt.
             fn new(ptr: *const __gl_imports::raw::c_void) -> FnPtr {{
                 if ptr.is_null() {{
-                    FnPtr {{
-                        f: missing_fn_panic as *const __gl_imports::raw::c_void,
-                        is_loaded: false
-                    }}
+                    FnPtr {{ f: missing_fn_panic as *const __gl_imports::raw::c_void }}
                 }} else {{
-                    FnPtr {{ f: ptr, is_loaded: true }}
+                    FnPtr {{ f: ptr }}
                 }}
             }}
 
@@ -120,7 +316,36 @@ where
             #[inline]
             #[allow(dead_code)]
             pub fn is_loaded(&self) -> bool {{
-                self.is_loaded
+                self.f != missing_fn_panic as *const __gl_imports::raw::c_void
+            }}
+        }}
+
+        // The pointer is only used to call the function it points to, which the driver allows
+        // from any thread.
+        unsafe impl {std}::marker::Send for FnPtr {{}}
+        unsafe impl {std}::marker::Sync for FnPtr {{}}
+    ",
+        std = std_path
+    )
+}
+
+/// Creates a `typed_fn_ptr` function, which converts a loaded pointer to the function pointer type
+/// of a field when `typed_fn_ptrs` is set.
+fn write_typed_fn_ptr<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Converts a pointer returned by a load function to the function pointer type `F`, or
+        /// `None` if it is null.
+        #[inline]
+        fn typed_fn_ptr<F: Copy>(ptr: *const __gl_imports::raw::c_void) -> Option<F> {{
+            if ptr.is_null() {{
+                None
+            }} else {{
+                Some(unsafe {{ __gl_imports::mem::transmute_copy(&ptr) }})
             }}
         }}
     "
@@ -129,55 +354,190 @@ where
 
 /// Creates a `panicking` module which contains one function per GL command.
 ///
-/// These functions are the mocks that are called if the real function could not be loaded.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// These functions are the mocks that are called if the real function could not be loaded. With
+/// `super::GeneratorOptions::wasm_stubs`, they say on wasm that the bindings are not available there.
+fn write_panicking_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
     writeln!(
         dest,
-        "#[inline(never)]
+        "#[allow(dead_code)]
+        #[inline(never)]
         fn missing_fn_panic() -> ! {{
+            {wasm_check}
             panic!(\"{api} function was not loaded\")
         }}",
-        api = registry.api
+        api = registry.api,
+        wasm_check = super::wasm_stubs::gen_missing_fn_check(registry, options),
     )
 }
 
 /// Creates a structure which stores all the `FnPtr` of the bindings.
 ///
-/// The name of the struct corresponds to the namespace.
-fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// The name of the struct corresponds to the namespace. With `split_extensions`, the `FnPtr` are
+/// stored in the structures of `SPLIT_FIELDS` instead.
+fn write_struct<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
     writeln!(
         dest,
         "
-        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        #[allow(non_camel_case_types, non_snake_case, dead_code, clippy::manual_non_exhaustive)]
         #[derive(Clone)]
-        pub struct {api} {{",
-        api = super::gen_struct_name(registry.api)
+        pub struct {ptrs} {{",
+        ptrs = super::gen_ptrs_struct_name(registry.api, options)
     )?;
 
-    for cmd in &registry.cmds {
+    if options.split_extensions {
+        for &(field, ty, doc) in super::SPLIT_FIELDS {
+            writeln!(dest, "/// {}\npub {}: {},", doc, field, ty)?;
+        }
+    } else {
+        write_fields(registry, options, registry.cmds.iter(), dest)?;
+    }
+    if options.interceptor {
+        super::interceptor::write_field(super::std_path(options), dest)?;
+    }
+    if options.skip_send_impl {
+        writeln!(
+            dest,
+            "_priv: {}::marker::PhantomData<*const ()>",
+            super::std_path(options)
+        )?;
+    } else {
+        writeln!(dest, "_priv: ()")?;
+    }
+
+    writeln!(dest, "}}")?;
+
+    if options.shared_struct {
+        writeln!(
+            dest,
+            "
+            /// The bindings, which share their function pointers with their clones.
+            #[derive(Clone)]
+            pub struct {api} {{
+                inner: {std}::sync::Arc<{ptrs}>,
+            }}
+
+            impl {std}::ops::Deref for {api} {{
+                type Target = {ptrs};
+
+                #[inline]
+                fn deref(&self) -> &{ptrs} {{
+                    &self.inner
+                }}
+            }}
+
+            /// Copies the function pointers first if they are shared with a clone.
+            impl {std}::ops::DerefMut for {api} {{
+                #[inline]
+                fn deref_mut(&mut self) -> &mut {ptrs} {{
+                    {std}::sync::Arc::make_mut(&mut self.inner)
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            ptrs = super::gen_ptrs_struct_name(registry.api, options),
+            std = super::std_path(options),
+        )?;
+    }
+
+    if options.split_extensions {
+        for &(field, ty, _) in super::SPLIT_FIELDS {
+            writeln!(
+                dest,
+                "
+                #[allow(non_camel_case_types, non_snake_case, dead_code)]
+                #[derive(Clone)]
+                pub struct {} {{",
+                ty
+            )?;
+            let cmds = registry.cmds.iter().filter(|cmd| {
+                super::gen_split_field(registry, options, &cmd.proto.ident) == Some(field)
+            });
+            write_fields(registry, options, cmds, dest)?;
+            writeln!(dest, "}}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a `FnPtr` field for each of `cmds`, which is written inside a structure.
+///
+/// With `typed_fn_ptrs`, the fields are an `Option` of the function pointer type of the command.
+fn write_fields<'a, I, W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    cmds: I,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    I: Iterator<Item = &'a Cmd>,
+    W: io::Write,
+{
+    for cmd in cmds {
         if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
             writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
         }
-        writeln!(dest, "pub {name}: FnPtr,", name = cmd.proto.ident)?;
+        writeln!(
+            dest,
+            "pub {name}: {ty},",
+            name = super::gen_ident(&cmd.proto.ident),
+            ty = if options.typed_fn_ptrs {
+                format!(
+                    "Option<{}>",
+                    super::gen_pfn_name(registry.api, &cmd.proto.ident)
+                )
+            } else {
+                "FnPtr".to_string()
+            },
+        )?;
     }
-    writeln!(dest, "_priv: ()")?;
-
-    writeln!(dest, "}}")
+    Ok(())
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_impl<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
     writeln!(dest,
-                  "impl {api} {{
+                  "/// Loads `symbol` with `loadfn`, or the first of `symbols` that loads if it doesn't.
+            #[inline(never)]
+            fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void,
+                             symbol: &'static [u8],
+                             symbols: &[&'static [u8]])
+                             -> *const __gl_imports::raw::c_void {{
+                // The symbols are NUL-terminated byte strings.
+                fn cstr(bytes: &'static [u8]) -> &'static __gl_imports::CStr {{
+                    unsafe {{ __gl_imports::CStr::from_bytes_with_nul_unchecked(bytes) }}
+                }}
+                let mut ptr = loadfn(cstr(symbol));
+                if ptr.is_null() {{
+                    for &sym in symbols {{
+                        ptr = loadfn(cstr(sym));
+                        if !ptr.is_null() {{ break; }}
+                    }}
+                }}
+                ptr
+            }}
+
+            impl {api} {{
             /// Load each OpenGL symbol using a custom load function. This allows for the
             /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
             ///
@@ -186,105 +546,671 @@ where
             /// ~~~
             #[allow(dead_code, unused_variables)]
             pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
-                #[inline(never)]
-                fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
-                                 symbol: &'static str,
-                                 symbols: &[&'static str])
-                                 -> *const __gl_imports::raw::c_void {{
-                    let mut ptr = loadfn(symbol);
-                    if ptr.is_null() {{
-                        for &sym in symbols {{
-                            ptr = loadfn(sym);
-                            if !ptr.is_null() {{ break; }}
-                        }}
-                    }}
-                    ptr
-                }}
-                let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
+                {api}::load_with_cstr(|symbol| loadfn(symbol.to_str().unwrap()))
+            }}
+
+            /// Load each OpenGL symbol using a custom load function that takes NUL-terminated
+            /// symbols, which can be passed to a C function without allocating a `CString`.
+            #[allow(dead_code, unused_variables, unused_mut)]
+            pub fn load_with_cstr<F>(mut loadfn: F) -> {api} where F: FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void {{
+                {cache}
+                let mut metaloadfn = |symbol: &'static [u8], symbols: &[&'static [u8]]| {{
                     do_metaloadfn(&mut loadfn, symbol, symbols)
                 }};
-                {api} {{",
-                  api = super::gen_struct_name(registry.api))?;
+                {open}",
+                  api = super::gen_struct_name(registry.api),
+                  cache = super::gen_load_cache(options),
+                  open = if options.shared_struct {
+                      format!(
+                          "{} {{ inner: {}::sync::Arc::new({} {{",
+                          super::gen_struct_name(registry.api),
+                          super::std_path(options),
+                          super::gen_ptrs_struct_name(registry.api, options),
+                      )
+                  } else {
+                      format!("{} {{", super::gen_struct_name(registry.api))
+                  })?;
+
+    let init = |cmd: &Cmd, dest: &mut W| {
+        writeln!(
+            dest,
+            "{name}: {ctor}(metaloadfn(b\"{symbol}\\0\", &[{fallbacks}])),",
+            name = super::gen_ident(&cmd.proto.ident),
+            ctor = super::gen_field_ctor(options),
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks = super::gen_fallbacks(registry, options, &cmd.proto.ident)
+                .iter()
+                .map(|symbol| format!("b\"{}\\0\"", symbol))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    };
+
+    if options.split_extensions {
+        for &(field, ty, _) in super::SPLIT_FIELDS {
+            writeln!(dest, "{}: {} {{", field, ty)?;
+            for cmd in &registry.cmds {
+                if super::gen_split_field(registry, options, &cmd.proto.ident) == Some(field) {
+                    init(cmd, dest)?;
+                }
+            }
+            writeln!(dest, "}},")?;
+        }
+    } else {
+        for cmd in &registry.cmds {
+            init(cmd, dest)?;
+        }
+    }
+
+    if options.interceptor {
+        writeln!(dest, "interceptor: None,")?;
+    }
+    if options.skip_send_impl {
+        writeln!(
+            dest,
+            "_priv: {}::marker::PhantomData",
+            super::std_path(options)
+        )?;
+    } else {
+        writeln!(dest, "_priv: ()")?;
+    }
+
+    writeln!(
+        dest,
+        "}}{close}
+        }}",
+        close = if options.shared_struct { ") }" } else { "" }
+    )?;
+
+    if options.interceptor {
+        super::interceptor::write_set_interceptor(super::std_path(options), dest)?;
+    }
+    write_validate(registry, options, dest)?;
+    write_fn_ptr(registry, options, dest)?;
+    write_command_ptr(dest)?;
+    write_version_loaded(registry, options, dest)?;
+    write_try_load_with(registry, options, dest)?;
+    write_load_with_retry(registry, dest)?;
+    write_load_subset_fns(options, dest)?;
+    if !options.gpu_timing.is_empty() {
+        super::gpu_timing::write_methods(registry, options, dest)?;
+    }
+
+    let print_err = gen_print_err(registry, options);
+    let timed_cmds = super::gpu_timing::gen_timed_cmds(registry, &options.gpu_timing);
+    let cfgs = options.debug_feature.as_ref().map(|feature| {
+        (
+            format!("not(feature = \"{}\")", feature),
+            format!("feature = \"{}\"", feature),
+        )
+    });
+    for cmd in &registry.cmds {
+        match cfgs {
+            Some((ref plain_cfg, ref debug_cfg)) => {
+                super::struct_gen::write_method(registry, options, cmd, Some(plain_cfg), dest)?;
+                write_method(
+                    registry,
+                    options,
+                    cmd,
+                    Some(debug_cfg),
+                    &print_err,
+                    &timed_cmds,
+                    dest,
+                )?;
+            },
+            None => write_method(registry, options, cmd, None, &print_err, &timed_cmds, dest)?,
+        }
+    }
+
+    writeln!(dest, "}}")?;
+
+    write_loaders(registry, options, dest)?;
+    write_command_ptrs(registry, options, dest)
+}
+
+/// Generates the statement that prints the error reported by `GetError` after a call, or an empty
+/// string if `GetError` is not part of the bindings. It is the same for every method, so that it
+/// is only generated once for the whole `impl`.
+pub fn gen_print_err(registry: &Registry, options: &super::GeneratorOptions) -> String {
+    if registry
+        .cmds
+        .iter()
+        .any(|cmd| cmd.proto.ident == "GetError")
+    {
+        format!(
+            r#"if {} {{
+                    match {} {{ 0 => (), r => {}::println!("[OpenGL] ^ GL error triggered: {{}}", r) }}
+                }}"#,
+            super::gen_field_is_loaded(
+                options,
+                &format!(
+                    "self.{}",
+                    super::gen_field_path(registry, options, "GetError")
+                )
+            ),
+            super::gen_field_call(registry, options, "GetError", ""),
+            super::std_path(options)
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Creates the method that calls `cmd` and prints the call, followed by the error reported by
+/// `GetError` if any, which is written inside the `impl` of the structure. `print_err` is the
+/// statement generated by `gen_print_err`, and the GPU time of the command is measured if it is
+/// one of `timed_cmds`. The method is written in pieces, without formatting its parts into
+/// intermediate strings.
+///
+/// The method is only compiled when the `cfg` predicate holds, if there is one.
+pub fn write_method<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    cmd: &Cmd,
+    cfg: Option<&str>,
+    print_err: &str,
+    timed_cmds: &BTreeSet<&str>,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if let Some(cfg) = cfg {
+        writeln!(dest, "#[cfg({})]", cfg)?;
+    }
+    write!(
+        dest,
+        "{}",
+        super::gen_doc_alias(registry, options, &cmd.proto.ident)
+    )?;
+
+    // The call is printed with `println!("[OpenGL] Name({:?}, {:?})" , a, b);`.
+    write!(
+        dest,
+        "#[allow(non_snake_case, unused_variables, dead_code, unused_unsafe)]
+        #[allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit, clippy::let_unit_value, clippy::let_and_return)]
+        #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+            {unsafe_open}{std}::println!(\"[OpenGL] {ident}(",
+        unsafe_open = super::UNSAFE_OPEN,
+        std = super::std_path(options),
+        name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+        params = super::gen_parameter_list(cmd, true, true),
+        return_suffix = cmd.proto.ty,
+        ident = cmd.proto.ident,
+    )?;
+    for i in 0..cmd.params.len() {
+        write!(dest, "{}{{:?}}", if i == 0 { "" } else { ", " })?;
+    }
+    write!(dest, ")\" ")?;
+    for param in &cmd.params {
+        if param.ty.contains("GLDEBUGPROC") {
+            write!(dest, ", \"<callback>\"")?;
+        } else {
+            write!(dest, ", {}", super::gen_ident(&param.ident))?;
+        }
+    }
+
+    let (timing_begin, timing_end) = if timed_cmds.contains(&cmd.proto.ident[..]) {
+        (
+            "let __gl_timing = self.gpu_timing_begin();".to_string(),
+            format!(
+                "self.gpu_timing_end(\"{}\", __gl_timing);",
+                super::gen_symbol_name(registry.api, &cmd.proto.ident)
+            ),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    writeln!(
+        dest,
+        ");
+            {before}
+            {timing_begin}
+            let r = {call};
+            {timing_end}
+            {after}
+            {print_err}
+            r{unsafe_close}
+        }}",
+        unsafe_close = super::UNSAFE_CLOSE,
+        timing_begin = timing_begin,
+        timing_end = timing_end,
+        before = if options.interceptor {
+            super::interceptor::gen_before(registry, cmd)
+        } else {
+            String::new()
+        },
+        call = super::gen_field_call(
+            registry,
+            options,
+            &cmd.proto.ident,
+            &super::gen_parameter_list(cmd, true, false).to_string()
+        ),
+        after = if options.interceptor {
+            super::interceptor::gen_after(registry, cmd)
+        } else {
+            String::new()
+        },
+        print_err = if cmd.proto.ident != "GetError" {
+            print_err
+        } else {
+            ""
+        },
+    )
+}
+
+/// Creates the `validate` method, which is written inside the `impl` of the structure.
+fn write_validate<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Checks that every command of `required` has been loaded, returning the symbols of
+        /// the commands that have not.
+        ///
+        /// Commands can be named as in the bindings (`\"Clear\"`) or by their symbol
+        /// (`\"glClear\"`). Names that are not commands of the bindings are ignored.
+        #[allow(dead_code, clippy::match_single_binding)]
+        pub fn validate(&self, required: &[&str]) -> Result<(), {std}::vec::Vec<&'static str>> {{
+            let mut missing = {std}::vec::Vec::new();
+            for &name in required {{
+                let command: Option<(&'static str, bool)> = match name {{",
+        std = super::std_path(options),
+    )?;
 
     for cmd in &registry.cmds {
         writeln!(
             dest,
-            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}])),",
+            "\"{name}\" | \"{symbol}\" => Some((\"{symbol}\", {is_loaded})),",
             name = cmd.proto.ident,
+            is_loaded = super::gen_field_is_loaded(
+                options,
+                &format!(
+                    "self.{}",
+                    super::gen_field_path(registry, options, &cmd.proto.ident)
+                )
+            ),
             symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
-            fallbacks = match registry.aliases.get(&cmd.proto.ident) {
-                Some(fbs) => fbs
-                    .iter()
-                    .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, &name)))
-                    .collect::<Vec<_>>()
-                    .join(", "),
-                None => format!(""),
-            },
-        )?
+        )?;
     }
-    writeln!(dest, "_priv: ()")?;
 
     writeln!(
         dest,
-        "}}
+        "_ => None,
+                }};
+                if let Some((symbol, false)) = command {{
+                    missing.push(symbol);
+                }}
+            }}
+            if missing.is_empty() {{ Ok(()) }} else {{ Err(missing) }}
         }}"
+    )
+}
+
+/// Creates the `fn_ptr` method, which is written inside the `impl` of the structure.
+fn write_fn_ptr<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Returns the pointer that the command `name` has been loaded from, or `None` if it
+        /// has not been loaded or is not part of the bindings.
+        ///
+        /// Commands can be named as in the bindings (`\"Clear\"`) or by their symbol
+        /// (`\"glClear\"`).
+        #[allow(dead_code, clippy::match_single_binding)]
+        pub fn fn_ptr(&self, name: &str) -> Option<*const __gl_imports::raw::c_void> {{
+            match name {{"
     )?;
 
     for cmd in &registry.cmds {
-        let idents = super::gen_parameters(cmd, true, false);
-        let typed_params = super::gen_parameters(cmd, false, true);
-        let println = format!(
-            "println!(\"[OpenGL] {}({})\" {});",
-            cmd.proto.ident,
-            (0..idents.len())
-                .map(|_| "{:?}".to_string())
+        writeln!(
+            dest,
+            "\"{name}\" | \"{symbol}\" => {ptr},",
+            name = cmd.proto.ident,
+            ptr = super::gen_field_ptr(
+                options,
+                &format!(
+                    "self.{}",
+                    super::gen_field_path(registry, options, &cmd.proto.ident)
+                ),
+                "__gl_imports::raw",
+            ),
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "_ => None,
+            }}
+        }}"
+    )
+}
+
+/// Creates the `command_ptr` method, which is written inside the `impl` of the structure.
+fn write_command_ptr<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Returns the pointer that the command with the symbol `name`, e.g. `\"glClear\"`,
+        /// has been loaded from, or `None` if it has not been loaded or is not part of the
+        /// bindings.
+        ///
+        /// The symbols of the aliases of a command resolve to that command. The lookup doesn't
+        /// allocate, so this can serve a C `GetProcAddress` callback.
+        #[allow(dead_code)]
+        pub fn command_ptr(&self, name: &str) -> Option<*const __gl_imports::raw::c_void> {{
+            match command_ptrs::SYMBOLS.binary_search_by(|&(symbol, _)| symbol.cmp(name)) {{
+                Ok(index) => (command_ptrs::SYMBOLS[index].1)(self),
+                Err(_) => None,
+            }}
+        }}"
+    )
+}
+
+/// Creates a method for each version of the registry, which is written inside the `impl` of the
+/// structure. The method is named after the feature without its API prefix, e.g.
+/// `version_4_3_loaded` for `GL_VERSION_4_3` or `es_version_3_0_loaded` for `GL_ES_VERSION_3_0`.
+fn write_version_loaded<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for version in &registry.versions {
+        let checks = version
+            .cmds
+            .iter()
+            .map(|cmd| {
+                super::gen_field_is_loaded(
+                    options,
+                    &format!("self.{}", super::gen_field_path(registry, options, cmd)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(
+            dest,
+            "/// Returns `true` if every command introduced by version {major}.{minor} has been
+            /// loaded.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn {name}_loaded(&self) -> bool {{
+                {checks}
+            }}",
+            name = version.name.splitn(2, '_').last().unwrap().to_lowercase(),
+            major = version.number.0,
+            minor = version.number.1,
+            checks = if checks.is_empty() {
+                "true".to_string()
+            } else {
+                checks.join(" && ")
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `try_load_with` method, which is written inside the `impl` of the structure.
+fn write_try_load_with<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Load each OpenGL symbol using a custom load function that can fail, failing unless
+        /// every command introduced by the versions of the bindings has been loaded. Extension
+        /// commands are optional.
+        ///
+        /// Loading stops at the first error returned by `loadfn`, which is returned as
+        /// `TryLoadError::Loader`. Otherwise `TryLoadError::Missing` lists the symbols of the
+        /// missing commands, which usually means that `loadfn` is broken or that the context is
+        /// older than the bindings.
+        #[allow(dead_code)]
+        pub fn try_load_with<F, E>(mut loadfn: F) -> Result<{api}, TryLoadError<E>>
+            where F: FnMut(&'static str) -> Result<*const __gl_imports::raw::c_void, E>
+        {{
+            let mut error = None;
+            let gl = {api}::load_with(|symbol| {{
+                if error.is_none() {{
+                    match loadfn(symbol) {{
+                        Ok(ptr) => return ptr,
+                        Err(e) => error = Some(e),
+                    }}
+                }}
+                {std}::ptr::null()
+            }});
+            if let Some(e) = error {{
+                return Err(TryLoadError::Loader(e));
+            }}
+            gl.validate(loaders::CORE).map(|()| gl).map_err(TryLoadError::Missing)
+        }}",
+        api = super::gen_struct_name(registry.api),
+        std = super::std_path(options),
+    )
+}
+
+/// Creates the `load_with_retry` method, which is written inside the `impl` of the structure.
+fn write_load_with_retry<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Load each OpenGL symbol using a custom load function, calling it again up to
+        /// `retries` times for a symbol it returns null for, for platform loaders that fail
+        /// transiently.
+        #[allow(dead_code)]
+        pub fn load_with_retry<F>(mut loadfn: F, retries: u32) -> {api}
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            {api}::load_with(|symbol| {{
+                let mut ptr = loadfn(symbol);
+                for _ in 0..retries {{
+                    if !ptr.is_null() {{ break; }}
+                    ptr = loadfn(symbol);
+                }}
+                ptr
+            }})
+        }}",
+        api = super::gen_struct_name(registry.api)
+    )
+}
+
+/// Creates the `load_subset_with` and `load_extension_with` methods, which are written inside the
+/// `impl` of the structure.
+///
+/// The methods look up commands in the `loaders` module created by `write_loaders`.
+fn write_load_subset_fns<W>(options: &super::GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"/// Loads the given commands using a custom load function, leaving the other commands in
+        /// their current state.
+        ///
+        /// Commands can be named as in the bindings (`"Clear"`) or by their symbol (`"glClear"`).
+        /// Returns the names that don't match any command of the bindings.
+        #[allow(dead_code)]
+        pub fn load_subset_with<'a, F>(&mut self, mut loadfn: F, commands: &[&'a str]) -> Result<(), {std}::vec::Vec<&'a str>>
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            let mut unknown = {std}::vec::Vec::new();
+            for &name in commands {{
+                match loaders::COMMANDS.iter().find(|&&(ident, symbol, _)| name == ident || name == symbol) {{
+                    Some(&(_, _, load)) => load(self, &mut |symbol| loadfn(symbol.to_str().unwrap())),
+                    None => unknown.push(name),
+                }}
+            }}
+            if unknown.is_empty() {{ Ok(()) }} else {{ Err(unknown) }}
+        }}
+
+        /// Loads the commands required by the extension `name` using a custom load function,
+        /// leaving the other commands in their current state.
+        ///
+        /// Returns `false` if the extension is not part of the bindings.
+        #[allow(dead_code)]
+        pub fn load_extension_with<F>(&mut self, loadfn: F, name: &str) -> bool
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            match loaders::EXTENSIONS.iter().find(|&&(extension, _)| name == extension) {{
+                Some(&(_, commands)) => self.load_subset_with(loadfn, commands).is_ok(),
+                None => false,
+            }}
+        }}"#,
+        std = super::std_path(options),
+    )
+}
+
+/// Creates a `loaders` module, which maps the name of each command to a function that loads it
+/// into the structure, and the name of each extension to its commands.
+fn write_loaders<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(unused_imports)]
+        mod loaders {{
+            use super::__gl_imports::{{raw, CStr}};
+            use super::{{{api}, {ctor_item}, do_metaloadfn}};
+
+            /// The name and symbol of each command, with a function that loads it.
+            #[allow(clippy::type_complexity)]
+            pub static COMMANDS: &[(&str, &str, fn(&mut {api}, &mut dyn FnMut(&'static CStr) -> *const raw::c_void))] = &[",
+        api = super::gen_struct_name(registry.api),
+        ctor_item = if options.typed_fn_ptrs {
+            "typed_fn_ptr"
+        } else {
+            "FnPtr"
+        },
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "(\"{name}\", \"{symbol}\", |gl, loadfn| gl.{field} = {ctor}(do_metaloadfn(loadfn, b\"{symbol}\\0\", &[{fallbacks}]))),",
+            name = cmd.proto.ident,
+            ctor = super::gen_field_ctor(options),
+            field = super::gen_field_path(registry, options, &cmd.proto.ident),
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks = super::gen_fallbacks(registry, options, &cmd.proto.ident)
+                .iter()
+                .map(|symbol| format!("b\"{}\\0\"", symbol))
                 .collect::<Vec<_>>()
                 .join(", "),
-            idents
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "];
+
+            /// The name of each extension, with the commands it requires.
+            pub static EXTENSIONS: &[(&str, &[&str])] = &["
+    )?;
+
+    for extension in &registry.extensions {
+        writeln!(
+            dest,
+            "(\"{name}\", &[{cmds}]),",
+            name = extension.name,
+            cmds = extension
+                .cmds
                 .iter()
-                .zip(typed_params.iter())
-                .map(|(name, ty)| if ty.contains("GLDEBUGPROC") {
-                    format!(", \"<callback>\"")
-                } else {
-                    format!(", {}", name)
-                })
+                .map(|cmd| format!("\"{}\"", cmd))
                 .collect::<Vec<_>>()
-                .concat()
-        );
-
-        writeln!(dest,
-                      "#[allow(non_snake_case, unused_variables, dead_code)]
-            #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
-                {println}
-                let r = __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
-                    (self.{name}.f)({idents});
-                {print_err}
-                r
-            }}",
-                      name = cmd.proto.ident,
-                      params = super::gen_parameters(cmd, true, true).join(", "),
-                      typed_params = typed_params.join(", "),
-                      return_suffix = cmd.proto.ty,
-                      idents = idents.join(", "),
-                      println = println,
-                      print_err = if cmd.proto.ident != "GetError" &&
-                                     registry
-                                         .cmds
-                                         .iter()
-                                         .find(|cmd| cmd.proto.ident == "GetError")
-                                         .is_some() {
-                          format!(r#"match __gl_imports::mem::transmute::<_, extern "system" fn() -> u32>
-                    (self.GetError.f)() {{ 0 => (), r => println!("[OpenGL] ^ GL error triggered: {{}}", r) }}"#)
-                      } else {
-                          format!("")
-                      })?
+                .join(", "),
+        )?;
     }
 
     writeln!(
         dest,
-        "}}
+        "];
+
+            /// The symbol of each command introduced by the versions of the bindings.
+            pub static CORE: &[&str] = &["
+    )?;
+
+    for cmd in super::gen_core_cmds(registry) {
+        writeln!(dest, "\"{}\",", super::gen_symbol_name(registry.api, cmd))?;
+    }
+
+    writeln!(
+        dest,
+        "];
+        }}"
+    )
+}
+
+/// Creates a `command_ptrs` module with a sorted table of the symbols of the commands and of
+/// their aliases, used by the `command_ptr` method.
+fn write_command_ptrs<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(unused_imports)]
+        mod command_ptrs {{
+            use super::__gl_imports::raw;
+            use super::{api};
 
-        unsafe impl __gl_imports::Send for {api} {{}}",
+            /// Each symbol in sorted order, with a function returning the pointer that its command
+            /// has been loaded from.
+            #[allow(clippy::type_complexity)]
+            pub static SYMBOLS: &[(&str, fn(&{api}) -> Option<*const raw::c_void>)] = &[",
         api = super::gen_struct_name(registry.api)
+    )?;
+
+    for (symbol, name) in super::gen_symbol_map(registry) {
+        writeln!(
+            dest,
+            "(\"{symbol}\", |gl| {ptr}),",
+            symbol = symbol,
+            ptr = super::gen_field_ptr(
+                options,
+                &format!("gl.{}", super::gen_field_path(registry, options, name)),
+                "raw",
+            ),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "];
+        }}"
     )
 }
@@ -0,0 +1,184 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The benchmark of the dispatch of the commands emitted when
+//! `GeneratorOptions::dispatch_bench` is set.
+
+use super::helpers::Dispatch;
+use registry::{Cmd, Registry};
+use std::io;
+
+/// The commands that are benchmarked when the registry has them, which are called often by
+/// applications and take a few scalar parameters.
+const BENCH_CMDS: &[&str] = &[
+    "Clear",
+    "Viewport",
+    "Enable",
+    "BindBuffer",
+    "BindTexture",
+    "UseProgram",
+    "Uniform1f",
+    "DrawArrays",
+];
+
+/// Selects the commands to benchmark, which are those of `BENCH_CMDS`, or the first commands
+/// introduced by the versions of the registry if it has none of them, such as for EGL.
+fn gen_bench_cmds(registry: &Registry) -> Vec<&Cmd> {
+    let core_cmds = super::gen_core_cmds(registry);
+    let cmds: Vec<_> = registry
+        .cmds
+        .iter()
+        .filter(|cmd| core_cmds.contains(&cmd.proto.ident[..]))
+        .collect();
+
+    let mut bench_cmds: Vec<_> = BENCH_CMDS
+        .iter()
+        .filter_map(|&name| cmds.iter().find(|cmd| cmd.proto.ident == name).cloned())
+        .collect();
+    if bench_cmds.is_empty() {
+        bench_cmds = cmds.into_iter().take(BENCH_CMDS.len()).collect();
+    }
+    bench_cmds
+}
+
+/// Writes the `dispatch_bench` module, compiled only for tests, which loads no-op versions of a
+/// representative set of commands and measures the time of calling them through the bindings.
+/// Its `run` function has the same signature for every generator, so that the results of the
+/// global and struct generators can be compared, and it has an ignored `dispatch_overhead` test
+/// printing them.
+pub fn write_dispatch_bench<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let cmds = gen_bench_cmds(registry);
+
+    writeln!(
+        dest,
+        "#[cfg(test)]
+        pub mod dispatch_bench {{
+            #![allow(non_snake_case, unused_variables, dead_code, clippy::unused_unit, clippy::zero_ptr)]
+
+            use super::{{__gl_imports, types}};
+            use {std}::time::{{Duration, Instant}};
+            use {std}::vec::Vec;",
+        std = std_path,
+    )?;
+
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "extern \"system\" fn {name}({params}) -> {return_suffix} {{
+                unsafe {{ __gl_imports::mem::zeroed() }}
+            }}",
+            name = cmd.proto.ident,
+            params = super::gen_parameter_list(cmd, true, true),
+            return_suffix = cmd.proto.ty,
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "/// Returns the no-op version of the command named `symbol`, or null if it is not
+        /// benchmarked.
+        fn load_noop(symbol: &str) -> *const __gl_imports::raw::c_void {{
+            match symbol {{"
+    )?;
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "\"{symbol}\" => {name} as *const __gl_imports::raw::c_void,",
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            name = cmd.proto.ident,
+        )?;
+    }
+    writeln!(
+        dest,
+        "_ => 0 as *const _,
+            }}
+        }}
+
+        /// Returns the mean time of `iterations` calls of `f`.
+        fn measure<F: FnMut()>(iterations: u32, mut f: F) -> Duration {{
+            let start = Instant::now();
+            for _ in 0..iterations {{
+                f();
+            }}
+            start.elapsed() / iterations.max(1)
+        }}"
+    )?;
+
+    let (target, label, load) = match dispatch {
+        Dispatch::Free => (
+            "super::".to_string(),
+            "global",
+            "super::load_with(load_noop);".to_string(),
+        ),
+        Dispatch::Method => (
+            "gl.".to_string(),
+            "struct",
+            format!(
+                "let gl = super::{api}::load_with(load_noop);",
+                api = super::gen_struct_name(registry.api)
+            ),
+        ),
+    };
+
+    writeln!(
+        dest,
+        "/// Loads the no-op versions of the benchmarked commands, and returns the mean time of
+        /// calling each of them `iterations` times through the bindings, with zeroed arguments.
+        pub fn run(iterations: u32) -> Vec<(&'static str, Duration)> {{
+            {load}
+            {std}::vec![",
+        load = load,
+        std = std_path,
+    )?;
+    for cmd in &cmds {
+        let args: Vec<_> = cmd
+            .params
+            .iter()
+            .map(|_| "__gl_imports::mem::zeroed()")
+            .collect();
+        writeln!(
+            dest,
+            "(\"{name}\", measure(iterations, || unsafe {{ {target}{name}({args}); }})),",
+            name = cmd.proto.ident,
+            target = target,
+            args = args.join(", "),
+        )?;
+    }
+    writeln!(
+        dest,
+        "]
+        }}
+
+        /// Prints the results of `run`, with
+        /// `cargo test --release dispatch_overhead -- --ignored --nocapture`.
+        #[test]
+        #[ignore]
+        fn dispatch_overhead() {{
+            for (name, time) in run(10_000_000) {{
+                {std}::println!(\"{label} {{}}: {{:?}} per call\", name, time);
+            }}
+        }}
+    }}",
+        label = label,
+        std = std_path,
+    )
+}
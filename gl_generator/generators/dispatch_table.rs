@@ -0,0 +1,493 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `dispatch` module emitted when `GeneratorOptions::dispatch_table` is set.
+//!
+//! Commands are called by a `CommandId` with their arguments as a slice of `Value`s, so that a
+//! scripting language can drive the bindings without glue code for each command.
+
+use super::helpers::Dispatch;
+use registry::{Cmd, Registry};
+use std::io;
+
+/// The types, after `types::`, that are aliases of a primitive integer, float or pointer type on
+/// every platform.
+const SCALAR_TYPES: &[&str] = &[
+    "BOOL",
+    "Bool",
+    "DWORD",
+    "EGLAttrib",
+    "EGLAttribKHR",
+    "EGLBoolean",
+    "EGLClientBuffer",
+    "EGLConfig",
+    "EGLContext",
+    "EGLDeviceEXT",
+    "EGLDisplay",
+    "EGLImage",
+    "EGLImageKHR",
+    "EGLNativeFileDescriptorKHR",
+    "EGLOutputLayerEXT",
+    "EGLOutputPortEXT",
+    "EGLStreamKHR",
+    "EGLSurface",
+    "EGLSync",
+    "EGLSyncKHR",
+    "EGLSyncNV",
+    "EGLenum",
+    "FLOAT",
+    "Font",
+    "GLXContext",
+    "GLXContextID",
+    "GLXDrawable",
+    "GLXFBConfig",
+    "GLXFBConfigSGIX",
+    "GLXPbuffer",
+    "GLXPbufferSGIX",
+    "GLXPixmap",
+    "GLXVideoCaptureDeviceNV",
+    "GLXVideoDeviceNV",
+    "GLXVideoSourceSGIX",
+    "GLXWindow",
+    "GLbitfield",
+    "GLboolean",
+    "GLbyte",
+    "GLclampd",
+    "GLclampf",
+    "GLclampx",
+    "GLdouble",
+    "GLeglImageOES",
+    "GLenum",
+    "GLfixed",
+    "GLfloat",
+    "GLhalfNV",
+    "GLhandleARB",
+    "GLint",
+    "GLint64",
+    "GLint64EXT",
+    "GLintptr",
+    "GLintptrARB",
+    "GLshort",
+    "GLsizei",
+    "GLsizeiptr",
+    "GLsizeiptrARB",
+    "GLsync",
+    "GLubyte",
+    "GLuint",
+    "GLuint64",
+    "GLuint64EXT",
+    "GLushort",
+    "GLvdpauSurfaceNV",
+    "HANDLE",
+    "HDC",
+    "HGLRC",
+    "HGPUNV",
+    "HPBUFFERARB",
+    "HPBUFFEREXT",
+    "HPVIDEODEV",
+    "HVIDEOINPUTDEVICENV",
+    "HVIDEOOUTPUTDEVICENV",
+    "INT",
+    "INT64",
+    "LPCSTR",
+    "LPVOID",
+    "Pixmap",
+    "UINT",
+    "Window",
+];
+
+/// Returns `true` if values of the Rust type `ty` can be converted from and to a `Value`.
+fn is_scalar(ty: &str) -> bool {
+    if ty == "()" || ty == "i64" || ty.starts_with("*const ") || ty.starts_with("*mut ") {
+        return true;
+    }
+    if ty.starts_with("__gl_imports::raw::c_") {
+        return ty != "__gl_imports::raw::c_void";
+    }
+    ty.starts_with("types::") && SCALAR_TYPES.contains(&&ty["types::".len()..])
+}
+
+/// Returns `true` if the command can be called through the dispatch table, which is not the case
+/// for commands taking callbacks or types that the application defines.
+fn is_dispatchable(cmd: &Cmd) -> bool {
+    is_scalar(&cmd.proto.ty) && cmd.params.iter().all(|param| is_scalar(&param.ty))
+}
+
+/// The conversions of `types::GLsync` when it is an opaque structure.
+const SYNC_VALUES: &str = "
+            impl FromValue for super::types::GLsync {
+                #[inline]
+                fn from_value(value: Value) -> Option<super::types::GLsync> {
+                    match value {
+                        Value::Ptr(ptr) => Some(super::types::GLsync::from_ptr(ptr as *const _)),
+                        _ => None,
+                    }
+                }
+            }
+
+            impl IntoValue for super::types::GLsync {
+                #[inline]
+                fn into_value(self) -> Value {
+                    Value::Ptr(self.as_ptr() as *const raw::c_void)
+                }
+            }";
+
+/// Returns `true` if any of the commands takes or returns a `GLsync`.
+fn uses_sync(cmds: &[&Cmd]) -> bool {
+    cmds.iter().any(|cmd| {
+        cmd.proto.ty == "types::GLsync"
+            || cmd.params.iter().any(|param| param.ty == "types::GLsync")
+    })
+}
+
+/// Writes a `dispatch` module with the `Value`, `CommandId` and `DispatchError` types, and a
+/// `dispatch` function, or method for `Dispatch::Method`, that calls a command by its id.
+pub fn write_dispatch_table<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let std_path = super::std_path(options);
+    let cmds: Vec<&Cmd> = registry
+        .cmds
+        .iter()
+        .filter(|cmd| is_dispatchable(cmd))
+        .collect();
+    if cmds.is_empty() {
+        return Ok(());
+    }
+    let api = super::gen_struct_name(registry.api);
+
+    writeln!(
+        dest,
+        r#"
+        /// Calling commands by id with type-erased arguments, for scripting bridges.
+        #[allow(dead_code)]
+        pub mod dispatch {{
+            use super::__gl_imports::raw;
+
+            /// A value of one of the primitive types that the commands take and return.
+            #[derive(Copy, Clone, Debug, PartialEq)]
+            pub enum Value {{
+                /// The return value of the commands that return nothing.
+                Void,
+                I8(i8),
+                U8(u8),
+                I16(i16),
+                U16(u16),
+                I32(i32),
+                U32(u32),
+                I64(i64),
+                U64(u64),
+                Isize(isize),
+                Usize(usize),
+                F32(f32),
+                F64(f64),
+                /// Any pointer or handle.
+                Ptr(*const raw::c_void),
+            }}
+
+            /// Converts a `Value` to the type of a parameter.
+            ///
+            /// Numbers are converted with `as`, so that for example an `F64` coming from a
+            /// scripting language can be passed to a `GLint` parameter.
+            pub trait FromValue: Sized {{
+                fn from_value(value: Value) -> Option<Self>;
+            }}
+
+            /// Converts the return value of a command to a `Value`.
+            pub trait IntoValue {{
+                fn into_value(self) -> Value;
+            }}
+
+            macro_rules! numeric_value {{
+                ($($ty:ident => $variant:ident),*) => {{$(
+                    impl FromValue for $ty {{
+                        #[inline]
+                        fn from_value(value: Value) -> Option<$ty> {{
+                            match value {{
+                                Value::I8(v) => Some(v as $ty),
+                                Value::U8(v) => Some(v as $ty),
+                                Value::I16(v) => Some(v as $ty),
+                                Value::U16(v) => Some(v as $ty),
+                                Value::I32(v) => Some(v as $ty),
+                                Value::U32(v) => Some(v as $ty),
+                                Value::I64(v) => Some(v as $ty),
+                                Value::U64(v) => Some(v as $ty),
+                                Value::Isize(v) => Some(v as $ty),
+                                Value::Usize(v) => Some(v as $ty),
+                                Value::F32(v) => Some(v as $ty),
+                                Value::F64(v) => Some(v as $ty),
+                                Value::Void | Value::Ptr(_) => None,
+                            }}
+                        }}
+                    }}
+
+                    impl IntoValue for $ty {{
+                        #[inline]
+                        fn into_value(self) -> Value {{
+                            Value::$variant(self)
+                        }}
+                    }}
+                )*}};
+            }}
+
+            numeric_value!(
+                i8 => I8, u8 => U8, i16 => I16, u16 => U16, i32 => I32, u32 => U32,
+                i64 => I64, u64 => U64, isize => Isize, usize => Usize, f32 => F32, f64 => F64
+            );
+
+            impl<T> FromValue for *const T {{
+                #[inline]
+                fn from_value(value: Value) -> Option<*const T> {{
+                    match value {{
+                        Value::Ptr(ptr) => Some(ptr as *const T),
+                        _ => None,
+                    }}
+                }}
+            }}
+
+            impl<T> FromValue for *mut T {{
+                #[inline]
+                fn from_value(value: Value) -> Option<*mut T> {{
+                    match value {{
+                        Value::Ptr(ptr) => Some(ptr as *mut T),
+                        _ => None,
+                    }}
+                }}
+            }}
+
+            impl IntoValue for () {{
+                #[inline]
+                fn into_value(self) -> Value {{
+                    Value::Void
+                }}
+            }}
+
+            impl<T> IntoValue for *const T {{
+                #[inline]
+                fn into_value(self) -> Value {{
+                    Value::Ptr(self as *const raw::c_void)
+                }}
+            }}
+
+            impl<T> IntoValue for *mut T {{
+                #[inline]
+                fn into_value(self) -> Value {{
+                    Value::Ptr(self as *const raw::c_void)
+                }}
+            }}
+            {sync_values}
+
+            /// The error returned by `dispatch` when the arguments don't match the command.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+            pub enum DispatchError {{
+                /// The number of arguments is not the number of parameters of the command.
+                Arity {{ expected: usize, found: usize }},
+                /// The argument at `index` cannot be converted to the type of its parameter.
+                Type {{ index: usize }},
+            }}
+
+            impl {std}::fmt::Display for DispatchError {{
+                fn fmt(&self, f: &mut {std}::fmt::Formatter) -> {std}::fmt::Result {{
+                    match *self {{
+                        DispatchError::Arity {{ expected, found }} => {{
+                            write!(f, "expected {{}} arguments, found {{}}", expected, found)
+                        }},
+                        DispatchError::Type {{ index }} => {{
+                            write!(f, "argument {{}} has the wrong type", index)
+                        }},
+                    }}
+                }}
+            }}
+
+            impl {std}::error::Error for DispatchError {{}}
+
+            #[inline]
+            fn check_arity(args: &[Value], expected: usize) -> Result<(), DispatchError> {{
+                if args.len() == expected {{
+                    Ok(())
+                }} else {{
+                    Err(DispatchError::Arity {{ expected, found: args.len() }})
+                }}
+            }}
+
+            #[inline]
+            fn arg<T: FromValue>(args: &[Value], index: usize) -> Result<T, DispatchError> {{
+                T::from_value(args[index]).ok_or(DispatchError::Type {{ index }})
+            }}
+
+            /// The commands that can be called through `dispatch`, which are all the commands
+            /// except those taking callbacks or types defined by the application.
+            #[allow(non_camel_case_types)]
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            #[repr(u16)]
+            pub enum CommandId {{"#,
+        std = std_path,
+        sync_values = if options.opaque_sync && uses_sync(&cmds) {
+            SYNC_VALUES
+        } else {
+            ""
+        },
+    )?;
+
+    for cmd in &cmds {
+        writeln!(dest, "{},", super::gen_ident(&cmd.proto.ident))?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        /// All the ids, in the order of their index.
+        pub const COMMANDS: &[CommandId] = &["
+    )?;
+    for cmd in &cmds {
+        writeln!(dest, "CommandId::{},", super::gen_ident(&cmd.proto.ident))?;
+    }
+
+    writeln!(dest, "];\n\nconst SYMBOLS: &[&str] = &[")?;
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "\"{}\",",
+            super::gen_symbol_name(registry.api, &cmd.proto.ident)
+        )?;
+    }
+
+    writeln!(dest, "];\n\nconst ARITIES: &[u8] = &[")?;
+    for cmd in &cmds {
+        writeln!(dest, "{},", cmd.params.len())?;
+    }
+
+    writeln!(
+        dest,
+        r#"];
+
+            impl CommandId {{
+                /// Returns the id at `index`, the value of `id as usize`.
+                #[inline]
+                pub fn from_index(index: usize) -> Option<CommandId> {{
+                    COMMANDS.get(index).cloned()
+                }}
+
+                /// Returns the id of the command with the symbol `name`, e.g. `"glClear"`.
+                pub fn from_name(name: &str) -> Option<CommandId> {{
+                    SYMBOLS.iter().position(|&symbol| symbol == name).map(|index| COMMANDS[index])
+                }}
+
+                /// The symbol of the command.
+                #[inline]
+                pub fn name(self) -> &'static str {{
+                    SYMBOLS[self as usize]
+                }}
+
+                /// The number of arguments that the command takes.
+                #[inline]
+                pub fn arity(self) -> usize {{
+                    ARITIES[self as usize] as usize
+                }}
+            }}
+        "#
+    )?;
+
+    let (thunk_ty_params, thunk_params, callee) = match dispatch {
+        Dispatch::Free => (String::new(), String::new(), "super::super::"),
+        Dispatch::Method => (
+            format!("&super::{}, ", api),
+            format!("gl: &super::super::{}, ", api),
+            "gl.",
+        ),
+    };
+
+    writeln!(
+        dest,
+        "pub type Thunk = unsafe fn({}&[Value]) -> Result<Value, DispatchError>;
+
+        /// A thunk for each command, indexed by `CommandId`.
+        pub static THUNKS: [Thunk; {}] = [",
+        thunk_ty_params,
+        cmds.len(),
+    )?;
+    for cmd in &cmds {
+        writeln!(dest, "thunks::{},", super::gen_ident(&cmd.proto.ident))?;
+    }
+    writeln!(
+        dest,
+        "];
+
+        #[allow(non_snake_case, clippy::unit_arg, unused_unsafe)]
+        mod thunks {{
+            use super::{{arg, check_arity, DispatchError, IntoValue, Value}};"
+    )?;
+
+    for cmd in &cmds {
+        let args: Vec<_> = (0..cmd.params.len())
+            .map(|index| format!("arg(args, {})?", index))
+            .collect();
+        writeln!(
+            dest,
+            "pub unsafe fn {name}({thunk_params}args: &[Value]) -> Result<Value, DispatchError> {{
+                check_arity(args, {arity})?;
+                Ok(IntoValue::into_value({unsafe_open}{callee}{cmd_name}({args}){unsafe_close}))
+            }}",
+            unsafe_open = super::UNSAFE_OPEN,
+            unsafe_close = super::UNSAFE_CLOSE,
+            name = super::gen_ident(&cmd.proto.ident),
+            cmd_name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+            thunk_params = thunk_params,
+            arity = cmd.params.len(),
+            callee = callee,
+            args = args.join(", "),
+        )?;
+    }
+
+    writeln!(dest, "}}\n}}")?;
+
+    let doc = "/// Calls the command `id` with `args`, converting each argument to the type of its
+        /// parameter.";
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "{doc}
+            #[allow(dead_code, clippy::missing_safety_doc)]
+            #[inline]
+            pub unsafe fn dispatch(id: dispatch::CommandId, args: &[dispatch::Value]) -> Result<dispatch::Value, dispatch::DispatchError> {{
+                {unsafe_open}dispatch::THUNKS[id as usize](args){unsafe_close}
+            }}",
+            unsafe_open = super::UNSAFE_OPEN,
+            unsafe_close = super::UNSAFE_CLOSE,
+            doc = doc,
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {doc}
+                #[allow(dead_code, clippy::missing_safety_doc)]
+                #[inline]
+                pub unsafe fn dispatch(&self, id: dispatch::CommandId, args: &[dispatch::Value]) -> Result<dispatch::Value, dispatch::DispatchError> {{
+                    {unsafe_open}dispatch::THUNKS[id as usize](self, args){unsafe_close}
+                }}
+            }}",
+            unsafe_open = super::UNSAFE_OPEN,
+            unsafe_close = super::UNSAFE_CLOSE,
+            api = api,
+            doc = doc,
+        ),
+    }
+}
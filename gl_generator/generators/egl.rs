@@ -0,0 +1,131 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The two-phase loading of the EGL bindings of the struct generators.
+
+use registry::Registry;
+use std::collections::BTreeSet;
+use std::io;
+
+/// The commands of the versions of EGL that can be loaded and called before a display has been
+/// initialized.
+const BOOTSTRAP_CMDS: &[&str] = &[
+    "BindAPI",
+    "GetCurrentContext",
+    "GetCurrentDisplay",
+    "GetCurrentSurface",
+    "GetDisplay",
+    "GetError",
+    "GetPlatformDisplay",
+    "GetProcAddress",
+    "Initialize",
+    "QueryAPI",
+    "QueryString",
+    "ReleaseThread",
+];
+
+/// The client extensions, whose commands `eglGetProcAddress` returns before a display has been
+/// initialized, besides the platform extensions such as `EGL_EXT_platform_wayland`.
+const CLIENT_EXTENSIONS: &[&str] = &[
+    "EGL_EXT_client_extensions",
+    "EGL_EXT_device_base",
+    "EGL_EXT_device_enumeration",
+    "EGL_EXT_device_query",
+    "EGL_KHR_client_get_all_proc_addresses",
+    "EGL_KHR_debug",
+];
+
+/// Returns whether `extension` is a client extension of EGL, as opposed to a display extension.
+fn is_client_extension(extension: &str) -> bool {
+    CLIENT_EXTENSIONS.contains(&extension) || extension.contains("_platform_")
+}
+
+/// Generates the symbols of the commands that `load_bootstrap_with` loads, which are the commands
+/// of `BOOTSTRAP_CMDS` introduced by the versions of the registry and those of its client
+/// extensions.
+fn gen_bootstrap_symbols(registry: &Registry) -> BTreeSet<String> {
+    let core_cmds = super::gen_core_cmds(registry);
+    let extension_cmds = registry
+        .extensions
+        .iter()
+        .filter(|extension| is_client_extension(&extension.name))
+        .flat_map(|extension| extension.cmds.iter().map(|cmd| &cmd[..]));
+
+    BOOTSTRAP_CMDS
+        .iter()
+        .cloned()
+        .filter(|cmd| core_cmds.contains(cmd))
+        .chain(extension_cmds)
+        .map(|cmd| super::gen_symbol_name(registry.api, cmd))
+        .collect()
+}
+
+/// Writes the `load_bootstrap_with` method, which loads the commands that can be used before
+/// `eglInitialize`, and the `complete_load_with` method, which loads the others afterwards.
+///
+/// `complete_load_with` uses the `loaders` module of the struct generators.
+pub fn write_egl_loader<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// The symbols of the commands that can be loaded before a display has been initialized,
+        /// in sorted order.
+        static EGL_BOOTSTRAP_SYMBOLS: &[&str] = &["
+    )?;
+
+    for symbol in gen_bootstrap_symbols(registry) {
+        writeln!(dest, "\"{}\",", symbol)?;
+    }
+
+    writeln!(
+        dest,
+        "];
+
+        impl {api} {{
+            /// Loads the commands that can be used before `eglInitialize`, which are
+            /// `eglGetDisplay`, `eglInitialize`, `eglQueryString` and the like, and the commands
+            /// of the client extensions, such as `eglGetPlatformDisplayEXT`. The other commands are
+            /// left unloaded until `complete_load_with` is called, as `eglGetProcAddress` may only
+            /// return them once a display has been initialized.
+            #[allow(dead_code, clippy::zero_ptr)]
+            pub fn load_bootstrap_with<F>(mut loadfn: F) -> {api}
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+            {{
+                {api}::load_with(|symbol| {{
+                    if EGL_BOOTSTRAP_SYMBOLS.binary_search(&symbol).is_ok() {{
+                        loadfn(symbol)
+                    }} else {{
+                        0 as *const _
+                    }}
+                }})
+            }}
+
+            /// Loads the commands that `load_bootstrap_with` left unloaded, once a display has
+            /// been initialized, leaving the commands it loaded in their current state.
+            #[allow(dead_code)]
+            pub fn complete_load_with<F>(&mut self, mut loadfn: F)
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+            {{
+                for &(_, symbol, load) in loaders::COMMANDS {{
+                    if EGL_BOOTSTRAP_SYMBOLS.binary_search(&symbol).is_err() {{
+                        load(self, &mut |symbol| loadfn(symbol.to_str().unwrap()));
+                    }}
+                }}
+            }}
+        }}",
+        api = super::gen_struct_name(registry.api),
+    )
+}
@@ -0,0 +1,82 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The methods emitted when `GeneratorOptions::global_bridge` is set.
+
+use registry::Registry;
+use std::io;
+
+/// Writes the `install_global` and `from_global` methods of the structure of the struct
+/// generators, which copy the function pointers to and from the global bindings at `path`.
+pub fn write_global_bridge<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "impl {api} {{
+            /// Copies the loaded function pointers into the global bindings at `{path}`, so that
+            /// code calling the global commands shares them without loading them again. The
+            /// commands that are not loaded are reset in the global bindings.
+            ///
+            /// Like `{path}::load_with`, this writes to global state without synchronization, so
+            /// no other thread may call or load the global commands meanwhile. The function
+            /// pointers must also be valid for the context that is current on every thread that
+            /// calls the global commands afterwards.
+            #[allow(dead_code, clippy::zero_ptr)]
+            pub fn install_global(&self) {{",
+        api = super::gen_struct_name(registry.api),
+        path = path,
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{{
+                let ptr = {ptr}.unwrap_or(0 as *const _);
+                {path}::{name}::load_with(|_| ptr);
+            }}",
+            ptr = super::gen_field_ptr(
+                options,
+                &format!(
+                    "self.{}",
+                    super::gen_field_path(registry, options, &cmd.proto.ident)
+                ),
+                "__gl_imports::raw",
+            ),
+            path = path,
+            name = super::gen_ident(&cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+            /// Creates the bindings from the function pointers that the global bindings at
+            /// `{path}` have loaded.
+            #[allow(dead_code, clippy::zero_ptr)]
+            pub fn from_global() -> {api} {{
+                {api}::load_with(|symbol| {path}::command_ptr(symbol).unwrap_or(0 as *const _))
+            }}
+        }}",
+        api = super::gen_struct_name(registry.api),
+        path = path,
+    )
+}
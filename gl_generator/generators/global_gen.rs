@@ -12,34 +12,282 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use registry::Registry;
+use registry::{Api, Cmd, Registry};
+use std::collections::BTreeSet;
 use std::io;
 
 #[allow(missing_copy_implementations)]
 pub struct GlobalGenerator;
 
+/// Generates the attributes gating the items of a command that only comes from extensions when
+/// `GeneratorOptions::extension_features` is set, or an empty string.
+fn gen_cfg_attrs(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    cmd: &str,
+    docs: bool,
+) -> String {
+    super::gen_cfg_attrs(
+        super::gen_cmd_cfg(registry, options, cmd)
+            .as_ref()
+            .map(|p| &p[..]),
+        docs,
+    )
+}
+
+/// Generates the attributes gating the items of an extension when
+/// `GeneratorOptions::extension_features` is set, or an empty string.
+fn gen_extension_cfg_attrs(options: &super::GeneratorOptions, name: &str, docs: bool) -> String {
+    if options.extension_features {
+        super::gen_cfg_attrs(Some(&format!("feature = \"{}\"", name)), docs)
+    } else {
+        String::new()
+    }
+}
+
+/// Generates the path of the `FnPtr` of a command in the `storage` module, which is a field of the
+/// table when `GeneratorOptions::once_lock` is set.
+fn gen_storage_path(options: &super::GeneratorOptions, cmd: &str) -> String {
+    if options.once_lock {
+        format!("storage::table().{}", super::gen_ident(cmd))
+    } else {
+        format!("storage::{}", super::gen_ident(cmd))
+    }
+}
+
+/// Wraps an expression reading the `storage` module in an `unsafe` block, which the table of
+/// `GeneratorOptions::once_lock` doesn't need.
+fn gen_storage_read(options: &super::GeneratorOptions, expr: &str) -> String {
+    if options.once_lock {
+        expr.to_string()
+    } else {
+        format!("unsafe {{ {} }}", expr)
+    }
+}
+
+/// Generates the array of the NUL-terminated fallback symbols of a command, which `metaloadfn`
+/// tries when the command can't be loaded.
+fn gen_fallbacks(registry: &Registry, options: &super::GeneratorOptions, cmd: &str) -> String {
+    let fallbacks = super::gen_fallbacks(registry, options, cmd)
+        .iter()
+        .map(|symbol| format!("b\"{}\\0\"", symbol))
+        .collect::<Vec<_>>();
+    format!("&[{}]", fallbacks.join(", "))
+}
+
 impl super::Generator for GlobalGenerator {
-    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
-    where
-        W: io::Write,
-    {
-        write_header(dest)?;
-        write_metaloadfn(dest)?;
-        write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
-        write_fns(registry, dest)?;
-        write_fnptr_struct_def(dest)?;
-        write_ptrs(registry, dest)?;
-        write_fn_mods(registry, dest)?;
-        write_panicking_fns(registry, dest)?;
-        write_load_fn(registry, dest)?;
-        Ok(())
+    fn name(&self) -> &'static str {
+        "GlobalGenerator"
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        self.write_with_options(registry, &Default::default(), dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &super::GeneratorOptions,
+        dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        if options.extension_features && (options.helpers || options.dispatch_table) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`extension_features` is not supported together with `helpers` or `dispatch_table`",
+            ));
+        }
+        if options.once_lock && options.generation_guard {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`once_lock` is not supported together with `generation_guard`",
+            ));
+        }
+
+        if options.profile_checks
+            && !registry
+                .cmds
+                .iter()
+                .any(|cmd| cmd.proto.ident == "GetIntegerv")
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`profile_checks` requires `GetIntegerv` to query the profile",
+            ));
+        }
+
+        let mut dest = io::BufWriter::with_capacity(super::WRITE_BUFFER_SIZE, dest);
+        let dest = &mut dest;
+
+        super::write_sections(SECTIONS, options, dest, |section, mut dest| {
+            write_section(registry, options, section, &mut dest)
+        })?;
+        io::Write::flush(dest)
+    }
+}
+
+/// The number of sections that `write_section` writes.
+const SECTIONS: usize = 8;
+
+/// Writes one of the sections of the bindings, which don't depend on each other and are written
+/// in order.
+fn write_section<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    section: usize,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    match section {
+        0 => {
+            write_header(registry, options, dest)?;
+            write_metaloadfn(dest)?;
+            write_type_aliases(registry, options, dest)?;
+            Ok(())
+        },
+        1 => {
+            write_enums(registry, dest)?;
+            if options.int_enums {
+                super::gen_int_enums(registry, dest)?;
+            }
+            Ok(())
+        },
+        2 => {
+            write_pfn_types(registry, dest)?;
+            Ok(())
+        },
+        3 => {
+            write_fns(registry, options, dest)?;
+            Ok(())
+        },
+        4 => {
+            write_fnptr_struct_def(dest)?;
+            write_ptrs(registry, options, dest)?;
+            Ok(())
+        },
+        5 => {
+            write_fn_mods(registry, options, dest)?;
+            write_version_mods(registry, dest)?;
+            if options.registry_docs {
+                write_extension_mods(registry, options, dest)?;
+            }
+            Ok(())
+        },
+        6 => {
+            write_panicking_fns(registry, options, dest)?;
+            Ok(())
+        },
+        7 => {
+            write_load_fn(registry, options, dest)?;
+            write_load_subset_fns(registry, options, dest)?;
+            write_command_ptr(registry, options, dest)?;
+            if options.generation_guard {
+                write_generation_guard(registry, options, dest)?;
+            }
+            if options.profile_checks {
+                write_profile_checks(registry, options, dest)?;
+            }
+            if options.helpers
+                || options.typed_get_error
+                || options.nonzero_create
+                || options.alias_shims
+            {
+                super::helpers::write_helpers(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.capabilities {
+                super::capabilities::write_capabilities(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    dest,
+                )?;
+            }
+            if options.handle_types {
+                super::handles::write_typed_commands(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.dispatch_table {
+                super::dispatch_table::write_dispatch_table(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.caching_loader {
+                super::caching_loader::write_caching_loader(super::std_path(options), dest)?;
+            }
+            if options.loader_trait {
+                super::loader_trait::write_loader_trait(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            if options.dispatch_bench {
+                super::dispatch_bench::write_dispatch_bench(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    super::std_path(options),
+                    dest,
+                )?;
+            }
+            if options.signature_test {
+                super::signature_test::write_signature_test(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    "unsafe fn",
+                    dest,
+                )?;
+            }
+            if options.command_versions {
+                super::command_version::write_command_version(registry, dest)?;
+            }
+            if registry.api == Api::Wgl {
+                super::wgl::write_wgl_loader(registry, super::helpers::Dispatch::Free, dest)?;
+            }
+            if options.platform_loader {
+                super::platform::write_platform_loader(
+                    registry,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            if let Some(prelude) = options.prelude {
+                super::prelude::write_prelude(
+                    registry,
+                    prelude,
+                    super::helpers::Dispatch::Free,
+                    options,
+                    dest,
+                )?;
+            }
+            Ok(())
+        },
+        _ => unreachable!(),
     }
 }
 
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
-///  bindings.
-fn write_header<W>(dest: &mut W) -> io::Result<()>
+///  bindings, followed by the `GENERATED_FOR` constant if `GeneratorOptions::version_assertions`
+///  is set.
+fn write_header<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -47,11 +295,18 @@ where
         dest,
         r#"
         mod __gl_imports {{
-            pub use std::mem;
-            pub use std::os::raw;
+            pub use {std}::ffi::CStr;
+            #[allow(unused_imports)] // Only used by some commands and options
+            pub use {std}::mem;
+            pub use {std}::os::raw;
         }}
-    "#
-    )
+    "#,
+        std = super::std_path(options)
+    )?;
+    if options.version_assertions {
+        super::gen_generated_for(registry, options, dest)?;
+    }
+    Ok(())
 }
 
 /// Creates the metaloadfn function for fallbacks
@@ -62,14 +317,19 @@ where
     writeln!(
         dest,
         r#"
+        #[allow(dead_code)]
         #[inline(never)]
-        fn metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
-                      symbol: &'static str,
-                      fallbacks: &[&'static str]) -> *const __gl_imports::raw::c_void {{
-            let mut ptr = loadfn(symbol);
+        fn metaloadfn(loadfn: &mut dyn FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void,
+                      symbol: &'static [u8],
+                      fallbacks: &[&'static [u8]]) -> *const __gl_imports::raw::c_void {{
+            // The symbols are NUL-terminated byte strings.
+            fn cstr(bytes: &'static [u8]) -> &'static __gl_imports::CStr {{
+                unsafe {{ __gl_imports::CStr::from_bytes_with_nul_unchecked(bytes) }}
+            }}
+            let mut ptr = loadfn(cstr(symbol));
             if ptr.is_null() {{
                 for &sym in fallbacks {{
-                    ptr = loadfn(sym);
+                    ptr = loadfn(cstr(sym));
                     if !ptr.is_null() {{ break; }}
                 }}
             }}
@@ -82,7 +342,11 @@ where
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_types`.
-fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_type_aliases<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -90,11 +354,14 @@ where
         dest,
         r#"
         pub mod types {{
-            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations, clippy::upper_case_acronyms)]
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry.api, options, dest)?;
+    if options.handle_types {
+        super::handles::write_handle_types(registry, dest)?;
+    }
 
     writeln!(
         dest,
@@ -116,74 +383,245 @@ where
     Ok(())
 }
 
+/// Creates a `PFN...PROC` type alias for each command, which is its function pointer type.
+fn write_pfn_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for cmd in &registry.cmds {
+        super::gen_pfn_item(registry.api, cmd, dest)?;
+    }
+
+    Ok(())
+}
+
 /// Creates the functions corresponding to the GL commands.
 ///
 /// The function calls the corresponding function pointer stored in the `storage` module created
-///  by `write_ptrs`.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+///  by `write_ptrs`. With `GeneratorOptions::debug_feature`, each function is written a second
+///  time by `write_debug_fn`, the plain and debug forms being compiled depending on the feature.
+fn write_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
+    let compatibility_cmds = if options.profile_checks {
+        super::gen_compatibility_cmds(registry)
+    } else {
+        BTreeSet::new()
+    };
+    let print_err = gen_print_err(registry, options);
+    let cfgs = options.debug_feature.as_ref().map(|feature| {
+        (
+            format!("#[cfg(not(feature = \"{}\"))] ", feature),
+            format!("#[cfg(feature = \"{}\")] ", feature),
+        )
+    });
+
     for cmd in &registry.cmds {
-        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
-            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        let symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident);
+        let mut check = String::new();
+        if options.generation_guard {
+            check.push_str(&format!(
+                "#[cfg(debug_assertions)] generation::check(\"{}\"); ",
+                symbol
+            ));
+        }
+        if compatibility_cmds.contains(&cmd.proto.ident[..]) {
+            check.push_str(&format!("profile::check(\"{}\"); ", symbol));
         }
 
-        writeln!(dest,
-            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline]
-            pub unsafe fn {name}({params}) -> {return_suffix} {{ \
-                __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
-                    (storage::{name}.f)({idents}) \
-            }}",
-            name = cmd.proto.ident,
-            params = super::gen_parameters(cmd, true, true).join(", "),
-            typed_params = super::gen_parameters(cmd, false, true).join(", "),
-            return_suffix = cmd.proto.ty,
-            idents = super::gen_parameters(cmd, true, false).join(", "),
-        )?;
+        match cfgs {
+            Some((ref plain_cfg, ref debug_cfg)) => {
+                write_fn(registry, options, cmd, plain_cfg, &check, dest)?;
+                write_debug_fn(registry, options, cmd, debug_cfg, &check, &print_err, dest)?;
+            },
+            None => write_fn(registry, options, cmd, "", &check, dest)?,
+        }
     }
 
     Ok(())
 }
 
+/// Creates the function calling `cmd`, which runs the statements of `check` first and is only
+/// compiled when the attribute `feature_cfg` holds, if it isn't empty.
+fn write_fn<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    cmd: &Cmd,
+    feature_cfg: &str,
+    check: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+        writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+    }
+
+    writeln!(
+        dest,
+        "{cfg} {feature_cfg}{alias}#[allow(non_snake_case, unused_variables, dead_code, unused_unsafe)]
+        #[allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit)] #[inline]
+        pub unsafe fn {fn_name}({params}) -> {return_suffix} {{ \
+            {unsafe_open}{check}\
+            __gl_imports::mem::transmute::<*const __gl_imports::raw::c_void, {pfn}>\
+                ({ptr}.f)({idents}){unsafe_close} \
+        }}",
+        cfg = gen_cfg_attrs(registry, options, &cmd.proto.ident, true),
+        feature_cfg = feature_cfg,
+        alias = super::gen_doc_alias(registry, options, &cmd.proto.ident),
+        unsafe_open = super::UNSAFE_OPEN,
+        unsafe_close = super::UNSAFE_CLOSE,
+        check = check,
+        fn_name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+        ptr = gen_storage_path(options, &cmd.proto.ident),
+        params = super::gen_parameter_list(cmd, true, true),
+        pfn = super::gen_pfn_name(registry.api, &cmd.proto.ident),
+        return_suffix = cmd.proto.ty,
+        idents = super::gen_parameter_list(cmd, true, false),
+    )
+}
+
+/// Generates the statement that prints the error reported by `GetError` after a call of a debug
+/// function, or an empty string if `GetError` is not part of the bindings. The error is only
+/// queried if `GetError` has been loaded.
+fn gen_print_err(registry: &Registry, options: &super::GeneratorOptions) -> String {
+    if registry
+        .cmds
+        .iter()
+        .any(|cmd| cmd.proto.ident == "GetError")
+    {
+        format!(
+            r#"if {module}::is_loaded() {{
+                    match {fn_name}() {{ 0 => (), r => {std}::println!("[OpenGL] ^ GL error triggered: {{}}", r) }}
+                }}"#,
+            std = super::std_path(options),
+            module = super::gen_ident("GetError"),
+            fn_name = super::gen_cmd_name(registry, options, "GetError"),
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Creates the debug form of the function of `write_fn`, which prints the call followed by the
+/// error reported by `GetError`, like the methods of `DebugStructGenerator`. `print_err` is the
+/// statement generated by `gen_print_err`.
+fn write_debug_fn<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    cmd: &Cmd,
+    feature_cfg: &str,
+    check: &str,
+    print_err: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+        writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+    }
+
+    // The call is printed with `println!("[OpenGL] Name({:?}, {:?})", a, b);`.
+    writeln!(
+        dest,
+        "{cfg} {feature_cfg}{alias}#[allow(non_snake_case, unused_variables, dead_code, unused_unsafe)]
+        #[allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit, clippy::let_unit_value, clippy::let_and_return)]
+        #[inline] pub unsafe fn {fn_name}({params}) -> {return_suffix} {{ \
+            {unsafe_open}{std}::println!(\"[OpenGL] {ident}({formats})\"{args});
+            {check}
+            let r = __gl_imports::mem::transmute::<*const __gl_imports::raw::c_void, {pfn}>\
+                ({ptr}.f)({idents});
+            {print_err}
+            r{unsafe_close}
+        }}",
+        cfg = gen_cfg_attrs(registry, options, &cmd.proto.ident, true),
+        feature_cfg = feature_cfg,
+        alias = super::gen_doc_alias(registry, options, &cmd.proto.ident),
+        unsafe_open = super::UNSAFE_OPEN,
+        unsafe_close = super::UNSAFE_CLOSE,
+        ident = cmd.proto.ident,
+        std = super::std_path(options),
+        formats = vec!["{:?}"; cmd.params.len()].join(", "),
+        args = cmd
+            .params
+            .iter()
+            .map(|param| if param.ty.contains("GLDEBUGPROC") {
+                ", \"<callback>\"".to_string()
+            } else {
+                format!(", {}", super::gen_ident(&param.ident))
+            })
+            .collect::<String>(),
+        check = check,
+        fn_name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+        ptr = gen_storage_path(options, &cmd.proto.ident),
+        params = super::gen_parameter_list(cmd, true, true),
+        pfn = super::gen_pfn_name(registry.api, &cmd.proto.ident),
+        return_suffix = cmd.proto.ty,
+        idents = super::gen_parameter_list(cmd, true, false),
+        print_err = if cmd.proto.ident != "GetError" {
+            print_err
+        } else {
+            ""
+        },
+    )
+}
+
 /// Creates a `FnPtr` structure which contains the store for a single binding.
 fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
-    writeln!(dest,
-             "
-        #[allow(missing_copy_implementations)]
+    writeln!(
+        dest,
+        "
+        #[allow(dead_code, missing_copy_implementations)]
         pub struct FnPtr {{
-            /// The function pointer that will be used when calling the function.
+            /// The function pointer that will be used when calling the function, which points to
+            /// `missing_fn_panic` if it hasn't been loaded.
             f: *const __gl_imports::raw::c_void,
-            /// True if the pointer points to a real function, false if points to a `panic!` fn.
-            is_loaded: bool,
         }}
 
+        #[allow(dead_code)]
         impl FnPtr {{
             /// Creates a `FnPtr` from a load attempt.
             pub fn new(ptr: *const __gl_imports::raw::c_void) -> FnPtr {{
                 if ptr.is_null() {{
-                    FnPtr {{ f: missing_fn_panic as *const __gl_imports::raw::c_void, is_loaded: false }}
+                    FnPtr {{ f: missing_fn_panic as *const __gl_imports::raw::c_void }}
                 }} else {{
-                    FnPtr {{ f: ptr, is_loaded: true }}
+                    FnPtr {{ f: ptr }}
                 }}
             }}
         }}
-    ")
+    "
+    )
 }
 
 /// Creates a `storage` module which contains a static `FnPtr` per GL command in the registry.
-fn write_ptrs<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_ptrs<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
+    if options.once_lock {
+        return write_ptrs_table(registry, options, dest);
+    }
+
     writeln!(
         dest,
         "mod storage {{
             #![allow(non_snake_case)]
             #![allow(non_upper_case_globals)]
+            #![allow(unused_imports)]
             use super::__gl_imports::raw;
             use super::FnPtr;"
     )?;
@@ -191,63 +629,162 @@ where
     for c in &registry.cmds {
         writeln!(
             dest,
-            "pub static mut {name}: FnPtr = FnPtr {{
-                f: super::missing_fn_panic as *const raw::c_void,
-                is_loaded: false
+            "{cfg} pub static mut {name}: FnPtr = FnPtr {{
+                f: super::missing_fn_panic as *const raw::c_void
             }};",
-            name = c.proto.ident
+            cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+            name = super::gen_ident(&c.proto.ident)
         )?;
     }
 
     writeln!(dest, "}}")
 }
 
+/// Creates a `storage` module which contains a `Table` with a `FnPtr` field per GL command in the
+/// registry, stored in a `OnceLock` that `load_with` initializes.
+fn write_ptrs_table<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "mod storage {{
+            #![allow(non_snake_case)]
+            #![allow(non_upper_case_globals)]
+            #![allow(unused_imports)]
+            use super::__gl_imports::raw;
+            use super::FnPtr;
+            use {std}::sync::OnceLock;
+
+            /// The function pointer of each command.
+            pub struct Table {{",
+        std = super::std_path(options)
+    )?;
+
+    for c in &registry.cmds {
+        writeln!(
+            dest,
+            "{cfg} pub {name}: FnPtr,",
+            cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+            name = super::gen_ident(&c.proto.ident)
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+            // The pointers are never written once the table has been created.
+            unsafe impl Send for Table {{}}
+            unsafe impl Sync for Table {{}}
+
+            /// The table that the first call to `load_with` initializes.
+            pub static TABLE: OnceLock<Table> = OnceLock::new();
+
+            /// The table read before the bindings have been loaded, in which no command is loaded.
+            static UNLOADED: Table = Table {{"
+    )?;
+
+    for c in &registry.cmds {
+        writeln!(
+            dest,
+            "{cfg} {name}: FnPtr {{ f: super::missing_fn_panic as *const raw::c_void }},",
+            cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+            name = super::gen_ident(&c.proto.ident)
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}};
+
+            /// Returns the loaded table, or a table of unloaded commands before `load_with`.
+            #[inline]
+            pub fn table() -> &'static Table {{
+                match TABLE.get() {{
+                    Some(table) => table,
+                    None => &UNLOADED,
+                }}
+            }}
+        }}"
+    )
+}
+
 /// Creates one module for each GL command.
 ///
 /// Each module contains `is_loaded` and `load_with` which interact with the `storage` module
 ///  created by `write_ptrs`.
-fn write_fn_mods<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_fn_mods<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
     for c in &registry.cmds {
-        let fallbacks = match registry.aliases.get(&c.proto.ident) {
-            Some(v) => {
-                let names = v
-                    .iter()
-                    .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, &name[..])))
-                    .collect::<Vec<_>>();
-                format!("&[{}]", names.join(", "))
-            },
-            None => "&[]".to_string(),
-        };
-        let fnname = &c.proto.ident[..];
+        if options.once_lock {
+            // The table can't be loaded one command at a time.
+            writeln!(
+                dest,
+                r##"
+                {cfg} #[allow(non_snake_case)]
+                pub mod {fnname} {{
+                    use super::{{storage, missing_fn_panic}};
+                    use super::__gl_imports::raw;
+
+                    #[inline]
+                    #[allow(dead_code)]
+                    pub fn is_loaded() -> bool {{
+                        {ptr}.f != missing_fn_panic as *const raw::c_void
+                    }}
+                }}
+            "##,
+                cfg = gen_cfg_attrs(registry, options, &c.proto.ident, true),
+                fnname = super::gen_ident(&c.proto.ident),
+                ptr = gen_storage_path(options, &c.proto.ident),
+            )?;
+            continue;
+        }
+
+        let fallbacks = gen_fallbacks(registry, options, &c.proto.ident);
+        let fnname = super::gen_ident(&c.proto.ident);
         let symbol = super::gen_symbol_name(registry.api, &c.proto.ident[..]);
         let symbol = &symbol[..];
 
         writeln!(
             dest,
             r##"
-            #[allow(non_snake_case)]
+            {cfg} #[allow(non_snake_case)]
             pub mod {fnname} {{
-                use super::{{storage, metaloadfn}};
-                use super::__gl_imports::raw;
+                use super::{{storage, metaloadfn, missing_fn_panic}};
+                use super::__gl_imports::{{raw, CStr}};
                 use super::FnPtr;
 
                 #[inline]
                 #[allow(dead_code)]
                 pub fn is_loaded() -> bool {{
-                    unsafe {{ storage::{fnname}.is_loaded }}
+                    unsafe {{ storage::{fnname}.f != missing_fn_panic as *const raw::c_void }}
                 }}
 
                 #[allow(dead_code)]
                 pub fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const raw::c_void {{
+                    load_with_cstr(|symbol| loadfn(symbol.to_str().unwrap()))
+                }}
+
+                #[allow(dead_code)]
+                pub fn load_with_cstr<F>(mut loadfn: F) where F: FnMut(&'static CStr) -> *const raw::c_void {{
                     unsafe {{
-                        storage::{fnname} = FnPtr::new(metaloadfn(&mut loadfn, "{symbol}", {fallbacks}))
+                        storage::{fnname} = FnPtr::new(metaloadfn(&mut loadfn, b"{symbol}\0", {fallbacks}))
                     }}
                 }}
             }}
         "##,
+            cfg = gen_cfg_attrs(registry, options, &c.proto.ident, true),
             fnname = fnname,
             fallbacks = fallbacks,
             symbol = symbol
@@ -257,28 +794,142 @@ where
     Ok(())
 }
 
+/// Creates one module for each version of the registry, named after its feature.
+///
+/// Each module contains an `is_loaded` function which checks the commands introduced by that
+///  version using the modules created by `write_fn_mods`.
+fn write_version_mods<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for version in &registry.versions {
+        let checks = version
+            .cmds
+            .iter()
+            .map(|cmd| format!("super::{}::is_loaded()", super::gen_ident(cmd)))
+            .collect::<Vec<_>>();
+
+        writeln!(
+            dest,
+            r#"
+            #[allow(non_snake_case)]
+            pub mod {name} {{
+                /// Returns `true` if every command introduced by version {major}.{minor} has been
+                /// loaded.
+                #[inline]
+                #[allow(dead_code)]
+                pub fn is_loaded() -> bool {{
+                    {checks}
+                }}
+            }}
+        "#,
+            name = version.name,
+            major = version.number.0,
+            minor = version.number.1,
+            checks = if checks.is_empty() {
+                "true".to_string()
+            } else {
+                checks.join(" && ")
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates one module for each extension of the registry, named after it and documented with the
+/// metadata of the registry.
+///
+/// Each module contains an `is_loaded` function which checks the commands required by that
+///  extension using the modules created by `write_fn_mods`.
+fn write_extension_mods<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for extension in &registry.extensions {
+        // Extension names start with the API and the vendor, as in `GL_ARB_debug_output`.
+        let vendor = extension.name.split('_').nth(1).unwrap_or("");
+        let comment = match extension.comment {
+            Some(ref comment) => comment
+                .lines()
+                .map(|line| format!("\n//!\n//! {}", line.trim()))
+                .collect(),
+            None => String::new(),
+        };
+        let checks = extension
+            .cmds
+            .iter()
+            .map(|cmd| format!("super::{}::is_loaded()", super::gen_ident(cmd)))
+            .collect::<Vec<_>>();
+
+        writeln!(
+            dest,
+            r#"
+            {cfg} #[allow(non_snake_case)]
+            pub mod {name} {{
+                //! The `{name}` extension, from the {vendor} vendor.{comment}
+
+                /// Returns `true` if every command required by the extension has been loaded.
+                #[inline]
+                #[allow(dead_code)]
+                pub fn is_loaded() -> bool {{
+                    {checks}
+                }}
+            }}
+        "#,
+            cfg = gen_extension_cfg_attrs(options, &extension.name, true),
+            name = extension.name,
+            vendor = vendor,
+            comment = comment,
+            checks = if checks.is_empty() {
+                "true".to_string()
+            } else {
+                checks.join(" && ")
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Creates a `missing_fn_panic` function.
 ///
-/// This function is the mock that is called if the real function could not be called.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// This function is the mock that is called if the real function could not be called. With
+/// `GeneratorOptions::wasm_stubs`, it says on wasm that the bindings are not available there.
+fn write_panicking_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
     writeln!(
         dest,
-        "#[inline(never)]
+        "#[allow(dead_code)]
+        #[inline(never)]
         fn missing_fn_panic() -> ! {{
+            {wasm_check}
             panic!(\"{api} function was not loaded\")
         }}
         ",
-        api = registry.api
+        api = registry.api,
+        wasm_check = super::wasm_stubs::gen_missing_fn_check(registry, options),
     )
 }
 
 /// Creates the `load_with` function.
 ///
 /// The function calls `load_with` in each module created by `write_fn_mods`.
-fn write_load_fn<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_load_fn<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -288,28 +939,454 @@ where
         /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
         /// ~~~ignore
         /// gl::load_with(|s| glfw.get_proc_address(s));
-        /// ~~~
+        /// ~~~{once}
         #[allow(dead_code)]
         pub fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            load_with_cstr(|symbol| loadfn(symbol.to_str().unwrap()))
+        }}
+
+        /// Load each OpenGL symbol using a custom load function that takes NUL-terminated
+        /// symbols, which can be passed to a C function without allocating a `CString`.
+        #[allow(dead_code)]
+        pub fn load_with_cstr<F>(mut loadfn: F) where F: FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void {{
             #[inline(never)]
-            fn inner(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void) {{
-    ")?;
+            #[allow(unused_variables)]
+            fn inner(loadfn: &mut dyn FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void) {{
+    ",
+        once = if options.once_lock {
+            "\n///\n/// Only the first call loads the commands, later ones leave them unchanged."
+        } else {
+            ""
+        },
+    )?;
+
+    if options.once_lock {
+        writeln!(dest, "storage::TABLE.get_or_init(|| storage::Table {{")?;
+        for c in &registry.cmds {
+            writeln!(
+                dest,
+                "{cfg} {name}: FnPtr::new(metaloadfn(&mut *loadfn, b\"{symbol}\\0\", {fallbacks})),",
+                cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+                name = super::gen_ident(&c.proto.ident),
+                symbol = super::gen_symbol_name(registry.api, &c.proto.ident),
+                fallbacks = gen_fallbacks(registry, options, &c.proto.ident),
+            )?;
+        }
+        writeln!(dest, "}});")?;
+    } else {
+        for c in &registry.cmds {
+            writeln!(
+                dest,
+                "{cfg} {cmd_name}::load_with_cstr(&mut *loadfn);",
+                cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+                cmd_name = super::gen_ident(&c.proto.ident)
+            )?;
+        }
+    }
+
+    writeln!(
+        dest,
+        "
+            }}
+
+            {cache}
+            inner(&mut loadfn);{bump}
+        }}
+
+        /// Load each OpenGL symbol using a custom load function that can fail, failing unless
+        /// every command introduced by the versions of the bindings has been loaded. Extension
+        /// commands are optional.
+        ///
+        /// Loading stops at the first error returned by `loadfn`, which is returned as
+        /// `TryLoadError::Loader`. In that case the commands that come after the failing one are
+        /// left unloaded. Otherwise `TryLoadError::Missing` lists the symbols of the missing
+        /// commands, which usually means that `loadfn` is broken or that the context is older
+        /// than the bindings.
+        #[allow(dead_code)]
+        pub fn try_load_with<F, E>(mut loadfn: F) -> Result<(), TryLoadError<E>>
+            where F: FnMut(&'static str) -> Result<*const __gl_imports::raw::c_void, E>
+        {{
+            let mut error = None;
+            load_with(|symbol| {{
+                if error.is_none() {{
+                    match loadfn(symbol) {{
+                        Ok(ptr) => return ptr,
+                        Err(e) => error = Some(e),
+                    }}
+                }}
+                {std}::ptr::null()
+            }});
+            if let Some(e) = error {{
+                return Err(TryLoadError::Loader(e));
+            }}
+            let missing: {std}::vec::Vec<_> = loaders::CORE
+                .iter()
+                .filter(|&&(_, is_loaded)| !is_loaded())
+                .map(|&(symbol, _)| symbol)
+                .collect();
+            if missing.is_empty() {{ Ok(()) }} else {{ Err(TryLoadError::Missing(missing)) }}
+        }}
+{try_load_error}
+        /// Load each OpenGL symbol using a custom load function, calling it again up to
+        /// `retries` times for a symbol it returns null for, for platform loaders that fail
+        /// transiently.
+        #[allow(dead_code)]
+        pub fn load_with_retry<F>(mut loadfn: F, retries: u32)
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            load_with(|symbol| {{
+                let mut ptr = loadfn(symbol);
+                for _ in 0..retries {{
+                    if !ptr.is_null() {{ break; }}
+                    ptr = loadfn(symbol);
+                }}
+                ptr
+            }})
+        }}
+    ",
+        std = super::std_path(options),
+        cache = super::gen_load_cache(options),
+        try_load_error = super::gen_try_load_error(registry.api, options),
+        bump = match (options.generation_guard, options.profile_checks) {
+            (true, true) => "\ngeneration::bump(true);\nprofile::reset();",
+            (true, false) => "\ngeneration::bump(true);",
+            (false, true) => "\nprofile::reset();",
+            (false, false) => "",
+        },
+    )
+}
+
+/// Creates the `load_subset_with` and `load_extension_with` functions.
+///
+/// The functions look up commands in a `loaders` module, which maps the name of each command to
+///  the `load_with` function of the module created by `write_fn_mods`, and the name of each
+///  extension to its commands. With `GeneratorOptions::once_lock`, the module only lists the core
+///  commands for `try_load_with`.
+fn write_load_subset_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest, "mod loaders {{")?;
+
+    if !options.once_lock {
+        writeln!(
+            dest,
+            "use super::__gl_imports::raw;
+
+            /// The name and symbol of each command, with a function that loads it.
+            #[allow(clippy::type_complexity)]
+            pub static COMMANDS: &[(&str, &str, fn(&mut dyn FnMut(&'static str) -> *const raw::c_void))] = &["
+        )?;
+
+        for c in &registry.cmds {
+            writeln!(
+                dest,
+                "{cfg} (\"{name}\", \"{symbol}\", |loadfn| super::{ident}::load_with(loadfn)),",
+                cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+                name = c.proto.ident,
+                ident = super::gen_ident(&c.proto.ident),
+                symbol = super::gen_symbol_name(registry.api, &c.proto.ident),
+            )?;
+        }
 
-    for c in &registry.cmds {
         writeln!(
             dest,
-            "{cmd_name}::load_with(&mut *loadfn);",
-            cmd_name = &c.proto.ident[..]
+            "];
+
+            /// The name of each extension, with the commands it requires.
+            pub static EXTENSIONS: &[(&str, &[&str])] = &["
         )?;
+
+        for extension in &registry.extensions {
+            writeln!(
+                dest,
+                "{cfg} (\"{name}\", &[{cmds}]),",
+                cfg = gen_extension_cfg_attrs(options, &extension.name, false),
+                name = extension.name,
+                cmds = extension
+                    .cmds
+                    .iter()
+                    .map(|cmd| format!("\"{}\"", cmd))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )?;
+        }
+
+        writeln!(dest, "];")?;
     }
 
     writeln!(
         dest,
         "
+            /// The symbol of each command introduced by the versions of the bindings, with a
+            /// function checking that it has been loaded.
+            #[allow(clippy::type_complexity)]
+            pub static CORE: &[(&str, fn() -> bool)] = &["
+    )?;
+
+    for cmd in super::gen_core_cmds(registry) {
+        writeln!(
+            dest,
+            "(\"{symbol}\", super::{ident}::is_loaded),",
+            symbol = super::gen_symbol_name(registry.api, cmd),
+            ident = super::gen_ident(cmd),
+        )?;
+    }
+
+    writeln!(dest, "];\n}}")?;
+
+    if options.once_lock {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        r#"
+        /// Loads the given commands using a custom load function, leaving the other commands in
+        /// their current state.
+        ///
+        /// Commands can be named as in the bindings (`"Clear"`) or by their symbol (`"glClear"`).
+        /// Returns the names that don't match any command of the bindings.
+        #[allow(dead_code)]
+        pub fn load_subset_with<'a, F>(mut loadfn: F, commands: &[&'a str]) -> Result<(), {std}::vec::Vec<&'a str>>
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            let mut unknown = {std}::vec::Vec::new();
+            for &name in commands {{
+                match loaders::COMMANDS.iter().find(|&&(ident, symbol, _)| name == ident || name == symbol) {{
+                    Some(&(_, _, load)) => load(&mut loadfn),
+                    None => unknown.push(name),
+                }}
             }}
+            if unknown.is_empty() {{ Ok(()) }} else {{ Err(unknown) }}
+        }}
 
-            inner(&mut loadfn)
+        /// Loads the commands required by the extension `name` using a custom load function,
+        /// leaving the other commands in their current state.
+        ///
+        /// Returns `false` if the extension is not part of the bindings.
+        #[allow(dead_code)]
+        pub fn load_extension_with<F>(loadfn: F, name: &str) -> bool
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            match loaders::EXTENSIONS.iter().find(|&&(extension, _)| name == extension) {{
+                Some(&(_, commands)) => load_subset_with(loadfn, commands).is_ok(),
+                None => false,
+            }}
         }}
-    "
+    "#,
+        std = super::std_path(options),
+    )
+}
+
+/// Creates the `command_ptr` function, which looks the symbol up in a sorted table of the symbols
+/// of the commands and of their aliases.
+fn write_command_ptr<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(unused_imports)]
+        mod command_ptrs {{
+            use super::__gl_imports::raw;
+            use super::storage;
+
+            /// Each symbol in sorted order, with a function reading the pointer of its command.
+            #[allow(clippy::type_complexity)]
+            pub static SYMBOLS: &[(&str, fn() -> (bool, *const raw::c_void))] = &["
+    )?;
+
+    for (symbol, name) in super::gen_symbol_map(registry) {
+        writeln!(
+            dest,
+            "{cfg} (\"{symbol}\", || (super::{name}::is_loaded(), {ptr})),",
+            cfg = gen_cfg_attrs(registry, options, name, false),
+            symbol = symbol,
+            name = super::gen_ident(name),
+            ptr = gen_storage_read(options, &format!("{}.f", gen_storage_path(options, name))),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "];
+        }}
+
+        /// Returns the pointer that the command with the symbol `name`, e.g. `\"glClear\"`, has
+        /// been loaded from, or `None` if it has not been loaded or is not part of the bindings.
+        ///
+        /// The symbols of the aliases of a command resolve to that command. The lookup doesn't
+        /// allocate, so this can serve a C `GetProcAddress` callback.
+        #[allow(dead_code)]
+        pub fn command_ptr(name: &str) -> Option<*const __gl_imports::raw::c_void> {{
+            match command_ptrs::SYMBOLS.binary_search_by(|&(symbol, _)| symbol.cmp(name)) {{
+                Ok(index) => match (command_ptrs::SYMBOLS[index].1)() {{
+                    (true, ptr) => Some(ptr),
+                    (false, _) => None,
+                }},
+                Err(_) => None,
+            }}
+        }}"
+    )
+}
+
+/// Creates the `unload` function and the `generation` module, whose counter is bumped by
+/// `load_with` and `unload` and checked by the commands in debug builds.
+fn write_generation_guard<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        /// Resets every function pointer, e.g. after the context has been lost. In debug builds,
+        /// calling a command afterwards panics until `load_with` is called again.
+        #[allow(dead_code, clippy::zero_ptr)]
+        pub fn unload() {{
+            unsafe {{"#
+    )?;
+
+    for c in &registry.cmds {
+        writeln!(
+            dest,
+            "{cfg} {{ storage::{name} = FnPtr::new(0 as *const __gl_imports::raw::c_void); }}",
+            cfg = gen_cfg_attrs(registry, options, &c.proto.ident, false),
+            name = super::gen_ident(&c.proto.ident)
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"
+            }}
+            generation::bump(false);
+        }}
+
+        #[allow(dead_code)]
+        mod generation {{
+            use {std}::cell::Cell;
+            use {std}::sync::atomic::{{AtomicUsize, Ordering}};
+
+            /// Odd while the function pointers are loaded, even after `unload`.
+            static GENERATION: AtomicUsize = AtomicUsize::new(1);
+
+            {std}::thread_local!(static CHECKED: Cell<usize> = const {{ Cell::new(0) }});
+
+            pub fn bump(loaded: bool) {{
+                let generation = (GENERATION.load(Ordering::SeqCst) | 1) + 1;
+                GENERATION.store(if loaded {{ generation + 1 }} else {{ generation }}, Ordering::SeqCst);
+            }}
+
+            /// Panics if the function pointers have been unloaded. Only the generation last
+            /// checked by this thread is compared in the common case.
+            #[inline]
+            pub fn check(symbol: &'static str) {{
+                let generation = GENERATION.load(Ordering::Relaxed);
+                CHECKED.with(|checked| {{
+                    if checked.get() != generation {{
+                        if (generation & 1) == 0 {{
+                            panic!("{{}} was called after `unload` and before the bindings were loaded again", symbol);
+                        }}
+                        checked.set(generation);
+                    }}
+                }});
+            }}
+        }}"#,
+        std = super::std_path(options)
+    )
+}
+
+/// Creates the `profile` module, whose `active_profile` function queries the profile of the
+/// current context once after each `load_with`, and whose `check` function is called by the
+/// commands that the core profile removes.
+fn write_profile_checks<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        /// The profile of the current context, for bindings used with both core and compatibility
+        /// contexts.
+        #[allow(dead_code)]
+        pub mod profile {{
+            use {std}::sync::atomic::{{AtomicUsize, Ordering}};
+
+            /// The profiles of an OpenGL context.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            pub enum ContextProfile {{
+                /// A core profile context, without the commands that the core profile removes.
+                Core,
+                /// A compatibility profile context, or a context older than 3.2, which has every
+                /// command.
+                Compatibility,
+            }}
+
+            /// `0` until `active_profile` queries the profile, then `1` for the core profile and
+            /// `2` for the compatibility profile.
+            static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+            /// Forgets the profile, which is queried again for the context that the bindings have
+            /// been loaded for.
+            pub fn reset() {{
+                ACTIVE.store(0, Ordering::Relaxed);
+            }}
+
+            /// Returns the profile of the current context, which is queried with `GetIntegerv`
+            /// the first time this is called after `load_with`.
+            ///
+            /// Contexts older than 3.2, which don't support the query and record an
+            /// `INVALID_ENUM` error for it, are compatibility contexts, as are all contexts if
+            /// `GetIntegerv` hasn't been loaded.
+            pub fn active_profile() -> ContextProfile {{
+                match ACTIVE.load(Ordering::Relaxed) {{
+                    1 => return ContextProfile::Core,
+                    2 => return ContextProfile::Compatibility,
+                    _ => {{}},
+                }}
+                let mut mask = 0;
+                if super::GetIntegerv::is_loaded() {{
+                    // `CONTEXT_PROFILE_MASK`, which isn't in the bindings before 3.2.
+                    unsafe {{ super::{get_integerv}(0x9126, &mut mask) }};
+                }}
+                // `CONTEXT_CORE_PROFILE_BIT`
+                let profile = if mask & 0x1 != 0 {{
+                    ContextProfile::Core
+                }} else {{
+                    ContextProfile::Compatibility
+                }};
+                ACTIVE.store(if profile == ContextProfile::Core {{ 1 }} else {{ 2 }}, Ordering::Relaxed);
+                profile
+            }}
+
+            /// Panics if the current context is a core profile context, which doesn't have the
+            /// command `symbol`.
+            #[inline]
+            pub fn check(symbol: &'static str) {{
+                if active_profile() == ContextProfile::Core {{
+                    panic!("{{}} was removed from the core profile, and the current context is a core profile context", symbol);
+                }}
+            }}
+        }}"#,
+        std = super::std_path(options),
+        get_integerv = super::gen_cmd_name(registry, options, "GetIntegerv"),
     )
 }
@@ -0,0 +1,284 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The GPU timing of the commands emitted by `DebugStructGenerator` when
+//! `GeneratorOptions::gpu_timing` is set.
+
+use super::CommandGroup;
+use registry::Registry;
+use std::collections::BTreeSet;
+use std::io;
+
+/// The commands that the timing calls, which a registry must have for `gpu_timing` to be set.
+pub const REQUIRED_CMDS: &[&str] = &[
+    "DeleteQueries",
+    "GenQueries",
+    "GetQueryObjectiv",
+    "GetQueryObjectui64v",
+    "QueryCounter",
+];
+
+/// The prefixes of the commands uploading data, with or without a vendor suffix.
+const UPLOAD_PREFIXES: &[&str] = &[
+    "BufferData",
+    "BufferStorage",
+    "BufferSubData",
+    "CompressedTexImage",
+    "CompressedTexSubImage",
+    "CompressedTextureSubImage",
+    "NamedBufferData",
+    "NamedBufferStorage",
+    "NamedBufferSubData",
+    "TexImage",
+    "TexSubImage",
+    "TextureSubImage",
+];
+
+/// Returns the group of the command `cmd`, if it belongs to one.
+fn gen_group(cmd: &str) -> Option<CommandGroup> {
+    if (cmd.starts_with("Draw") && !cmd.starts_with("DrawBuffer")) || cmd.starts_with("MultiDraw") {
+        Some(CommandGroup::Draw)
+    } else if UPLOAD_PREFIXES.iter().any(|prefix| cmd.starts_with(prefix)) {
+        Some(CommandGroup::Upload)
+    } else if cmd.starts_with("DispatchCompute") {
+        Some(CommandGroup::Compute)
+    } else {
+        None
+    }
+}
+
+/// Returns the commands of the registry that belong to one of the groups of
+/// `GeneratorOptions::gpu_timing`.
+pub fn gen_timed_cmds<'a>(registry: &'a Registry, groups: &[CommandGroup]) -> BTreeSet<&'a str> {
+    registry
+        .cmds
+        .iter()
+        .map(|cmd| &cmd.proto.ident[..])
+        .filter(|cmd| match gen_group(cmd) {
+            Some(group) => groups.contains(&group),
+            None => false,
+        })
+        .collect()
+}
+
+/// Fails if the registry doesn't have the commands of `REQUIRED_CMDS`.
+pub fn check_registry(registry: &Registry) -> io::Result<()> {
+    for &required in REQUIRED_CMDS {
+        if !registry.cmds.iter().any(|cmd| cmd.proto.ident == required) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "`gpu_timing` requires `{}`, from GL 3.3 or `GL_ARB_timer_query`",
+                    required
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes the `gpu_timing` module, with the `CommandTiming` type and the state of the thread,
+/// which is whether the commands are measured and the queries that are waiting for their result.
+pub fn write_gpu_timing<W>(std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// The GPU time of the timed commands, measured with timestamp queries written before and
+        /// after each of them once `set_enabled(true)` has been called on the thread.
+        pub mod gpu_timing {{
+            use super::types;
+            use {std}::cell::{{Cell, RefCell}};
+
+            /// The GPU time that a command took.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+            pub struct CommandTiming {{
+                /// The symbol of the command, such as `\"glDrawArrays\"`.
+                pub command: &'static str,
+                /// The time between the timestamps written before and after the command, in
+                /// nanoseconds.
+                pub nanos: u64,
+            }}
+
+            {std}::thread_local! {{
+                static ENABLED: Cell<bool> = const {{ Cell::new(false) }};
+                static PENDING: RefCell<{std}::vec::Vec<(&'static str, [types::GLuint; 2])>> =
+                    const {{ RefCell::new({std}::vec::Vec::new()) }};
+            }}
+
+            /// Starts or stops measuring the timed commands called on this thread, which are not
+            /// measured by default.
+            pub fn set_enabled(enabled: bool) {{
+                ENABLED.with(|e| e.set(enabled));
+            }}
+
+            /// Returns whether the timed commands called on this thread are measured.
+            pub fn is_enabled() -> bool {{
+                ENABLED.with(|e| e.get())
+            }}
+
+            /// Records the queries written around `command`, whose results are read by
+            /// `gpu_timings`.
+            #[doc(hidden)]
+            pub fn push(command: &'static str, queries: [types::GLuint; 2]) {{
+                PENDING.with(|p| p.borrow_mut().push((command, queries)));
+            }}
+
+            /// Takes the queries whose results haven't been read yet, oldest first.
+            #[doc(hidden)]
+            pub fn take_pending() -> {std}::vec::Vec<(&'static str, [types::GLuint; 2])> {{
+                PENDING.with(|p| p.replace({std}::vec::Vec::new()))
+            }}
+        }}",
+        std = std_path,
+    )
+}
+
+/// Writes the methods writing and reading the queries, which are written inside the `impl` of the
+/// structure. The queries are written through the fields, so that they are not logged.
+pub fn write_methods<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    // The enums are written as numbers, `0x8E28` being `TIMESTAMP`, `0x8866` `QUERY_RESULT` and
+    // `0x8867` `QUERY_RESULT_AVAILABLE`.
+    let counter = |query: &str| {
+        super::gen_field_call(
+            registry,
+            options,
+            "QueryCounter",
+            &format!("{}, 0x8E28", query),
+        )
+    };
+
+    writeln!(
+        dest,
+        "/// Writes the timestamp before a timed command, if the commands are measured on this
+        /// thread and the context supports timestamp queries.
+        #[inline]
+        fn gpu_timing_begin(&self) -> Option<[types::GLuint; 2]> {{
+            if !gpu_timing::is_enabled() || !{is_loaded} {{
+                return None;
+            }}
+            let mut queries = [0; 2];
+            unsafe {{
+                {gen_queries};
+                {counter_begin};
+            }}
+            Some(queries)
+        }}
+
+        /// Writes the timestamp after the timed command `command`, if `gpu_timing_begin` wrote
+        /// one before it.
+        #[inline]
+        fn gpu_timing_end(&self, command: &'static str, queries: Option<[types::GLuint; 2]>) {{
+            if let Some(queries) = queries {{
+                unsafe {{
+                    {counter_end};
+                }}
+                gpu_timing::push(command, queries);
+            }}
+        }}
+
+        /// Returns the GPU time of the timed commands called on this thread whose timestamps are
+        /// available, oldest first, and keeps the others for a later call. Calling it once per
+        /// frame, with the context of the commands current, feeds a frame profiler.
+        #[allow(dead_code)]
+        pub fn gpu_timings(&self) -> {std}::vec::Vec<gpu_timing::CommandTiming> {{
+            let mut timings = {std}::vec::Vec::new();
+            let mut pending = {std}::vec::Vec::new();
+            for (command, queries) in gpu_timing::take_pending() {{
+                let mut available = 0;
+                unsafe {{
+                    {get_available};
+                }}
+                if available == 0 {{
+                    pending.push((command, queries));
+                    continue;
+                }}
+                let (mut start, mut end) = (0, 0);
+                unsafe {{
+                    {get_start};
+                    {get_end};
+                    {delete_queries};
+                }}
+                timings.push(gpu_timing::CommandTiming {{
+                    command,
+                    nanos: end.saturating_sub(start),
+                }});
+            }}
+            for (command, queries) in pending {{
+                gpu_timing::push(command, queries);
+            }}
+            timings
+        }}",
+        std = super::std_path(options),
+        is_loaded = super::gen_field_is_loaded(
+            options,
+            &format!(
+                "self.{}",
+                super::gen_field_path(registry, options, "QueryCounter")
+            )
+        ),
+        gen_queries =
+            super::gen_field_call(registry, options, "GenQueries", "2, queries.as_mut_ptr()"),
+        counter_begin = counter("queries[0]"),
+        counter_end = counter("queries[1]"),
+        get_available = super::gen_field_call(
+            registry,
+            options,
+            "GetQueryObjectiv",
+            "queries[1], 0x8867, &mut available"
+        ),
+        get_start = super::gen_field_call(
+            registry,
+            options,
+            "GetQueryObjectui64v",
+            "queries[0], 0x8866, &mut start"
+        ),
+        get_end = super::gen_field_call(
+            registry,
+            options,
+            "GetQueryObjectui64v",
+            "queries[1], 0x8866, &mut end"
+        ),
+        delete_queries =
+            super::gen_field_call(registry, options, "DeleteQueries", "2, queries.as_ptr()"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gen_group;
+    use CommandGroup;
+
+    #[test]
+    fn test_gen_group() {
+        assert_eq!(gen_group("DrawArrays"), Some(CommandGroup::Draw));
+        assert_eq!(
+            gen_group("MultiDrawElementsIndirect"),
+            Some(CommandGroup::Draw)
+        );
+        assert_eq!(gen_group("DrawBuffers"), None);
+        assert_eq!(gen_group("TexSubImage2D"), Some(CommandGroup::Upload));
+        assert_eq!(gen_group("BufferDataARB"), Some(CommandGroup::Upload));
+        assert_eq!(gen_group("DispatchCompute"), Some(CommandGroup::Compute));
+        assert_eq!(gen_group("Clear"), None);
+    }
+}
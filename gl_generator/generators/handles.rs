@@ -0,0 +1,199 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The handle types and the `typed` module emitted when `GeneratorOptions::handle_types` is set.
+//!
+//! The registry gives the kind of object that a `GLuint` names in the `class` attribute of the
+//! parameters and return values of the commands. Each class becomes a `#[repr(transparent)]`
+//! newtype, and the `typed` module has a wrapper of each command with such a binding, taking and
+//! returning the newtypes. The commands themselves are unchanged.
+
+use super::helpers::Dispatch;
+use registry::{Binding, Cmd, Registry};
+use std::collections::BTreeSet;
+use std::io;
+
+/// The name of the newtype of the objects of `class`, e.g. `VertexArray` for `vertex array`.
+fn handle_name(class: &str) -> String {
+    class
+        .split(&[' ', '_', '-'][..])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            Some(first).into_iter().chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+/// The type of `binding` in the `typed` module, with the newtype of its class in place of
+/// `GLuint`, or `None` if it doesn't name an object.
+fn handle_ty(binding: &Binding) -> Option<String> {
+    let class = binding.class.as_ref()?;
+    let handle = format!("types::handles::{}", handle_name(class));
+    match &*binding.ty {
+        "types::GLuint" => Some(handle),
+        "*const types::GLuint" => Some(format!("*const {}", handle)),
+        "*mut types::GLuint" => Some(format!("*mut {}", handle)),
+        _ => None,
+    }
+}
+
+/// Whether `cmd` takes or returns an object name that has a newtype.
+fn has_handles(cmd: &Cmd) -> bool {
+    handle_ty(&cmd.proto).is_some() || cmd.params.iter().any(|param| handle_ty(param).is_some())
+}
+
+/// The classes of the objects that the commands of the registry name, sorted by name.
+fn handle_classes(registry: &Registry) -> BTreeSet<&str> {
+    registry
+        .cmds
+        .iter()
+        .flat_map(|cmd| Some(&cmd.proto).into_iter().chain(&cmd.params))
+        .filter(|binding| handle_ty(binding).is_some())
+        .filter_map(|binding| binding.class.as_ref().map(|class| &class[..]))
+        .collect()
+}
+
+/// Creates a `handles` module with a newtype of `GLuint` for each class of objects, meant to be
+/// written inside the `types` module. Nothing is written if the registry has no classes.
+pub fn write_handle_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let classes = handle_classes(registry);
+    if classes.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        "/// The names of the objects of each class, which the commands of the `typed` module take
+        /// and return in place of `GLuint`.
+        pub mod handles {{
+            use super::GLuint;"
+    )?;
+
+    for class in classes {
+        writeln!(
+            dest,
+            "/// The name of a {class} object.
+            #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[repr(transparent)]
+            pub struct {name}(pub GLuint);",
+            class = class,
+            name = handle_name(class),
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates a `typed` module with a wrapper of each command that takes or returns an object name,
+/// with the newtypes of `types::handles` in its signature. With `Dispatch::Method`, the wrappers
+/// take the bindings as their first parameter.
+pub fn write_typed_commands<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let cmds: Vec<&Cmd> = registry
+        .cmds
+        .iter()
+        .filter(|cmd| has_handles(cmd))
+        .collect();
+    if cmds.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        "/// The commands that take or return object names, with the types of `types::handles` in
+        /// place of `GLuint`.
+        pub mod typed {{
+            #![allow(non_snake_case, dead_code, unused_imports, unused_unsafe)]
+            #![allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit)]
+            use super::__gl_imports;
+            use super::types;"
+    )?;
+
+    for cmd in cmds {
+        let name = super::gen_cmd_name(registry, options, &cmd.proto.ident);
+
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        if dispatch == Dispatch::Method {
+            params.push(format!(
+                "gl: &super::{}",
+                super::gen_struct_name(registry.api)
+            ));
+        }
+        for param in &cmd.params {
+            let ident = super::gen_ident(&param.ident);
+            match handle_ty(param) {
+                Some(ty) => {
+                    params.push(format!("{}: {}", ident, ty));
+                    if ty.starts_with('*') {
+                        args.push(format!("{} as {}", ident, param.ty));
+                    } else {
+                        args.push(format!("{}.0", ident));
+                    }
+                },
+                None => {
+                    params.push(format!("{}: {}", ident, param.ty));
+                    args.push(ident.into_owned());
+                },
+            }
+        }
+
+        let call = match dispatch {
+            Dispatch::Free => format!("super::{}({})", name, args.join(", ")),
+            Dispatch::Method => format!("gl.{}({})", name, args.join(", ")),
+        };
+        let (return_ty, body) = match handle_ty(&cmd.proto) {
+            Some(ref ty) if ty.starts_with('*') => (ty.clone(), format!("{} as {}", call, ty)),
+            Some(ty) => {
+                let body = format!("{}({})", ty, call);
+                (ty, body)
+            },
+            None => (cmd.proto.ty.to_string(), call),
+        };
+
+        writeln!(
+            dest,
+            "{cfg}
+            /// Calls `{name}` with the object names wrapped in their handle type.
+            #[inline]
+            pub unsafe fn {name}({params}) -> {return_ty} {{
+                unsafe {{ {body} }}
+            }}",
+            cfg = super::gen_cfg_attrs(
+                super::gen_cmd_cfg(registry, options, &cmd.proto.ident)
+                    .as_ref()
+                    .map(|p| &p[..]),
+                false,
+            ),
+            name = name,
+            params = params.join(", "),
+            return_ty = return_ty,
+            body = body,
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
@@ -0,0 +1,1581 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Safe convenience wrappers emitted when `GeneratorOptions::helpers` is set.
+//!
+//! The helpers only ever call commands that are present in the registry, so the same code works
+//! for every API and version.
+
+use registry::Registry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+
+/// How the generated bindings expose their commands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dispatch {
+    /// Commands are free functions at the root of the bindings.
+    Free,
+    /// Commands are methods of the struct named after the API.
+    Method,
+}
+
+impl Dispatch {
+    /// The expression that calls the command `name` from inside a helper.
+    fn call(self, name: &str) -> String {
+        match self {
+            Dispatch::Free => format!("super::{}", name),
+            Dispatch::Method => format!("gl.{}", name),
+        }
+    }
+}
+
+/// The `glUniform*` suffixes and the element type they upload.
+const UNIFORM_TYPES: &[(&str, &str)] = &[
+    ("f", "GLfloat"),
+    ("i", "GLint"),
+    ("ui", "GLuint"),
+    ("d", "GLdouble"),
+];
+
+/// The error codes returned by `glGetError`, the variant of `GlError` they become and their
+/// description.
+const ERRORS: &[(&str, &str, &str)] = &[
+    (
+        "INVALID_ENUM",
+        "InvalidEnum",
+        "invalid enum: an enumerated argument has an unacceptable value",
+    ),
+    (
+        "INVALID_VALUE",
+        "InvalidValue",
+        "invalid value: a numeric argument is out of range",
+    ),
+    (
+        "INVALID_OPERATION",
+        "InvalidOperation",
+        "invalid operation: the command is not legal for the current state",
+    ),
+    (
+        "STACK_OVERFLOW",
+        "StackOverflow",
+        "stack overflow: the command would cause a stack overflow",
+    ),
+    (
+        "STACK_UNDERFLOW",
+        "StackUnderflow",
+        "stack underflow: the command would cause a stack underflow",
+    ),
+    (
+        "OUT_OF_MEMORY",
+        "OutOfMemory",
+        "out of memory: there is not enough memory left to execute the command",
+    ),
+    (
+        "INVALID_FRAMEBUFFER_OPERATION",
+        "InvalidFramebufferOperation",
+        "invalid framebuffer operation: the framebuffer object is not complete",
+    ),
+    (
+        "CONTEXT_LOST",
+        "ContextLost",
+        "context lost: the context has been lost due to a graphics card reset",
+    ),
+];
+
+/// The `GlError` variant of `NO_ERROR`, which only exists with
+/// `GeneratorOptions::typed_get_error`.
+const NO_ERROR: (&str, &str, &str) = ("NO_ERROR", "NoError", "no error");
+
+/// An enum passed to debug callbacks: its name, its documentation, and the enum of the registry
+/// each variant comes from with the name of the variant.
+type DebugEnum = (
+    &'static str,
+    &'static str,
+    &'static [(&'static str, &'static str)],
+);
+
+/// The enums passed to debug callbacks, each with the enum of the registry its variants come
+/// from and the name of the variant.
+const DEBUG_ENUMS: &[DebugEnum] = &[
+    (
+        "Source",
+        "The part of the implementation that generated a debug message.",
+        &[
+            ("DEBUG_SOURCE_API", "Api"),
+            ("DEBUG_SOURCE_WINDOW_SYSTEM", "WindowSystem"),
+            ("DEBUG_SOURCE_SHADER_COMPILER", "ShaderCompiler"),
+            ("DEBUG_SOURCE_THIRD_PARTY", "ThirdParty"),
+            ("DEBUG_SOURCE_APPLICATION", "Application"),
+            ("DEBUG_SOURCE_OTHER", "Other"),
+        ],
+    ),
+    (
+        "Type",
+        "The kind of event that a debug message reports.",
+        &[
+            ("DEBUG_TYPE_ERROR", "Error"),
+            ("DEBUG_TYPE_DEPRECATED_BEHAVIOR", "DeprecatedBehavior"),
+            ("DEBUG_TYPE_UNDEFINED_BEHAVIOR", "UndefinedBehavior"),
+            ("DEBUG_TYPE_PORTABILITY", "Portability"),
+            ("DEBUG_TYPE_PERFORMANCE", "Performance"),
+            ("DEBUG_TYPE_MARKER", "Marker"),
+            ("DEBUG_TYPE_PUSH_GROUP", "PushGroup"),
+            ("DEBUG_TYPE_POP_GROUP", "PopGroup"),
+            ("DEBUG_TYPE_OTHER", "Other"),
+        ],
+    ),
+    (
+        "Severity",
+        "How important a debug message is.",
+        &[
+            ("DEBUG_SEVERITY_HIGH", "High"),
+            ("DEBUG_SEVERITY_MEDIUM", "Medium"),
+            ("DEBUG_SEVERITY_LOW", "Low"),
+            ("DEBUG_SEVERITY_NOTIFICATION", "Notification"),
+        ],
+    ),
+];
+
+/// Writes all the helpers that apply to the commands of the registry if
+/// `GeneratorOptions::helpers` is set, the `GetError` returning a `GlError` if
+/// `GeneratorOptions::typed_get_error` is set, the `Try*` creation commands if
+/// `GeneratorOptions::nonzero_create` is set, and the shims named after the aliases of the
+/// commands if `GeneratorOptions::alias_shims` is set.
+///
+/// The helpers that are `unsafe` functions call the commands in an `unsafe` block, so that they
+/// compile cleanly in crates that deny `unsafe_op_in_unsafe_fn`.
+pub fn write_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let std_path = super::std_path(options);
+    let typed_get_error = super::has_typed_get_error(registry, options);
+
+    if options.helpers {
+        write_uniform_helpers(registry, dispatch, std_path, dest)?;
+        write_matrix_uniform_helpers(registry, dispatch, std_path, dest)?;
+        write_internal_format_helpers(registry, dispatch, std_path, dest)?;
+        write_shader_source_helpers(registry, dispatch, std_path, dest)?;
+        write_map_helpers(registry, dispatch, std_path, dest)?;
+    }
+    if options.helpers || typed_get_error {
+        write_error_helpers(registry, typed_get_error, std_path, dest)?;
+    }
+    if typed_get_error {
+        write_typed_get_error(registry, dispatch, dest)?;
+    }
+    if options.helpers {
+        write_check_error(registry, dispatch, typed_get_error, options, dest)?;
+    }
+    if options.nonzero_create {
+        write_nonzero_create(registry, dispatch, options, dest)?;
+    }
+    if options.alias_shims {
+        write_alias_shims(registry, dispatch, options, dest)?;
+    }
+    if options.helpers {
+        write_debug_callback_helpers(registry, dispatch, std_path, dest)?;
+        write_object_label_helpers(registry, dispatch, std_path, dest)?;
+        write_debug_group_helpers(registry, dispatch, std_path, dest)?;
+    }
+    Ok(())
+}
+
+fn has_cmd(registry: &Registry, name: &str) -> bool {
+    registry.cmds.iter().any(|cmd| cmd.proto.ident == name)
+}
+
+/// Creates a `uniform` module with a `Uniform` trait implemented for every value type that has a
+/// matching `glUniform*` command, and a `set_uniform` function dispatching through it.
+fn write_uniform_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let count = format!(
+        "<types::GLsizei as {}::convert::TryFrom<usize>>::try_from(self.len())\
+         .expect(\"the slice has more than GLsizei::MAX uniform values\")",
+        std_path
+    );
+    let mut impls = Vec::new();
+    for &(suffix, ty) in UNIFORM_TYPES {
+        let ty = format!("types::{}", ty);
+        for n in 1..5 {
+            let scalar = format!("Uniform{}{}", n, suffix);
+            let vector = format!("Uniform{}{}v", n, suffix);
+
+            if n == 1 && has_cmd(registry, &scalar) {
+                impls.push((
+                    ty.clone(),
+                    format!("{}(location, *self)", dispatch.call(&scalar)),
+                ));
+            }
+            if !has_cmd(registry, &vector) {
+                continue;
+            }
+            if n == 1 {
+                impls.push((
+                    format!("[{}]", ty),
+                    format!(
+                        "{}(location, {}, self.as_ptr())",
+                        dispatch.call(&vector),
+                        count
+                    ),
+                ));
+            } else {
+                impls.push((
+                    format!("[{}; {}]", ty, n),
+                    format!("{}(location, 1, self.as_ptr())", dispatch.call(&vector)),
+                ));
+                impls.push((
+                    format!("[[{}; {}]]", ty, n),
+                    format!(
+                        "{}(location, {}, self.as_ptr() as *const {})",
+                        dispatch.call(&vector),
+                        count,
+                        ty
+                    ),
+                ));
+            }
+        }
+    }
+
+    if impls.is_empty() {
+        return Ok(());
+    }
+
+    let (set_params, set_args) = match dispatch {
+        Dispatch::Free => ("location: types::GLint", "location"),
+        Dispatch::Method => (
+            "gl: &super::{api}, location: types::GLint",
+            "self, location",
+        ),
+    };
+    let set_params = set_params.replace("{api}", super::gen_struct_name(registry.api));
+
+    writeln!(
+        dest,
+        r#"
+        /// Uploading uniform values without picking the `Uniform*` command by hand.
+        pub mod uniform {{
+            #![allow(dead_code)]
+            use super::types;
+
+            /// A value that can be uploaded to a uniform variable.
+            ///
+            /// Scalars and `[T; N]` arrays upload a single value, slices upload one value per
+            /// element starting at the given location. Uploading a slice panics if it has more
+            /// than `GLsizei::MAX` elements.
+            pub trait Uniform {{
+                /// Uploads the value to the uniform at `location` of the current program.
+                fn set(&self, {set_params});
+            }}
+        "#,
+        set_params = set_params,
+    )?;
+
+    for (ty, call) in impls {
+        writeln!(
+            dest,
+            "impl Uniform for {ty} {{
+                #[inline]
+                fn set(&self, {set_params}) {{
+                    unsafe {{ {call} }}
+                }}
+            }}",
+            ty = ty,
+            set_params = set_params,
+            call = call,
+        )?;
+    }
+
+    writeln!(dest, "}}")?;
+
+    let doc = "/// Uploads `value` to the uniform at `location` of the current program, using the
+        /// `Uniform*` command that matches the type of the value.";
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "{doc}
+            #[allow(dead_code)]
+            #[inline]
+            pub fn set_uniform<T: ?Sized + uniform::Uniform>(location: types::GLint, value: &T) {{
+                value.set({set_args})
+            }}",
+            doc = doc,
+            set_args = set_args,
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {doc}
+                #[allow(dead_code)]
+                #[inline]
+                pub fn set_uniform<T: ?Sized + uniform::Uniform>(&self, location: types::GLint, value: &T) {{
+                    value.set({set_args})
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            doc = doc,
+            set_args = set_args,
+        ),
+    }
+}
+
+/// The shapes of the `glUniformMatrix*` commands: the suffix of the command, and the number of
+/// columns and rows of the matrix.
+const MATRIX_SHAPES: &[(&str, usize, usize)] = &[
+    ("2", 2, 2),
+    ("3", 3, 3),
+    ("4", 4, 4),
+    ("2x3", 2, 3),
+    ("3x2", 3, 2),
+    ("2x4", 2, 4),
+    ("4x2", 4, 2),
+    ("3x4", 3, 4),
+    ("4x3", 4, 3),
+];
+
+/// Creates a `uniform_matrix*` function for every `glUniformMatrix*` command, which uploads a
+/// single matrix given as a nested array, and a `uniform_matrix*_slice` function uploading a slice
+/// of them. Both take the `transpose` flag as a `bool` and compute the count and the pointer.
+fn write_matrix_uniform_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let receiver = match dispatch {
+        Dispatch::Free => "",
+        Dispatch::Method => "&self, ",
+    };
+
+    let mut fns = Vec::new();
+    for &(suffix, ty) in UNIFORM_TYPES {
+        if suffix != "f" && suffix != "d" {
+            continue;
+        }
+        for &(shape, columns, rows) in MATRIX_SHAPES {
+            let cmd = format!("UniformMatrix{}{}v", shape, suffix);
+            if !has_cmd(registry, &cmd) {
+                continue;
+            }
+            let call = match dispatch {
+                Dispatch::Free => cmd.clone(),
+                Dispatch::Method => format!("self.{}", cmd),
+            };
+            fns.push(format!(
+                "/// Uploads a {columns}x{rows} matrix to the uniform at `location` of the current
+                /// program with `{cmd}`. The elements of `value` are read in column-major order,
+                /// or in row-major order if `transpose` is `true`.
+                #[allow(dead_code)]
+                #[inline]
+                pub fn uniform_matrix{shape}{suffix}v({receiver}location: types::GLint, transpose: bool, value: &[[types::{ty}; {rows}]; {columns}]) {{
+                    let transpose = transpose as types::GLboolean;
+                    unsafe {{ {call}(location, 1, transpose, value.as_ptr() as *const types::{ty}) }}
+                }}
+
+                /// Uploads one {columns}x{rows} matrix per element of `values`, starting at the
+                /// uniform at `location` of the current program, with `{cmd}`.
+                ///
+                /// Panics if `values` has more than `GLsizei::MAX` elements.
+                #[allow(dead_code)]
+                #[inline]
+                pub fn uniform_matrix{shape}{suffix}v_slice({receiver}location: types::GLint, transpose: bool, values: &[[[types::{ty}; {rows}]; {columns}]]) {{
+                    let transpose = transpose as types::GLboolean;
+                    let count = <types::GLsizei as {std}::convert::TryFrom<usize>>::try_from(values.len())
+                        .expect(\"there are more than GLsizei::MAX matrices\");
+                    unsafe {{ {call}(location, count, transpose, values.as_ptr() as *const types::{ty}) }}
+                }}",
+                columns = columns,
+                rows = rows,
+                cmd = cmd,
+                shape = shape,
+                suffix = suffix,
+                receiver = receiver,
+                ty = ty,
+                call = call,
+                std = std_path,
+            ));
+        }
+    }
+
+    if fns.is_empty() {
+        return Ok(());
+    }
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns.join("\n")),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns.join("\n"),
+        ),
+    }
+}
+
+/// Creates an `internal_format` module with an `InternalFormat` enum of the `InternalFormat` group
+/// of the registry, and a `tex_image_2d` function taking it in place of the `GLint` that
+/// `TexImage2D` takes.
+fn write_internal_format_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let group = match registry.groups.get("InternalFormat") {
+        Some(group) => group,
+        None => return Ok(()),
+    };
+    let cmd = match registry
+        .cmds
+        .iter()
+        .find(|cmd| cmd.proto.ident == "TexImage2D")
+    {
+        Some(cmd) => cmd,
+        None => return Ok(()),
+    };
+
+    // Several enums of the group share a value, such as `DEPTH_COMPONENT16` and
+    // `DEPTH_COMPONENT16_ARB`, so only the shortest name of each value becomes a variant.
+    let mut variants = BTreeMap::new();
+    for enm in &registry.enums {
+        if enm.ty != "GLenum" || !group.enums.contains(&enm.ident) {
+            continue;
+        }
+        let ident = variants.entry(&enm.value).or_insert(&enm.ident);
+        if enm.ident.len() < ident.len() {
+            *ident = &enm.ident;
+        }
+    }
+
+    if variants.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        r#"
+        /// Passing texture internal formats that are checked to be valid.
+        pub mod internal_format {{
+            #![allow(dead_code)]
+            use super::types;
+
+            /// A value of the `InternalFormat` group, such as `RGBA8` or `DEPTH24_STENCIL8`.
+            #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            #[repr(u32)]
+            pub enum InternalFormat {{"#
+    )?;
+
+    for (value, ident) in &variants {
+        writeln!(dest, "{} = {},", super::gen_ident(ident), value)?;
+    }
+
+    writeln!(
+        dest,
+        r#"}}
+
+            impl {std}::convert::TryFrom<types::GLenum> for InternalFormat {{
+                /// The value that is not an internal format.
+                type Error = types::GLenum;
+
+                fn try_from(value: types::GLenum) -> Result<InternalFormat, types::GLenum> {{
+                    match value {{"#,
+        std = std_path,
+    )?;
+
+    for (value, ident) in &variants {
+        writeln!(
+            dest,
+            "{} => Ok(InternalFormat::{}),",
+            value,
+            super::gen_ident(ident)
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"_ => Err(value),
+                    }}
+                }}
+            }}
+
+            impl From<InternalFormat> for types::GLenum {{
+                #[inline]
+                fn from(format: InternalFormat) -> types::GLenum {{
+                    format as types::GLenum
+                }}
+            }}
+        }}"#
+    )?;
+
+    let params: Vec<_> = cmd
+        .params
+        .iter()
+        .map(|param| match param.group {
+            Some(ref group) if group == "InternalFormat" => format!(
+                "{}: internal_format::InternalFormat",
+                super::gen_ident(&param.ident)
+            ),
+            _ => format!("{}: {}", super::gen_ident(&param.ident), param.ty),
+        })
+        .collect();
+    let args: Vec<_> = cmd
+        .params
+        .iter()
+        .map(|param| match param.group {
+            Some(ref group) if group == "InternalFormat" => {
+                format!("{} as {}", super::gen_ident(&param.ident), param.ty)
+            },
+            _ => super::gen_ident(&param.ident).into_owned(),
+        })
+        .collect();
+
+    let doc = "/// Calls `TexImage2D` with an internal format that is known to be valid.";
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "{doc}
+            #[allow(dead_code, unused_unsafe, clippy::missing_safety_doc, clippy::too_many_arguments)]
+            #[inline]
+            pub unsafe fn tex_image_2d({params}) {{
+                unsafe {{ TexImage2D({args}) }}
+            }}",
+            doc = doc,
+            params = params.join(", "),
+            args = args.join(", "),
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {doc}
+                #[allow(dead_code, unused_unsafe, clippy::missing_safety_doc, clippy::too_many_arguments)]
+                #[inline]
+                pub unsafe fn tex_image_2d(&self, {params}) {{
+                    unsafe {{ self.TexImage2D({args}) }}
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            doc = doc,
+            params = params.join(", "),
+            args = args.join(", "),
+        ),
+    }
+}
+
+/// Creates the `shader_source` and `shader_sources` functions, which pass Rust strings to
+/// `ShaderSource` along with their lengths, so that no `CString` is needed.
+fn write_shader_source_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !has_cmd(registry, "ShaderSource") {
+        return Ok(());
+    }
+
+    let (receiver, call) = match dispatch {
+        Dispatch::Free => ("", "ShaderSource"),
+        Dispatch::Method => ("&self, ", "self.ShaderSource"),
+    };
+    let fns = format!(
+        "/// Replaces the source code of `shader` with `src`.
+        ///
+        /// Panics if `src` is longer than `GLint::MAX` bytes.
+        #[allow(dead_code)]
+        #[inline]
+        pub fn shader_source({receiver}shader: types::GLuint, src: &str) {{
+            let string = src.as_ptr() as *const types::GLchar;
+            // A negative length would make the implementation read up to a NUL past the string.
+            let length = <types::GLint as {std}::convert::TryFrom<usize>>::try_from(src.len())
+                .expect(\"the shader source is longer than GLint::MAX bytes\");
+            unsafe {{ {call}(shader, 1, &string, &length) }}
+        }}
+
+        /// Replaces the source code of `shader` with the concatenation of `srcs`.
+        ///
+        /// Panics if there are more than `GLsizei::MAX` strings or one of them is longer than
+        /// `GLint::MAX` bytes.
+        #[allow(dead_code)]
+        pub fn shader_sources({receiver}shader: types::GLuint, srcs: &[&str]) {{
+            let count = <types::GLsizei as {std}::convert::TryFrom<usize>>::try_from(srcs.len())
+                .expect(\"there are more than GLsizei::MAX shader sources\");
+            let strings: {std}::vec::Vec<_> =
+                srcs.iter().map(|src| src.as_ptr() as *const types::GLchar).collect();
+            let lengths: {std}::vec::Vec<_> = srcs
+                .iter()
+                .map(|src| {{
+                    <types::GLint as {std}::convert::TryFrom<usize>>::try_from(src.len())
+                        .expect(\"the shader source is longer than GLint::MAX bytes\")
+                }})
+                .collect();
+            unsafe {{ {call}(shader, count, strings.as_ptr(), lengths.as_ptr()) }}
+        }}",
+        receiver = receiver,
+        call = call,
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns,
+        ),
+    }
+}
+
+/// Creates a `map` module with a `typed_slice` function viewing a pointer returned by a buffer
+/// mapping command as a slice, and a `map_buffer_range` function mapping a range of values with
+/// `MapBufferRange`. The pointer is checked to be aligned for the type of the values, which the
+/// implementation doesn't guarantee, since creating a slice from a misaligned pointer is undefined
+/// behavior.
+fn write_map_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !has_cmd(registry, "MapBufferRange") {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        r#"
+        /// Viewing mapped buffers as typed slices.
+        pub mod map {{
+            #![allow(dead_code)]
+
+            /// Returns the `len` values of type `T` at `ptr`, which a buffer mapping command
+            /// returned, or `None` if `ptr` is null or not aligned for `T`. A misaligned pointer
+            /// also fails a debug assertion.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to `len` initialized values of type `T`, which stay mapped and are
+            /// not accessed through another pointer for the lifetime `'a`.
+            pub unsafe fn typed_slice<'a, T>(
+                ptr: *mut {std}::os::raw::c_void,
+                len: usize,
+            ) -> Option<&'a mut [T]> {{
+                if ptr.is_null() {{
+                    return None;
+                }}
+                let aligned = (ptr as usize & ({std}::mem::align_of::<T>() - 1)) == 0;
+                debug_assert!(
+                    aligned,
+                    "the mapped pointer {{:p}} is not aligned for `{{}}`",
+                    ptr,
+                    {std}::any::type_name::<T>()
+                );
+                if !aligned {{
+                    return None;
+                }}
+                Some(unsafe {{ {std}::slice::from_raw_parts_mut(ptr as *mut T, len) }})
+            }}
+        }}"#,
+        std = std_path,
+    )?;
+
+    let (receiver, call) = match dispatch {
+        Dispatch::Free => ("", "MapBufferRange"),
+        Dispatch::Method => ("&self, ", "self.MapBufferRange"),
+    };
+    let fns = format!(
+        "/// Maps `len` values of type `T` of the buffer bound to `target`, starting at the value
+        /// `offset`, with the `access` flags of `MapBufferRange`, and returns them as a slice, or
+        /// `None` if the range doesn't fit in a `GLintptr`, the mapping failed or the pointer is
+        /// not aligned for `T`.
+        ///
+        /// # Safety
+        ///
+        /// The buffer must hold initialized values of type `T`, and the slice must not be used
+        /// once the buffer has been unmapped.
+        #[allow(dead_code)]
+        pub unsafe fn map_buffer_range<'a, T>({receiver}target: types::GLenum, offset: usize, len: usize, access: types::GLbitfield) -> Option<&'a mut [T]> {{
+            let size = {std}::mem::size_of::<T>();
+            // A wrapped range would map fewer bytes than the slice covers.
+            let offset = offset
+                .checked_mul(size)
+                .and_then(|offset| <types::GLintptr as {std}::convert::TryFrom<usize>>::try_from(offset).ok())?;
+            let length = len
+                .checked_mul(size)
+                .and_then(|length| <types::GLsizeiptr as {std}::convert::TryFrom<usize>>::try_from(length).ok())?;
+            let ptr = unsafe {{ {call}(target, offset, length, access) }};
+            unsafe {{ map::typed_slice(ptr, len) }}
+        }}",
+        receiver = receiver,
+        call = call,
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns,
+        ),
+    }
+}
+
+/// Creates an `error` module with a `GlError` enum of the error codes that `GetError` can return,
+/// which converts from a `GLenum` with `TryFrom` and implements `Display` and `Error`.
+///
+/// With `typed`, `GlError` also has a `NoError` variant and an `Other` variant for the codes that
+/// the registry doesn't have, and converts from any `GLenum` with `From`.
+fn write_error_helpers<W>(
+    registry: &Registry,
+    typed: bool,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !has_cmd(registry, "GetError") {
+        return Ok(());
+    }
+
+    let no_error = if typed { Some(NO_ERROR) } else { None };
+    let errors: Vec<_> = no_error
+        .iter()
+        .chain(ERRORS)
+        .filter_map(|&(ident, variant, desc)| {
+            registry
+                .enums
+                .iter()
+                .find(|enm| enm.ident == ident && enm.ty == "GLenum")
+                .map(|enm| (&enm.value, variant, desc))
+        })
+        .collect();
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        r#"
+        /// The errors reported by `GetError`.
+        pub mod error {{
+            #![allow(dead_code)]
+            use super::types;
+"#
+    )?;
+
+    if typed {
+        writeln!(
+            dest,
+            r#"/// An error code returned by `GetError`.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            pub enum GlError {{"#
+        )?;
+        for &(_, variant, _) in &errors {
+            writeln!(dest, "{},", variant)?;
+        }
+        writeln!(
+            dest,
+            r#"/// An error code that the bindings don't know, such as one introduced by an
+                /// extension that is not part of them.
+                Other(types::GLenum),
+            }}
+
+            impl From<types::GLenum> for GlError {{
+                fn from(value: types::GLenum) -> GlError {{
+                    match value {{"#
+        )?;
+        for &(value, variant, _) in &errors {
+            writeln!(dest, "{} => GlError::{},", value, variant)?;
+        }
+        writeln!(
+            dest,
+            r#"_ => GlError::Other(value),
+                    }}
+                }}
+            }}
+
+            impl From<GlError> for types::GLenum {{
+                fn from(error: GlError) -> types::GLenum {{
+                    match error {{"#
+        )?;
+        for &(value, variant, _) in &errors {
+            writeln!(dest, "GlError::{} => {},", variant, value)?;
+        }
+        writeln!(dest, "GlError::Other(value) => value,\n}}\n}}\n}}")?;
+    } else {
+        writeln!(
+            dest,
+            r#"/// An error code returned by `GetError`, other than `NO_ERROR`.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            #[repr(u32)]
+            pub enum GlError {{"#
+        )?;
+        for &(value, variant, _) in &errors {
+            writeln!(dest, "{} = {},", variant, value)?;
+        }
+        writeln!(
+            dest,
+            r#"}}
+
+            impl {std}::convert::TryFrom<types::GLenum> for GlError {{
+                /// The value that is not an error code, such as `NO_ERROR`.
+                type Error = types::GLenum;
+
+                fn try_from(value: types::GLenum) -> Result<GlError, types::GLenum> {{
+                    match value {{"#,
+            std = std_path,
+        )?;
+        for &(value, variant, _) in &errors {
+            writeln!(dest, "{} => Ok(GlError::{}),", value, variant)?;
+        }
+        writeln!(
+            dest,
+            r#"_ => Err(value),
+                    }}
+                }}
+            }}
+
+            impl From<GlError> for types::GLenum {{
+                #[inline]
+                fn from(error: GlError) -> types::GLenum {{
+                    error as types::GLenum
+                }}
+            }}"#
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"impl {std}::fmt::Display for GlError {{
+                fn fmt(&self, f: &mut {std}::fmt::Formatter) -> {std}::fmt::Result {{
+                    f.write_str(match *self {{"#,
+        std = std_path,
+    )?;
+
+    for &(_, variant, desc) in &errors {
+        writeln!(dest, "GlError::{} => {:?},", variant, desc)?;
+    }
+    if typed {
+        writeln!(
+            dest,
+            r#"GlError::Other(value) => return write!(f, "unknown error {{:#06x}}", value),"#
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"}})
+                }}
+            }}
+
+            impl {std}::error::Error for GlError {{}}
+        }}"#,
+        std = std_path,
+    )
+}
+
+/// Creates the `check_error` function, or method for the struct generators, which reads the error
+/// flags with `GetError` until it returns `NO_ERROR` and panics in debug builds if it returned
+/// anything else, and the `check_gl_error!` macro calling it in debug builds for the global
+/// bindings, which is exported if `GeneratorOptions::macro_export_path` is set.
+fn write_check_error<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    typed: bool,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let std_path = super::std_path(options);
+    let (export, path) = super::macro_export(options);
+    let has_enum = |ident: &str| registry.enums.iter().any(|enm| enm.ident == ident);
+    if !has_cmd(registry, "GetError") || !has_enum("NO_ERROR") {
+        return Ok(());
+    }
+
+    let names: Vec<_> = ERRORS
+        .iter()
+        .filter(|&&(ident, _, _)| has_enum(ident))
+        .map(|&(ident, _, _)| {
+            format!(
+                "{} => {}::string::String::from(\"GL_{}\"),",
+                ident, std_path, ident
+            )
+        })
+        .collect();
+    let get_error = if typed { "GetErrorRaw" } else { "GetError" };
+    let (receiver, call) = match dispatch {
+        Dispatch::Free => (String::new(), get_error.to_string()),
+        Dispatch::Method => ("&self, ".to_string(), format!("self.{}", get_error)),
+    };
+    let func = format!(
+        r#"/// Reads the error flags with `GetError` until it returns `NO_ERROR`, and returns the
+        /// errors. In debug builds, panics if there were any, with the name of each error,
+        /// `context` and the location of the caller.
+        ///
+        /// At most 32 errors are read, since some implementations keep returning one when no
+        /// context is current.
+        #[allow(dead_code)]
+        #[track_caller]
+        pub fn check_error({receiver}context: &str) -> {std}::vec::Vec<types::GLenum> {{
+            let mut errors = {std}::vec::Vec::new();
+            while errors.len() < 32 {{
+                let error = unsafe {{ {call}() }};
+                if error == NO_ERROR {{
+                    break;
+                }}
+                errors.push(error);
+            }}
+            if cfg!(debug_assertions) && !errors.is_empty() {{
+                let names: {std}::vec::Vec<_> = errors
+                    .iter()
+                    .map(|&error| match error {{
+                        {names}
+                        _ => {std}::format!("{{:#06x}}", error),
+                    }})
+                    .collect();
+                panic!(
+                    "{{}} ({{}}) at {{}}",
+                    names.join(", "),
+                    context,
+                    {std}::panic::Location::caller()
+                );
+            }}
+            errors
+        }}"#,
+        receiver = receiver,
+        call = call,
+        names = names.join("\n"),
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            r#"{func}
+
+            /// Calls `check_error` with the given context, or an empty one, in debug builds, and
+            /// expands to nothing in release builds.{scope}
+            {export}
+            macro_rules! check_gl_error {{
+                () => {{{{
+                    #[cfg(debug_assertions)]
+                    {{
+                        {path}check_error("");
+                    }}
+                }}}};
+                ($context:expr) => {{{{
+                    #[cfg(debug_assertions)]
+                    {{
+                        {path}check_error($context);
+                    }}
+                }}}};
+            }}"#,
+            func = func,
+            scope = if options.macro_export_path.is_some() {
+                ""
+            } else {
+                " `check_error` must be in scope."
+            },
+            export = export,
+            path = path,
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {func}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            func = func,
+        ),
+    }
+}
+
+/// Creates the `GetError` function, or method for the struct generators, which returns the result
+/// of `GetErrorRaw` as an `error::GlError`.
+fn write_typed_get_error<W>(registry: &Registry, dispatch: Dispatch, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let doc =
+        "/// Returns the value of the error flag as an `error::GlError`, which is `NoError` if no
+        /// error has been recorded since the last call, and resets it. `GetErrorRaw` returns it
+        /// as a `GLenum`.";
+
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "{doc}
+            #[allow(non_snake_case, dead_code, unused_unsafe, clippy::missing_safety_doc)]
+            #[inline]
+            pub unsafe fn GetError() -> error::GlError {{
+                error::GlError::from(unsafe {{ GetErrorRaw() }})
+            }}",
+            doc = doc,
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {doc}
+                #[allow(non_snake_case, dead_code, unused_unsafe, clippy::missing_safety_doc)]
+                #[inline]
+                pub unsafe fn GetError(&self) -> error::GlError {{
+                    error::GlError::from(unsafe {{ self.GetErrorRaw() }})
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            doc = doc,
+        ),
+    }
+}
+
+/// Creates a `Try*` function, or method for the struct generators, for each `Create*` command
+/// returning a `GLuint`, such as `TryCreateProgram`, which returns `None` where the command
+/// returns `0` to signal that the object couldn't be created.
+fn write_nonzero_create<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let std_path = super::std_path(options);
+    let cmds = registry
+        .cmds
+        .iter()
+        .filter(|cmd| cmd.proto.ident.starts_with("Create") && cmd.proto.ty == "types::GLuint");
+
+    let mut fns = Vec::new();
+    for cmd in cmds {
+        let ident = super::gen_ident(&cmd.proto.ident);
+        fns.push(format!(
+            "{cfg}
+            /// Calls `{ident}`, returning `None` if the object couldn't be created.
+            #[allow(non_snake_case, dead_code, unused_unsafe, clippy::missing_safety_doc)]
+            #[inline]
+            pub unsafe fn Try{ident}({receiver}{params}) -> Option<{std}::num::NonZeroU32> {{
+                {std}::num::NonZeroU32::new(unsafe {{ {call}({args}) }})
+            }}",
+            cfg = super::gen_cfg_attrs(
+                super::gen_cmd_cfg(registry, options, &cmd.proto.ident)
+                    .as_ref()
+                    .map(|p| &p[..]),
+                false,
+            ),
+            ident = ident,
+            receiver = match (dispatch, cmd.params.is_empty()) {
+                (Dispatch::Free, _) => "",
+                (Dispatch::Method, true) => "&self",
+                (Dispatch::Method, false) => "&self, ",
+            },
+            params = super::gen_parameter_list(cmd, true, true),
+            std = std_path,
+            call = match dispatch {
+                Dispatch::Free => ident.to_string(),
+                Dispatch::Method => format!("self.{}", ident),
+            },
+            args = super::gen_parameter_list(cmd, true, false),
+        ));
+    }
+
+    if fns.is_empty() {
+        return Ok(());
+    }
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns.join("\n")),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns.join("\n"),
+        ),
+    }
+}
+
+/// Creates a function, or method for the struct generators, named after each alias of the
+/// commands that isn't a command of the registry itself, such as `Uniform1iARB` for `Uniform1i`,
+/// which forwards its arguments to the command.
+fn write_alias_shims<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let idents: BTreeSet<&str> = registry
+        .cmds
+        .iter()
+        .map(|cmd| &cmd.proto.ident[..])
+        .collect();
+    let mut shims = BTreeSet::new();
+
+    let mut fns = Vec::new();
+    for cmd in &registry.cmds {
+        let aliases = match registry.aliases.get(&cmd.proto.ident) {
+            Some(aliases) => aliases,
+            None => continue,
+        };
+        let name = super::gen_cmd_name(registry, options, &cmd.proto.ident);
+        for alias in aliases {
+            if idents.contains(&alias[..]) || !shims.insert(&alias[..]) {
+                continue;
+            }
+            fns.push(format!(
+                "{cfg}
+                /// Calls `{name}`, of which this is an alias in the registry.
+                #[allow(non_snake_case, dead_code, unused_unsafe, clippy::missing_safety_doc)]
+                #[allow(clippy::too_many_arguments, clippy::unused_unit)]
+                #[inline]
+                pub unsafe fn {alias}({receiver}{params}) -> {return_suffix} {{
+                    unsafe {{ {call}({args}) }}
+                }}",
+                cfg = super::gen_cfg_attrs(
+                    super::gen_cmd_cfg(registry, options, &cmd.proto.ident)
+                        .as_ref()
+                        .map(|p| &p[..]),
+                    false,
+                ),
+                name = name,
+                alias = super::gen_ident(alias),
+                receiver = match (dispatch, cmd.params.is_empty()) {
+                    (Dispatch::Free, _) => "",
+                    (Dispatch::Method, true) => "&self",
+                    (Dispatch::Method, false) => "&self, ",
+                },
+                params = super::gen_parameter_list(cmd, true, true),
+                return_suffix = cmd.proto.ty,
+                call = match dispatch {
+                    Dispatch::Free => name.to_string(),
+                    Dispatch::Method => format!("self.{}", name),
+                },
+                args = super::gen_parameter_list(cmd, true, false),
+            ));
+        }
+    }
+
+    if fns.is_empty() {
+        return Ok(());
+    }
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns.join("\n")),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns.join("\n"),
+        ),
+    }
+}
+
+/// Creates a `debug` module with the `Source`, `Type` and `Severity` of debug messages, and a
+/// `load_debug_callback` function installing a closure as the callback of `DebugMessageCallback`.
+fn write_debug_callback_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !has_cmd(registry, "DebugMessageCallback") {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        r#"
+        /// Receiving debug messages with a Rust closure.
+        pub mod debug {{
+            #![allow(dead_code)]
+            use super::types;"#
+    )?;
+
+    for &(name, doc, variants) in DEBUG_ENUMS {
+        let variants: Vec<_> = variants
+            .iter()
+            .filter(|&&(ident, _)| registry.enums.iter().any(|enm| enm.ident == ident))
+            .collect();
+
+        writeln!(
+            dest,
+            r#"
+            /// {doc}
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+            pub enum {name} {{"#,
+            doc = doc,
+            name = name,
+        )?;
+        for &&(_, variant) in &variants {
+            writeln!(dest, "{},", variant)?;
+        }
+        writeln!(
+            dest,
+            r#"/// A value that is not part of the bindings.
+                Unknown(types::GLenum),
+            }}
+
+            impl From<types::GLenum> for {name} {{
+                fn from(value: types::GLenum) -> {name} {{
+                    match value {{"#,
+            name = name,
+        )?;
+        for &&(ident, variant) in &variants {
+            writeln!(dest, "super::{} => {}::{},", ident, name, variant)?;
+        }
+        writeln!(
+            dest,
+            r#"value => {name}::Unknown(value),
+                    }}
+                }}
+            }}"#,
+            name = name,
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"
+            /// The `GLDEBUGPROC` that calls the closure `user_param` points to.
+            pub(super) extern "system" fn trampoline<F>(
+                source: types::GLenum,
+                gltype: types::GLenum,
+                _id: types::GLuint,
+                severity: types::GLenum,
+                length: types::GLsizei,
+                message: *const types::GLchar,
+                user_param: *mut {std}::os::raw::c_void,
+            ) where
+                F: Fn(Source, Type, Severity, &str) + Send + Sync + 'static,
+            {{
+                let callback = unsafe {{ &*(user_param as *const F) }};
+                let message = unsafe {{
+                    if length < 0 {{
+                        {std}::ffi::CStr::from_ptr(message).to_bytes()
+                    }} else {{
+                        {std}::slice::from_raw_parts(message as *const u8, length as usize)
+                    }}
+                }};
+                let message = {std}::string::String::from_utf8_lossy(message);
+                // A panic cannot unwind into the driver, so it stops at the callback.
+                let _ = {std}::panic::catch_unwind({std}::panic::AssertUnwindSafe(|| {{
+                    callback(source.into(), gltype.into(), severity.into(), &message)
+                }}));
+            }}
+        }}"#,
+        std = std_path,
+    )?;
+
+    let (receiver, call) = match dispatch {
+        Dispatch::Free => ("", "DebugMessageCallback"),
+        Dispatch::Method => ("&self, ", "self.DebugMessageCallback"),
+    };
+    let fns = format!(
+        "/// Installs `callback` as the callback of `DebugMessageCallback`, to be called with each
+        /// debug message.
+        ///
+        /// The closure is never dropped, as the implementation can call it until the context is
+        /// destroyed. A panic inside it is caught before it reaches the implementation.
+        #[allow(dead_code)]
+        pub fn load_debug_callback<F>({receiver}callback: F)
+        where
+            F: Fn(debug::Source, debug::Type, debug::Severity, &str) + Send + Sync + 'static,
+        {{
+            let user_param = {std}::boxed::Box::into_raw({std}::boxed::Box::new(callback));
+            unsafe {{ {call}(Some(debug::trampoline::<F>), user_param as *const _) }}
+        }}",
+        receiver = receiver,
+        call = call,
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns,
+        ),
+    }
+}
+
+/// Creates the `label_object` and `object_label` functions, which set and read the labels that
+/// `ObjectLabel` gives to objects as Rust strings, truncated to `MAX_LABEL_LENGTH`.
+fn write_object_label_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let has_enum = registry
+        .enums
+        .iter()
+        .any(|enm| enm.ident == "MAX_LABEL_LENGTH");
+    if !has_enum
+        || !has_cmd(registry, "ObjectLabel")
+        || !has_cmd(registry, "GetObjectLabel")
+        || !has_cmd(registry, "GetIntegerv")
+    {
+        return Ok(());
+    }
+
+    let (receiver, prefix) = match dispatch {
+        Dispatch::Free => ("", ""),
+        Dispatch::Method => ("&self, ", "self."),
+    };
+    let fns = format!(
+        "/// Labels the object `name` of the kind `identifier`, such as `BUFFER` or `TEXTURE`, with
+        /// `label`, which is cut at a character boundary to fit in `MAX_LABEL_LENGTH`.
+        #[allow(dead_code)]
+        pub fn label_object({receiver}identifier: types::GLenum, name: types::GLuint, label: &str) {{
+            let mut max = 0;
+            unsafe {{ {prefix}GetIntegerv(MAX_LABEL_LENGTH, &mut max) }};
+            // The length must be less than `MAX_LABEL_LENGTH`.
+            let mut length = label.len().min(max.max(1) as usize - 1);
+            while !label.is_char_boundary(length) {{
+                length -= 1;
+            }}
+            let length = <types::GLsizei as {std}::convert::TryFrom<usize>>::try_from(length)
+                .expect(\"MAX_LABEL_LENGTH is not a GLsizei\");
+            let label = label.as_ptr() as *const types::GLchar;
+            unsafe {{ {prefix}ObjectLabel(identifier, name, length, label) }}
+        }}
+
+        /// Returns the label of the object `name` of the kind `identifier`, which is empty if the
+        /// object has no label. Bytes that are not UTF-8 are replaced.
+        #[allow(dead_code)]
+        pub fn object_label({receiver}identifier: types::GLenum, name: types::GLuint) -> {std}::string::String {{
+            let mut max = 0;
+            unsafe {{ {prefix}GetIntegerv(MAX_LABEL_LENGTH, &mut max) }};
+            let mut label = {std}::vec![0u8; max.max(1) as usize];
+            let size = <types::GLsizei as {std}::convert::TryFrom<usize>>::try_from(label.len())
+                .expect(\"MAX_LABEL_LENGTH is not a GLsizei\");
+            let mut length = 0;
+            unsafe {{
+                {prefix}GetObjectLabel(
+                    identifier,
+                    name,
+                    size,
+                    &mut length,
+                    label.as_mut_ptr() as *mut types::GLchar,
+                )
+            }};
+            label.truncate((length.max(0) as usize).min(label.len()));
+            // Some implementations count the terminating NUL in `length`.
+            if let Some(nul) = label.iter().position(|&byte| byte == 0) {{
+                label.truncate(nul);
+            }}
+            {std}::string::String::from_utf8_lossy(&label).into_owned()
+        }}",
+        receiver = receiver,
+        prefix = prefix,
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            fns = fns,
+        ),
+    }
+}
+
+/// Creates the `DebugGroup` guard and the `debug_group` function, which pushes a debug group with
+/// `PushDebugGroup` and returns a guard popping it with `PopDebugGroup` when dropped.
+fn write_debug_group_helpers<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    std_path: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let has_enum = |ident: &str| registry.enums.iter().any(|enm| enm.ident == ident);
+    if !has_enum("DEBUG_SOURCE_APPLICATION")
+        || !has_enum("MAX_DEBUG_GROUP_STACK_DEPTH")
+        || !has_cmd(registry, "PushDebugGroup")
+        || !has_cmd(registry, "PopDebugGroup")
+        || !has_cmd(registry, "GetIntegerv")
+    {
+        return Ok(());
+    }
+
+    let api = super::gen_struct_name(registry.api);
+    let (lifetime, field, field_init, receiver, prefix, pop) = match dispatch {
+        Dispatch::Free => ("", String::new(), "", "", "", "PopDebugGroup"),
+        Dispatch::Method => (
+            "<'a>",
+            format!("gl: &'a {},", api),
+            "gl: self, ",
+            "&self, ",
+            "self.",
+            "self.gl.PopDebugGroup",
+        ),
+    };
+    writeln!(
+        dest,
+        r#"
+        {std}::thread_local! {{
+            /// The number of debug groups that `debug_group` pushed on this thread and that are
+            /// not popped yet.
+            static DEBUG_GROUP_DEPTH: {std}::cell::Cell<usize> =
+                const {{ {std}::cell::Cell::new(0) }};
+        }}
+
+        /// A debug group pushed by `debug_group`, which is popped when the guard is dropped.
+        #[must_use = "the debug group is popped as soon as the guard is dropped"]
+        pub struct DebugGroup{lifetime} {{
+            {field}
+            depth: usize,
+            // The group belongs to the context that is current on this thread.
+            _not_send: {std}::marker::PhantomData<*const ()>,
+        }}
+
+        impl{lifetime} Drop for DebugGroup{lifetime} {{
+            fn drop(&mut self) {{
+                let depth = DEBUG_GROUP_DEPTH.with(|depth| depth.replace(self.depth - 1));
+                unsafe {{ {pop}() }};
+                // Checking while unwinding would abort on the guards that are dropped next.
+                debug_assert!(
+                    depth == self.depth || {std}::thread::panicking(),
+                    "debug groups must be dropped in the reverse order of their creation"
+                );
+            }}
+        }}"#,
+        std = std_path,
+        lifetime = lifetime,
+        field = field,
+        pop = pop,
+    )?;
+
+    let fns = format!(
+        r#"/// Pushes a debug group named `message` from `DEBUG_SOURCE_APPLICATION`, which is popped
+        /// when the returned guard is dropped, so that debuggers show the commands in between
+        /// under it. `message` must be shorter than `MAX_DEBUG_MESSAGE_LENGTH`.
+        ///
+        /// In debug builds, panics if the groups would nest deeper than
+        /// `MAX_DEBUG_GROUP_STACK_DEPTH`, and the guards panic if they are dropped out of order.
+        #[allow(dead_code)]
+        pub fn debug_group({receiver}message: &str) -> DebugGroup{elided} {{
+            let depth = DEBUG_GROUP_DEPTH.with(|depth| depth.get()) + 1;
+            if cfg!(debug_assertions) {{
+                let mut max = 0;
+                unsafe {{ {prefix}GetIntegerv(MAX_DEBUG_GROUP_STACK_DEPTH, &mut max) }};
+                // The default group takes the first entry of the stack.
+                assert!(
+                    max <= 0 || depth < max as usize,
+                    "debug group `{{}}` nests deeper than MAX_DEBUG_GROUP_STACK_DEPTH ({{}})",
+                    message,
+                    max
+                );
+            }}
+            // A negative length would make the implementation read up to a NUL past the message.
+            let length = <types::GLsizei as {std}::convert::TryFrom<usize>>::try_from(message.len())
+                .expect("the debug group message is longer than GLsizei::MAX bytes");
+            unsafe {{
+                {prefix}PushDebugGroup(
+                    DEBUG_SOURCE_APPLICATION,
+                    0,
+                    length,
+                    message.as_ptr() as *const types::GLchar,
+                )
+            }};
+            DEBUG_GROUP_DEPTH.with(|cell| cell.set(depth));
+            DebugGroup {{
+                {field_init}depth,
+                _not_send: {std}::marker::PhantomData,
+            }}
+        }}"#,
+        receiver = receiver,
+        elided = if dispatch == Dispatch::Method {
+            "<'_>"
+        } else {
+            ""
+        },
+        prefix = prefix,
+        field_init = field_init,
+        std = std_path,
+    );
+
+    match dispatch {
+        Dispatch::Free => writeln!(dest, "{}", fns),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {fns}
+            }}",
+            api = api,
+            fns = fns,
+        ),
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `GlInterceptor` hooks emitted when `GeneratorOptions::interceptor` is set.
+
+use registry::{Cmd, Registry};
+use std::io;
+
+/// Writes the `GlInterceptor` trait.
+pub fn write_interceptor<W>(std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        /// Hooks called around every command of the bindings, for tools such as debuggers and
+        /// inspection layers. Installed with `set_interceptor`.
+        pub trait GlInterceptor: {std}::marker::Send + {std}::marker::Sync {{
+            /// Called before the command with the symbol `name` (e.g. `"glClear"`), with its
+            /// arguments.
+            fn before(&self, name: &'static str, args: &[&dyn {std}::fmt::Debug]);
+
+            /// Called after the command with the symbol `name` has returned.
+            fn after(&self, name: &'static str);
+        }}
+        "#,
+        std = std_path,
+    )
+}
+
+/// Writes the `interceptor` field, which is written inside the structure.
+pub fn write_field<W>(std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "interceptor: Option<{std}::sync::Arc<dyn GlInterceptor>>,",
+        std = std_path
+    )
+}
+
+/// Writes the `set_interceptor` method, which is written inside the `impl` of the structure.
+pub fn write_set_interceptor<W>(std_path: &str, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Installs `interceptor`, whose hooks are called around every command, or removes the
+        /// current one with `None`.
+        #[allow(dead_code)]
+        pub fn set_interceptor(&mut self, interceptor: Option<{std}::sync::Arc<dyn GlInterceptor>>) {{
+            self.interceptor = interceptor;
+        }}",
+        std = std_path
+    )
+}
+
+/// Generates the statement calling the `before` hook of the interceptor, if any, inside the
+/// method of `cmd`.
+pub fn gen_before(registry: &Registry, cmd: &Cmd) -> String {
+    let args: Vec<_> = cmd
+        .params
+        .iter()
+        .map(|param| {
+            if param.ty.contains("GLDEBUGPROC") {
+                "&\"<callback>\"".to_string()
+            } else {
+                format!("&{}", super::gen_ident(&param.ident))
+            }
+        })
+        .collect();
+    format!(
+        "if let Some(ref interceptor) = self.interceptor {{ interceptor.before(\"{}\", &[{}]); }}",
+        super::gen_symbol_name(registry.api, &cmd.proto.ident),
+        args.join(", ")
+    )
+}
+
+/// Generates the statement calling the `after` hook of the interceptor, if any, inside the
+/// method of `cmd`.
+pub fn gen_after(registry: &Registry, cmd: &Cmd) -> String {
+    format!(
+        "if let Some(ref interceptor) = self.interceptor {{ interceptor.after(\"{}\"); }}",
+        super::gen_symbol_name(registry.api, &cmd.proto.ident)
+    )
+}
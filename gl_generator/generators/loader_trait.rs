@@ -0,0 +1,97 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `GlLoader` trait emitted when `GeneratorOptions::loader_trait` is set.
+
+use super::helpers::Dispatch;
+use registry::Registry;
+use std::io;
+
+/// Writes the `GlLoader` trait, implemented for the load functions, and the `load_with_loader`
+/// function or method loading the bindings with a `GlLoader`.
+pub fn write_loader_trait<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// A loader of the commands, which can hold state such as the display or the library
+        /// that the commands are looked up in, and be kept to load other bindings. The load
+        /// functions taken by `load_with` implement it.
+        ///
+        /// ~~~ignore
+        /// struct EglLoader {{ display: egl::Display }}
+        ///
+        /// impl GlLoader for EglLoader {{
+        ///     fn load(&mut self, symbol: &'static str) -> *const raw::c_void {{
+        ///         self.display.get_proc_address(symbol)
+        ///     }}
+        /// }}
+        /// ~~~
+        pub trait GlLoader {{
+            /// Returns the address of the command named `symbol`, such as `\"glClear\"`, or
+            /// null if it isn't available.
+            fn load(&mut self, symbol: &'static str) -> *const __gl_imports::raw::c_void;
+        }}
+
+        impl<F> GlLoader for F where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            #[inline]
+            fn load(&mut self, symbol: &'static str) -> *const __gl_imports::raw::c_void {{
+                self(symbol)
+            }}
+        }}"
+    )?;
+
+    if options.caching_loader {
+        writeln!(
+            dest,
+            "impl<F> GlLoader for CachingLoader<F>
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+            {{
+                #[inline]
+                fn load(&mut self, symbol: &'static str) -> *const __gl_imports::raw::c_void {{
+                    CachingLoader::load(self, symbol)
+                }}
+            }}"
+        )?;
+    }
+
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "/// Load each OpenGL symbol using `loader`, which can be used again afterwards.
+            #[allow(dead_code)]
+            pub fn load_with_loader<L: GlLoader + ?Sized>(loader: &mut L) {{
+                load_with(|symbol| loader.load(symbol))
+            }}"
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                /// Load each OpenGL symbol using `loader`, which can be used again afterwards.
+                #[allow(dead_code)]
+                pub fn load_with_loader<L: GlLoader + ?Sized>(loader: &mut L) -> {api} {{
+                    {api}::load_with(|symbol| loader.load(symbol))
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+        ),
+    }
+}
@@ -13,23 +13,632 @@
 // limitations under the License.
 
 use registry::{Cmd, Enum, Registry};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::io;
 use Api;
 
 pub mod debug_struct_gen;
 pub mod global_gen;
+pub mod null_gen;
 pub mod static_gen;
 pub mod static_struct_gen;
 pub mod struct_gen;
+pub mod types_gen;
+
+mod caching_loader;
+mod capabilities;
+mod command_version;
+mod current;
+mod dispatch_bench;
+mod dispatch_table;
+mod egl;
+mod global_bridge;
+mod gpu_timing;
+mod handles;
+mod helpers;
+mod interceptor;
+mod loader_trait;
+mod pfn_table;
+mod platform;
+mod prelude;
+mod signature_test;
+mod wasm_stubs;
+mod wgl;
 
 /// Trait for a bindings generator.
 ///
+/// The trait is object safe, so a `&dyn Generator` can be chosen at run time and passed to
+/// `Registry::write_bindings`.
+///
 /// See https://github.com/brendanzab/gl-rs/tree/master/gl_generator#generator-types
 pub trait Generator {
+    /// The name of the generator, such as `"GlobalGenerator"`, so that tools can report which
+    /// generator produced a file.
+    ///
+    /// The default implementation returns the name of the implementing type, without its path.
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     /// Builds the GL bindings.
-    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()>;
+
+    /// Builds the GL bindings, taking the supplied `GeneratorOptions` into account.
+    ///
+    /// The default implementation ignores the options and calls `write`, so generators that
+    /// don't support any options only need to implement `write`.
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let _ = options;
+        self.write(registry, dest)
+    }
+}
+
+impl<G: Generator + ?Sized> Generator for &G {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        (**self).write(registry, dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        (**self).write_with_options(registry, options, dest)
+    }
+}
+
+/// The names of the generators that `generator_by_name` knows.
+const GENERATOR_NAMES: &[&str] = &[
+    "global",
+    "struct",
+    "static",
+    "static_struct",
+    "debug_struct",
+    "types",
+    "null",
+];
+
+/// The names that `generator_by_name` accepts, such as `"global"` or `"debug_struct"`.
+pub fn available_generators() -> &'static [&'static str] {
+    GENERATOR_NAMES
+}
+
+/// Returns the generator named `name`, one of `available_generators`, or `None` if there is no
+/// such generator. A `-` can be written instead of a `_`, as in `"debug-struct"`.
+///
+/// The generators take their options from the `GeneratorOptions` passed to
+/// `Registry::write_bindings_with_options`, which `GeneratorOptions::from_map` can build from
+/// strings as well.
+pub fn generator_by_name(name: &str) -> Option<Box<dyn Generator>> {
+    match &name.replace('-', "_")[..] {
+        "global" => Some(Box::new(global_gen::GlobalGenerator)),
+        "struct" => Some(Box::new(struct_gen::StructGenerator)),
+        "static" => Some(Box::new(static_gen::StaticGenerator)),
+        "static_struct" => Some(Box::new(static_struct_gen::StaticStructGenerator)),
+        "debug_struct" => Some(Box::new(debug_struct_gen::DebugStructGenerator)),
+        "types" => Some(Box::new(types_gen::TypesGenerator)),
+        "null" => Some(Box::new(null_gen::NullGenerator)),
+        _ => None,
+    }
+}
+
+/// The capacity of the buffer that the generators write through, so that the many small writes
+/// of the bindings reach the destination in large chunks even if it isn't buffered.
+const WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Writes the `count` sections of the bindings to `dest` in order, `write_section` writing the
+/// section of the given index. With `GeneratorOptions::parallel`, each section is rendered into
+/// its own buffer first, on the thread pool of `rayon` if the feature is enabled.
+fn write_sections<W, F>(
+    count: usize,
+    options: &GeneratorOptions,
+    dest: &mut W,
+    write_section: F,
+) -> io::Result<()>
+where
+    W: io::Write,
+    F: Fn(usize, &mut dyn io::Write) -> io::Result<()> + Sync,
+{
+    if !options.parallel {
+        for section in 0..count {
+            write_section(section, dest)?;
+        }
+        return Ok(());
+    }
+
+    let render = |section| {
+        let mut buffer = Vec::new();
+        write_section(section, &mut buffer)?;
+        Ok(buffer)
+    };
+    #[cfg(feature = "rayon")]
+    let sections: Vec<io::Result<Vec<u8>>> = {
+        use rayon::prelude::*;
+        (0..count).into_par_iter().map(render).collect()
+    };
+    #[cfg(not(feature = "rayon"))]
+    let sections: Vec<io::Result<Vec<u8>>> = (0..count).map(render).collect();
+
+    for section in sections {
+        dest.write_all(&section?)?;
+    }
+    Ok(())
+}
+
+/// Optional features of the generated bindings.
+///
+/// All options are disabled by default, which produces the same output as `Generator::write`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    /// Emit safe convenience wrappers around commonly used commands, such as `set_uniform`.
+    /// The debug groups of `debug_group` are counted in a `thread_local!` with a `const`
+    /// initializer.
+    pub helpers: bool,
+    /// The path that the bindings use instead of `std`, for example `::reexports` for a crate that
+    /// controls its own prelude. `std` is used when `None`.
+    ///
+    /// The path must provide `error`, `ffi`, `fmt`, `marker`, `mem`, `os::raw`, `ptr` and `vec`, and
+    /// depending on the generator and the other options `any`, `boxed`, `cell`, `collections`,
+    /// `convert`, `num`, `ops`, `panic`, `slice`, `string`, `sync`, `thread` and `time`, along
+    /// with the macros `format!`, `println!`, `thread_local!` and `vec!`.
+    pub std_path: Option<String>,
+    /// Emit a `load` function that opens the platform library itself, without a windowing
+    /// library. Not supported by the static generators, which link to the library instead.
+    pub platform_loader: bool,
+    /// Suffixes to try, after the aliases of the registry, when a command cannot be loaded. For
+    /// example with `"ARB"`, `glFoo` falls back to `glFooARB`. Commands that already end with a
+    /// vendor suffix, such as `glFooOES`, are not probed.
+    pub vendor_suffixes: Vec<String>,
+    /// Emit a `dispatch` function that calls commands by `CommandId` with type-erased `Value`
+    /// arguments, for scripting languages that have no glue code for each command.
+    pub dispatch_table: bool,
+    /// Emit the methods of the struct generators, and the functions of `GlobalGenerator`, in both
+    /// their plain and debug form, the debug one being compiled when the given Cargo feature, such
+    /// as `"gl_debug"`, is enabled.
+    pub debug_feature: Option<String>,
+    /// Emit a `CachingLoader` type that wraps a load function and remembers its results. Not
+    /// emitted by the static generators, which don't load anything.
+    pub caching_loader: bool,
+    /// Emit a `prelude` module re-exporting the given items, so that `use prelude::*` brings
+    /// them all into scope.
+    pub prelude: Option<Prelude>,
+    /// Store the command pointers of the struct generators in a `core` field of type `Core` and
+    /// an `ext` field of type `Extensions`, the methods staying on the main structure.
+    pub split_extensions: bool,
+    /// Leave out the commands and enums that only come from extensions. Applied by
+    /// `Registry::write_bindings_with_options`.
+    pub skip_extensions: bool,
+    /// Make the structure of the struct generators a wrapper around an `Arc` of a structure
+    /// holding the command pointers, such as `GlInner`, so that cloning it is cheap.
+    pub shared_struct: bool,
+    /// Emit a `GlInterceptor` trait whose hooks are called around every command of the struct
+    /// generators, once installed with `set_interceptor`. Ignored by the other generators.
+    pub interceptor: bool,
+    /// Make the structure of the struct generators neither `Send` nor `Sync`, so that the
+    /// compiler keeps it on the thread it was loaded on, for drivers whose contexts are bound to
+    /// a thread.
+    pub skip_send_impl: bool,
+    /// Emit an `unload` function for `GlobalGenerator`, and make the commands panic in debug
+    /// builds when they are called after `unload` and before the next `load_with`.
+    pub generation_guard: bool,
+    /// Emit a module named after each extension for `GlobalGenerator`, with an `is_loaded`
+    /// function like the version modules, documented with the vendor of the extension and its
+    /// comment in the registry.
+    pub registry_docs: bool,
+    /// Make the struct generators store each command as an `Option` of its function pointer type,
+    /// such as `Option<PFNGLCLEARPROC>`, converted once when it is loaded, instead of a `FnPtr`.
+    pub typed_fn_ptrs: bool,
+    /// A prefix for the symbols that the static generators link to, such as `"gles_"` to link
+    /// `glClear` to `gles_glClear`, so that libraries of several APIs exporting the same symbols
+    /// can be linked together once their symbols have been renamed. Ignored by the other
+    /// generators.
+    pub static_symbol_prefix: Option<String>,
+    /// Emit a `#[repr(C)]` structure of the function pointers of the struct generators, such as
+    /// `GlPfnTable`, with `pfn_table` and `from_pfn_table` methods converting between it and the
+    /// bindings. Ignored by the other generators.
+    pub pfn_table: bool,
+    /// Make `types::GLsync` an opaque `#[repr(transparent)]` structure wrapping the pointer, with
+    /// `null` and `is_null` methods, instead of an alias of a raw pointer that can be mixed up
+    /// with other pointers.
+    pub opaque_sync: bool,
+    /// Compile the items of `GlobalGenerator` that only come from extensions only when the Cargo
+    /// feature named after one of those extensions, such as `GL_ARB_debug_output`, is enabled,
+    /// and mark them with `doc(cfg(...))` on docs.rs. The crate must declare those features.
+    /// Not supported together with `helpers` or `dispatch_table`, and ignored by the other
+    /// generators.
+    pub extension_features: bool,
+    /// The path of the bindings of `GlobalGenerator` for the same registry, such as `"::gl"`,
+    /// for which the struct generators emit an `install_global` method copying their function
+    /// pointers into the global bindings and a `from_global` constructor. Ignored by the other
+    /// generators.
+    pub global_bridge: Option<String>,
+    /// Emit an `int` module with a `GLint` copy of each enum of the groups that `GLint`
+    /// parameters take, such as `int::RGBA` for the `internalformat` of `TexImage2D`, next to the
+    /// `GLenum` enums at the root of the bindings.
+    pub int_enums: bool,
+    /// Type aliases of the `types` module to define differently, as pairs of the name and the
+    /// type, such as `("EGLNativeWindowType", "*mut super::wl_egl_window")`. The type is
+    /// written as is in the `types` module. Naming an alias that doesn't exist is an error.
+    /// See also `GeneratorOptions::type_override`.
+    pub type_overrides: Vec<(String, String)>,
+    /// Emit a `make_current` method for the struct generators, which stores the bindings in a
+    /// thread-local, and `with_current` and `try_with_current` functions giving access to them.
+    /// Ignored by the other generators.
+    pub thread_local_current: bool,
+    /// The path of a module providing the Xlib types, such as `"::x11::xlib"` or
+    /// `"::x11_dl::xlib"`, that the `types` module of the GLX bindings re-exports instead of
+    /// defining its own `Display`, `XVisualInfo` and so on, so that they unify with the ones of the
+    /// windowing code. Ignored by the other APIs.
+    pub xlib_path: Option<String>,
+    /// Emit a `dispatch_bench` module, compiled only for tests, whose `run` function measures the
+    /// time of calling a representative set of commands loaded as no-ops, for comparing the
+    /// dispatch of `GlobalGenerator` and `StructGenerator`. Ignored by the other generators.
+    pub dispatch_bench: bool,
+    /// Make `GetError` return an `error::GlError`, which gains a `NoError` variant and an `Other`
+    /// variant for unknown error codes, so that its result can be matched directly. The command
+    /// returning a `GLenum` is renamed `GetErrorRaw`. Ignored by EGL, whose errors are not
+    /// `GLenum`s.
+    pub typed_get_error: bool,
+    /// Emit a `Try*` variant of each `Create*` command returning a `GLuint`, such as
+    /// `TryCreateProgram` or `TryCreateShader`, which returns an `Option<NonZeroU32>` that is
+    /// `None` when the command returns `0` because the object couldn't be created. The commands
+    /// themselves are unchanged.
+    pub nonzero_create: bool,
+    /// Emit a `Capabilities` structure with a field for each of the common limits of the
+    /// implementation whose enum is in the registry, such as `max_texture_size`, and a `query`
+    /// function filling them in with `GetIntegerv`. Ignored by the APIs without `GetIntegerv`.
+    pub capabilities: bool,
+    /// Emit an `#[inline]` function named after each alias of the commands that the registry
+    /// records, such as `Uniform1iARB` for `Uniform1i`, forwarding to the command, so that code
+    /// written against either name compiles. Aliases that are commands of the registry
+    /// themselves are left alone, and the registry has no aliases with `Fallbacks::None`.
+    pub alias_shims: bool,
+    /// Format the bindings canonically, without indentation or blank lines, so that they only
+    /// change when the items that the generators emit do, and not with the layout of their
+    /// templates. The items are always written in the same order, sorted by name within each
+    /// section. Applied by `Registry::write_bindings_with_options`, and used by
+    /// `Registry::verify_bindings`.
+    pub stable_output: bool,
+    /// Emit a `profile` module for `GlobalGenerator`, with an `active_profile` function returning
+    /// the profile of the current context, and make the commands that the core profile removes
+    /// panic when it is a core context. Meant for compatibility profile bindings used with both
+    /// kinds of contexts. Ignored by the other generators.
+    pub profile_checks: bool,
+    /// Render the sections of the bindings of `GlobalGenerator`, `StructGenerator` and
+    /// `DebugStructGenerator`, such as the enums or the commands, into separate buffers before
+    /// writing them out in order. The sections are rendered in parallel when the `rayon` feature
+    /// of the crate is enabled. The output is the same as without this option.
+    pub parallel: bool,
+    /// Store the function pointers of `GlobalGenerator` in a table behind a `std::sync::OnceLock`,
+    /// which the first call to `load_with` initializes, instead of a `static mut` per command.
+    /// The bindings can't be reloaded, and the commands can't be loaded one by one, so the
+    /// `load_with` function of each command, `load_subset_with` and `load_extension_with` are left
+    /// out. Requires Rust 1.70, not supported together with `generation_guard`, and ignored by
+    /// the other generators.
+    pub once_lock: bool,
+    /// Emit a `signature_test` module, compiled only for tests, with a test comparing the
+    /// parameter and return types of the function or method calling each command with a snapshot
+    /// of its signature embedded in the bindings, so that changes to how the generators emit the
+    /// commands are caught when the bindings are tested.
+    pub signature_test: bool,
+    /// The groups of commands whose methods of `DebugStructGenerator` measure their GPU time,
+    /// with timestamp queries written before and after the call, once
+    /// `gpu_timing::set_enabled(true)` has been called on the thread. `gpu_timings` returns the
+    /// times that are available. Requires `QueryCounter` and `GetQueryObjectui64v`, from GL 3.3
+    /// or `GL_ARB_timer_query`, and ignored by the other generators.
+    pub gpu_timing: Vec<CommandGroup>,
+    /// Makes the bindings compile for `wasm32` targets, where no GL library can be linked or
+    /// loaded. The static generators replace their `extern` block there with functions of the
+    /// same signature that panic, and the other generators panic with the same message when a
+    /// command is called, instead of saying that it was not loaded.
+    pub wasm_stubs: bool,
+    /// Emit `#[link]` attributes on the `extern` block of `StaticGenerator` and
+    /// `StaticStructGenerator`, gated on `target_os`, linking the library of the API on each
+    /// platform, such as `opengl32` on Windows, the `OpenGL` framework on macOS and `GL` on the
+    /// others, so that build scripts don't need `cargo:rustc-link-lib` lines. Ignored by the
+    /// other generators.
+    pub link_attrs: bool,
+    /// The libraries that `link_attrs` links on the platforms named by their `target_os`, instead
+    /// of the default ones. Set with `GeneratorOptions::link_override`.
+    pub link_overrides: Vec<(String, String)>,
+    /// Emit a `GlLoader` trait, with a `load` method returning the address of a command, which
+    /// the load functions implement, and a `load_with_loader` function or method loading the
+    /// bindings with one, so that loaders can hold state and be reused. Ignored by the static
+    /// generators.
+    pub loader_trait: bool,
+    /// Link only the commands introduced by the versions of the bindings with `StaticGenerator`,
+    /// and resolve the extension commands at run time with `load_extensions_with`, so that the
+    /// bindings link against libraries that don't export every extension command. The extension
+    /// commands panic until they have been loaded, and have an `is_loaded` function like with
+    /// `GlobalGenerator`. Ignored by the other generators.
+    pub dynamic_extensions: bool,
+    /// Remember the result of each symbol during a `load_with` pass of `GlobalGenerator` and the
+    /// struct generators, so that a symbol that is both the symbol of a command and a fallback of
+    /// another one is requested from the load function once per pass. Ignored by the static
+    /// generators.
+    pub dedup_loads: bool,
+    /// Mark the function or method calling each command with `#[doc(alias = "...")]` naming its
+    /// symbol, such as `glDrawArrays` for `DrawArrays`, so that searching the documentation for
+    /// the name of the C API finds the command.
+    pub doc_aliases: bool,
+    /// The edition of the crate that the bindings are compiled in. With `Edition::Edition2024`,
+    /// the `extern` blocks of the static generators are `unsafe extern` blocks. The bindings are
+    /// the same for the other editions.
+    pub edition: Edition,
+    /// Emit a `#[repr(transparent)]` newtype of `GLuint` in `types::handles` for each class of
+    /// objects that the `class` attributes of the registry name, such as `Buffer` or `Texture`,
+    /// and a `typed` module with a wrapper of each command taking or returning such objects, whose
+    /// signature uses the newtypes. The wrappers of the struct generators take the bindings as
+    /// their first parameter. The commands themselves are unchanged, and nothing is emitted for
+    /// registries without `class` attributes.
+    pub handle_types: bool,
+    /// Emit a `command_version` function returning the version of the API that introduced the
+    /// command with the given symbol, such as `Some((3, 0))` for `"glBindVertexArray"`, from the
+    /// features of the registry, or `None` for the commands that only come from extensions.
+    pub command_versions: bool,
+    /// Emit a `GENERATED_FOR` constant with the API, version and profile of the registry, and an
+    /// `assert_gl_at_least!(major, minor)` macro that fails to compile if the bindings were
+    /// generated for a lower version.
+    pub version_assertions: bool,
+    /// The path from the root of the crate to the public module that the bindings are included
+    /// in, such as `"gl"` for `pub mod gl { include!(...); }`, or an empty string for the root
+    /// itself.
+    ///
+    /// The `assert_gl_at_least!` macro of `version_assertions` and the `check_gl_error!` macro of
+    /// `helpers` are then `#[macro_export]`ed and refer to the bindings through `$crate`, so that
+    /// they work without imports and in the crates depending on the crate of the bindings.
+    /// Exported macros live at the root of the crate, so only one module of bindings per crate
+    /// can set it.
+    pub macro_export_path: Option<String>,
+}
+
+impl GeneratorOptions {
+    /// Defines the type alias `name` of the `types` module as `ty` instead, such as
+    /// `"EGLNativeWindowType"` as `"*mut super::wl_egl_window"`.
+    pub fn type_override(mut self, name: &str, ty: &str) -> GeneratorOptions {
+        self.type_overrides.push((name.to_string(), ty.to_string()));
+        self
+    }
+
+    /// Builds options from key/value pairs, as given on a command line or in environment
+    /// variables, applying each with `GeneratorOptions::set` in order.
+    ///
+    /// ~~~ignore
+    /// let options = GeneratorOptions::from_map(vec![("helpers", "true"), ("std_path", "::reexports")])?;
+    /// ~~~
+    pub fn from_map<I, K, V>(pairs: I) -> Result<GeneratorOptions, String>
     where
-        W: io::Write;
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut options = GeneratorOptions::default();
+        for (key, value) in pairs {
+            options.set(key.as_ref(), value.as_ref())?;
+        }
+        Ok(options)
+    }
+
+    /// Sets the option named `key`, the name of its field such as `"helpers"`, from a string.
+    ///
+    /// The flags take `true` or `false`, and the `Option<String>` options their value. The
+    /// options holding lists add to them: `vendor_suffixes` and `gpu_timing` take names separated
+    /// by commas, such as `"ARB,EXT"` or `"draw,upload"`, and `type_override` and `link_override`
+    /// take one `NAME=VALUE` pair, such as `"windows=libGLESv2"`. `prelude` is `types` or
+    /// `types_and_commands`. Unknown keys and invalid values are errors.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        fn flag(key: &str, value: &str) -> Result<bool, String> {
+            match value {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(format!(
+                    "invalid value `{}` for `{}`, expected `true` or `false`",
+                    value, key
+                )),
+            }
+        }
+
+        fn pair(key: &str, value: &str) -> Result<(String, String), String> {
+            let mut parts = value.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if !name.is_empty() => {
+                    Ok((name.to_string(), value.to_string()))
+                },
+                _ => Err(format!(
+                    "invalid value `{}` for `{}`, expected NAME=VALUE",
+                    value, key
+                )),
+            }
+        }
+
+        fn list(value: &str) -> impl Iterator<Item = &str> {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+        }
+
+        match key {
+            "helpers" => self.helpers = flag(key, value)?,
+            "std_path" => self.std_path = Some(value.to_string()),
+            "platform_loader" => self.platform_loader = flag(key, value)?,
+            "vendor_suffixes" => self.vendor_suffixes.extend(list(value).map(str::to_string)),
+            "dispatch_table" => self.dispatch_table = flag(key, value)?,
+            "debug_feature" => self.debug_feature = Some(value.to_string()),
+            "caching_loader" => self.caching_loader = flag(key, value)?,
+            "prelude" => {
+                self.prelude = Some(match value {
+                    "types" => Prelude::Types,
+                    "types_and_commands" => Prelude::TypesAndCommands,
+                    _ => {
+                        return Err(format!(
+                            "invalid value `{}` for `prelude`, expected `types` or \
+                             `types_and_commands`",
+                            value
+                        ))
+                    },
+                })
+            },
+            "split_extensions" => self.split_extensions = flag(key, value)?,
+            "skip_extensions" => self.skip_extensions = flag(key, value)?,
+            "shared_struct" => self.shared_struct = flag(key, value)?,
+            "interceptor" => self.interceptor = flag(key, value)?,
+            "skip_send_impl" => self.skip_send_impl = flag(key, value)?,
+            "generation_guard" => self.generation_guard = flag(key, value)?,
+            "registry_docs" => self.registry_docs = flag(key, value)?,
+            "typed_fn_ptrs" => self.typed_fn_ptrs = flag(key, value)?,
+            "static_symbol_prefix" => self.static_symbol_prefix = Some(value.to_string()),
+            "pfn_table" => self.pfn_table = flag(key, value)?,
+            "opaque_sync" => self.opaque_sync = flag(key, value)?,
+            "extension_features" => self.extension_features = flag(key, value)?,
+            "global_bridge" => self.global_bridge = Some(value.to_string()),
+            "int_enums" => self.int_enums = flag(key, value)?,
+            "type_override" => self.type_overrides.push(pair(key, value)?),
+            "thread_local_current" => self.thread_local_current = flag(key, value)?,
+            "xlib_path" => self.xlib_path = Some(value.to_string()),
+            "dispatch_bench" => self.dispatch_bench = flag(key, value)?,
+            "typed_get_error" => self.typed_get_error = flag(key, value)?,
+            "nonzero_create" => self.nonzero_create = flag(key, value)?,
+            "capabilities" => self.capabilities = flag(key, value)?,
+            "alias_shims" => self.alias_shims = flag(key, value)?,
+            "stable_output" => self.stable_output = flag(key, value)?,
+            "profile_checks" => self.profile_checks = flag(key, value)?,
+            "parallel" => self.parallel = flag(key, value)?,
+            "once_lock" => self.once_lock = flag(key, value)?,
+            "signature_test" => self.signature_test = flag(key, value)?,
+            "gpu_timing" => {
+                for group in list(value) {
+                    self.gpu_timing.push(match group {
+                        "draw" => CommandGroup::Draw,
+                        "upload" => CommandGroup::Upload,
+                        "compute" => CommandGroup::Compute,
+                        _ => return Err(format!("unknown command group `{}`", group)),
+                    });
+                }
+            },
+            "wasm_stubs" => self.wasm_stubs = flag(key, value)?,
+            "link_attrs" => self.link_attrs = flag(key, value)?,
+            "link_override" => self.link_overrides.push(pair(key, value)?),
+            "loader_trait" => self.loader_trait = flag(key, value)?,
+            "dynamic_extensions" => self.dynamic_extensions = flag(key, value)?,
+            "dedup_loads" => self.dedup_loads = flag(key, value)?,
+            "doc_aliases" => self.doc_aliases = flag(key, value)?,
+            "edition" => {
+                self.edition = match value {
+                    "2015" => Edition::Edition2015,
+                    "2018" => Edition::Edition2018,
+                    "2021" => Edition::Edition2021,
+                    "2024" => Edition::Edition2024,
+                    _ => {
+                        return Err(format!(
+                            "invalid value `{}` for `edition`, expected `2015`, `2018`, `2021` \
+                             or `2024`",
+                            value
+                        ))
+                    },
+                }
+            },
+            "handle_types" => self.handle_types = flag(key, value)?,
+            "command_versions" => self.command_versions = flag(key, value)?,
+            "version_assertions" => self.version_assertions = flag(key, value)?,
+            "macro_export_path" => self.macro_export_path = Some(value.to_string()),
+            _ => return Err(format!("unknown generator option `{}`", key)),
+        }
+        Ok(())
+    }
+
+    /// Adds the suffixes that drivers most often export core commands under, `EXT`, `ARB`, `OES`
+    /// and `KHR` in that order, to `vendor_suffixes`, for drivers that expose a command only
+    /// under a suffix that the registry doesn't record as an alias. This can hide that a driver
+    /// lacks a command, so it is not the default.
+    pub fn common_vendor_suffixes(mut self) -> GeneratorOptions {
+        for suffix in COMMON_VENDOR_SUFFIXES {
+            if !self.vendor_suffixes.iter().any(|s| s == suffix) {
+                self.vendor_suffixes.push(suffix.to_string());
+            }
+        }
+        self
+    }
+
+    /// Links `library` on the targets whose `target_os` is `target_os` with `link_attrs`, such as
+    /// `"libGLESv2"` on `"windows"` for ANGLE. A library ending with `.framework`, such as
+    /// `"OpenGLES.framework"`, is linked as a framework.
+    pub fn link_override(mut self, target_os: &str, library: &str) -> GeneratorOptions {
+        self.link_overrides
+            .push((target_os.to_string(), library.to_string()));
+        self
+    }
+}
+
+/// The suffixes that `GeneratorOptions::common_vendor_suffixes` adds.
+const COMMON_VENDOR_SUFFIXES: &[&str] = &["EXT", "ARB", "OES", "KHR"];
+
+/// The items that the `prelude` module re-exports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Prelude {
+    /// The contents of the `types` module.
+    Types,
+    /// The contents of the `types` module and the commands, which are the struct named after the
+    /// API for the struct generators.
+    TypesAndCommands,
+}
+
+/// The editions of Rust that `GeneratorOptions::edition` can target.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edition {
+    /// Rust 2015, the edition of the crates that don't set one.
+    #[default]
+    Edition2015,
+    /// Rust 2018.
+    Edition2018,
+    /// Rust 2021.
+    Edition2021,
+    /// Rust 2024, which requires Rust 1.85.
+    Edition2024,
+}
+
+/// The groups of commands that `GeneratorOptions::gpu_timing` can measure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CommandGroup {
+    /// The draw calls, such as `DrawArrays` or `MultiDrawElementsIndirect`.
+    Draw,
+    /// The commands uploading buffer or texture data, such as `BufferSubData` or `TexImage2D`.
+    Upload,
+    /// The compute dispatches, `DispatchCompute` and `DispatchComputeIndirect`.
+    Compute,
+}
+
+/// The path of the standard library in the generated bindings.
+fn std_path(options: &GeneratorOptions) -> &str {
+    options.std_path.as_ref().map_or("std", |path| path)
+}
+
+/// The `#[macro_export]` attribute and the path of the bindings from inside their macros, which is
+/// based on `$crate` if `GeneratorOptions::macro_export_path` is set, and relative to the module
+/// calling the macros otherwise.
+fn macro_export(options: &GeneratorOptions) -> (&'static str, String) {
+    match options.macro_export_path {
+        Some(ref path) if path.is_empty() => ("#[macro_export]", "$crate::".to_string()),
+        Some(ref path) => (
+            "#[macro_export]",
+            format!("$crate::{}::", path.trim_start_matches("::")),
+        ),
+        None => ("#[allow(unused_macros)]", String::new()),
+    }
 }
 
 pub fn gen_struct_name(api: Api) -> &'static str {
@@ -45,14 +654,24 @@ pub fn gen_struct_name(api: Api) -> &'static str {
     }
 }
 
+/// Generates the name of the structure that holds the `FnPtr` of the struct generators, which is
+/// the structure named after the API unless `GeneratorOptions::shared_struct` is set.
+pub fn gen_ptrs_struct_name(api: Api, options: &GeneratorOptions) -> String {
+    if options.shared_struct {
+        format!("{}Inner", gen_struct_name(api))
+    } else {
+        gen_struct_name(api).to_string()
+    }
+}
+
 /// This function generates a `const name: type = value;` item.
 pub fn gen_enum_item<W>(enm: &Enum, types_prefix: &str, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     writeln!(dest,
-        "#[allow(dead_code, non_upper_case_globals)] pub const {ident}: {types_prefix}{ty} = {value}{cast_suffix};",
-        ident = enm.ident,
+        "#[allow(dead_code, non_upper_case_globals, clippy::redundant_static_lifetimes)] pub const {ident}: {types_prefix}{ty} = {value}{cast_suffix};",
+        ident = gen_ident(&enm.ident),
         types_prefix = if enm.ty == "&'static str" { "" } else { types_prefix },
         ty = enm.ty,
         value = enm.value,
@@ -63,42 +682,334 @@ where
     )
 }
 
+/// Creates an `int` module with a `GLint` copy of each `GLenum` enum of the groups that the
+/// `GLint` parameters of the commands take, so that they can be passed without a cast.
+pub fn gen_int_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let groups: BTreeSet<&str> = registry
+        .cmds
+        .iter()
+        .flat_map(|cmd| cmd.params.iter())
+        .filter(|param| param.ty == "types::GLint")
+        .filter_map(|param| param.group.as_ref().map(|group| &group[..]))
+        .collect();
+    let idents: BTreeSet<&str> = groups
+        .iter()
+        .filter_map(|group| registry.groups.get(*group))
+        .flat_map(|group| group.enums.iter().map(|enm| &enm[..]))
+        .collect();
+
+    writeln!(
+        dest,
+        "/// The enums that `GLint` parameters take, such as `int::RGBA` for the internal format
+        /// of `TexImage2D`, as `GLint` constants.
+        #[allow(dead_code, non_upper_case_globals)]
+        pub mod int {{"
+    )?;
+
+    for enm in &registry.enums {
+        if enm.ty == "GLenum" && idents.contains(&enm.ident[..]) {
+            writeln!(
+                dest,
+                "pub const {ident}: super::types::GLint = super::{ident} as super::types::GLint;",
+                ident = gen_ident(&enm.ident),
+            )?;
+        }
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `Api` and `Profile` enums, the `GENERATED_FOR` constant describing the registry
+/// with them, and the `assert_gl_at_least!` macro comparing a version with the version of the
+/// registry at compile time, which is exported if `GeneratorOptions::macro_export_path` is set.
+pub fn gen_generated_for<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let (major, minor) = registry.version;
+    let (export, path) = macro_export(options);
+    // Without a path to the bindings, the version is written into the macro instead of read from
+    // `GENERATED_FOR`, which may not be in scope where it is called.
+    let (major_expr, minor_expr) = if options.macro_export_path.is_some() {
+        (
+            format!("{}GENERATED_FOR.1", path),
+            format!("{}GENERATED_FOR.2", path),
+        )
+    } else {
+        (major.to_string(), minor.to_string())
+    };
+    writeln!(
+        dest,
+        r#"
+        /// The APIs that bindings can be generated for.
+        #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Api {{
+            Gl,
+            Glx,
+            Wgl,
+            Egl,
+            GlCore,
+            Gles1,
+            Gles2,
+            Glsc2,
+        }}
+
+        /// The profiles that bindings can be generated for.
+        #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Profile {{
+            Core,
+            Compatibility,
+        }}
+
+        /// The API, major version, minor version and profile that the bindings were generated for.
+        #[allow(dead_code)]
+        pub const GENERATED_FOR: (Api, u8, u8, Profile) = (Api::{api:?}, {major}, {minor}, Profile::{profile:?});
+
+        /// Fails to compile if the bindings were generated for a version lower than
+        /// `major.minor`, such as `assert_gl_at_least!(4, 3)` for code that needs {api} 4.3.
+        {export}
+        macro_rules! assert_gl_at_least {{
+            ($major:expr, $minor:expr) => {{
+                const _: () = assert!(
+                    {major_expr} > $major || ({major_expr} == $major && {minor_expr} >= $minor),
+                    concat!(
+                        "the bindings were generated for {api} {major}.{minor}, which is lower than ",
+                        stringify!($major),
+                        ".",
+                        stringify!($minor)
+                    )
+                );
+            }};
+        }}"#,
+        api = registry.api,
+        major = major,
+        minor = minor,
+        profile = registry.profile,
+        export = export,
+        major_expr = major_expr,
+        minor_expr = minor_expr,
+    )
+}
+
+/// This function generates a `type PFN...PROC = extern "system" fn(...) -> ...;` item, the
+/// function pointer type of a command. The parameters keep their names from the registry, which
+/// IDEs show when the pointer is called.
+pub fn gen_pfn_item<W>(api: Api, cmd: &Cmd, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "#[allow(dead_code, non_camel_case_types, non_snake_case, clippy::upper_case_acronyms, clippy::unused_unit)] pub type {name} = extern \"system\" fn({params}) -> {return_suffix};",
+        name = gen_pfn_name(api, &cmd.proto.ident),
+        params = gen_parameter_list(cmd, true, true),
+        return_suffix = cmd.proto.ty,
+    )
+}
+
 /// Generates all the type aliases for a namespace.
 ///
 /// Aliases are either `pub type = ...` or `#[repr(C)] pub struct ... { ... }` and contain all the
 /// things that we can't obtain from the XML files.
-pub fn gen_types<W>(api: Api, dest: &mut W) -> io::Result<()>
+pub fn gen_types<W>(api: Api, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
-    if let Api::Egl = api {
-        writeln!(dest, "{}", include_str!("templates/types/egl.rs"))?;
-        return Ok(());
+    let mut types = if let Api::Egl = api {
+        include_str!("templates/types/egl.rs").to_string()
+    } else {
+        let mut types = include_str!("templates/types/gl.rs").to_string();
+        if options.opaque_sync {
+            types = types.replace(
+                "pub enum __GLsync {}\npub type GLsync = *const __GLsync;\n",
+                &include_str!("templates/types/opaque_sync.rs")
+                    .replace("{std}", std_path(options)),
+            );
+        }
+        match api {
+            Api::Glx => {
+                types.push_str(&gen_xlib_types(options)?);
+                types.push_str(include_str!("templates/types/glx.rs"));
+            },
+            Api::Wgl => types.push_str(include_str!("templates/types/wgl.rs")),
+            _ => {},
+        }
+        types
+    };
+
+    for (name, ty) in &options.type_overrides {
+        types = override_type(&types, name, ty)?;
     }
+    types.push_str(&gen_size_asserts(&types));
 
-    writeln!(dest, "{}", include_str!("templates/types/gl.rs"))?;
+    writeln!(dest, "{}", types)
+}
 
-    match api {
-        Api::Glx => writeln!(dest, "{}", include_str!("templates/types/glx.rs"))?,
-        Api::Wgl => writeln!(dest, "{}", include_str!("templates/types/wgl.rs"))?,
-        _ => {},
+/// The aliases whose size is fixed by `khrplatform.h`, or that are opaque handles with the size
+/// of a pointer, with a type of the same size.
+const KHRONOS_SIZES: &[(&str, &str)] = &[
+    ("GLfixed", "i32"),
+    ("GLclampx", "i32"),
+    ("GLhalf", "u16"),
+    ("GLhalfARB", "u16"),
+    ("GLhalfNV", "u16"),
+    ("GLint64", "i64"),
+    ("GLuint64", "u64"),
+    ("GLint64EXT", "i64"),
+    ("GLuint64EXT", "u64"),
+    ("GLintptr", "usize"),
+    ("GLsizeiptr", "usize"),
+    ("GLintptrARB", "usize"),
+    ("GLsizeiptrARB", "usize"),
+    ("GLvdpauSurfaceNV", "usize"),
+    ("khronos_utime_nanoseconds_t", "u64"),
+    ("khronos_uint64_t", "u64"),
+    ("khronos_ssize_t", "usize"),
+    ("EGLAttrib", "usize"),
+    ("EGLAttribKHR", "usize"),
+    ("GLsync", "usize"),
+    ("GLeglImageOES", "usize"),
+];
+
+/// Generates a compile-time assertion of the size of each alias of `types` listed in
+/// `KHRONOS_SIZES`, so that a wrong definition fails to compile on the affected targets.
+fn gen_size_asserts(types: &str) -> String {
+    let mut asserts = String::from("\n// Sizes required by khrplatform.h and the opaque handles\n");
+    for &(name, ty) in KHRONOS_SIZES {
+        if types.contains(&format!("pub type {} =", name)) {
+            asserts.push_str(&format!(
+                "const _: () = assert!(super::__gl_imports::mem::size_of::<{}>() == \
+                 super::__gl_imports::mem::size_of::<{}>());\n",
+                name, ty
+            ));
+        }
     }
+    asserts
+}
 
-    Ok(())
+/// The names of the Xlib types used by the GLX bindings.
+const XLIB_TYPES: &[&str] = &[
+    "Bool",
+    "Display",
+    "Font",
+    "Pixmap",
+    "Visual",
+    "VisualID",
+    "Window",
+    "XID",
+    "XVisualInfo",
+];
+
+/// Generates the Xlib types of the GLX bindings, which are re-exported from
+/// `GeneratorOptions::xlib_path` if it is set and stand-ins otherwise.
+fn gen_xlib_types(options: &GeneratorOptions) -> io::Result<String> {
+    let path = match options.xlib_path {
+        Some(ref path) => path,
+        None => return Ok(include_str!("templates/types/xlib.rs").to_string()),
+    };
+
+    if !is_valid_path(path) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{}` is not a valid path for the Xlib types", path),
+        ));
+    }
+
+    Ok(format!(
+        "#[allow(unused_imports)] pub use {}::{{{}}};\n",
+        path,
+        XLIB_TYPES.join(", ")
+    ))
+}
+
+/// Checks that `path` is a Rust path to a module, such as `::x11::xlib` or `super::xlib`.
+fn is_valid_path(path: &str) -> bool {
+    let path = path.strip_prefix("::").unwrap_or(path);
+    let segments: Vec<_> = path.split("::").collect();
+    segments.iter().enumerate().all(|(i, &segment)| {
+        let leading = segments[..i].iter().all(|&s| s == "super" || s == "self");
+        is_valid_ident(segment)
+            || (leading && segment == "super")
+            || (i == 0 && (segment == "crate" || segment == "self"))
+    })
+}
+
+/// Replaces the definition of each `pub type {name} = ...;` alias of `types` with `ty`, failing
+/// if there is no such alias.
+fn override_type(types: &str, name: &str, ty: &str) -> io::Result<String> {
+    let prefix = format!("pub type {} = ", name);
+    let mut found = false;
+    let lines: Vec<_> = types
+        .lines()
+        .map(|line| {
+            if line.starts_with(&prefix) {
+                found = true;
+                format!("{}{};", prefix, ty)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if found {
+        Ok(lines.join("\n"))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{}` is not a type alias of the `types` module", name),
+        ))
+    }
+}
+
+/// Returns whether `GetError` returns an `error::GlError`, which is the case when
+/// `GeneratorOptions::typed_get_error` is set for an API with a `GetError` returning a `GLenum`.
+pub fn has_typed_get_error(registry: &Registry, options: &GeneratorOptions) -> bool {
+    options.typed_get_error
+        && registry.api != Api::Egl
+        && registry
+            .cmds
+            .iter()
+            .any(|cmd| cmd.proto.ident == "GetError")
+}
+
+/// Generates the name of the function or method calling the command `cmd`, which is its
+/// identifier except for `GetError`, renamed `GetErrorRaw` if `has_typed_get_error`.
+pub fn gen_cmd_name<'a>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    cmd: &'a str,
+) -> Cow<'a, str> {
+    if cmd == "GetError" && has_typed_get_error(registry, options) {
+        Cow::Borrowed("GetErrorRaw")
+    } else {
+        gen_ident(cmd)
+    }
 }
 
 /// Generates the list of Rust `Arg`s that a `Cmd` requires.
+///
+/// The generators use `gen_parameter_list` instead, which doesn't allocate.
+#[cfg_attr(not(feature = "unstable_generator_utils"), allow(dead_code))]
 pub fn gen_parameters(cmd: &Cmd, with_idents: bool, with_types: bool) -> Vec<String> {
     cmd.params
         .iter()
         .map(|binding| {
             // returning
             if with_idents && with_types {
-                format!("{}: {}", binding.ident, binding.ty)
+                format!("{}: {}", gen_ident(&binding.ident), binding.ty)
             } else if with_types {
                 format!("{}", binding.ty)
             } else if with_idents {
-                format!("{}", binding.ident)
+                format!("{}", gen_ident(&binding.ident))
             } else {
                 panic!()
             }
@@ -106,6 +1017,105 @@ pub fn gen_parameters(cmd: &Cmd, with_idents: bool, with_types: bool) -> Vec<Str
         .collect()
 }
 
+/// Generates the parameters of a `Cmd` like `gen_parameters`, separated by commas. The list is
+/// formatted directly into the destination, without allocating a string for each parameter.
+///
+/// Example results: `"x: types::GLint, y: types::GLint"`, `"x, y"`, etc.
+pub fn gen_parameter_list(cmd: &Cmd, with_idents: bool, with_types: bool) -> ParameterList<'_> {
+    assert!(with_idents || with_types);
+    ParameterList {
+        cmd,
+        with_idents,
+        with_types,
+    }
+}
+
+/// The parameters of a `Cmd` separated by commas, as generated by `gen_parameter_list`.
+pub struct ParameterList<'a> {
+    cmd: &'a Cmd,
+    with_idents: bool,
+    with_types: bool,
+}
+
+impl<'a> fmt::Display for ParameterList<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, binding) in self.cmd.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            if self.with_idents {
+                f.write_str(&gen_ident(&binding.ident))?;
+            }
+            if self.with_idents && self.with_types {
+                f.write_str(": ")?;
+            }
+            if self.with_types {
+                f.write_str(&binding.ty)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The keywords of Rust, which can't be used as identifiers in the generated bindings.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Generates the Rust identifier of a command, enum or parameter, which is its name with an
+/// underscore appended if it is a keyword.
+///
+/// Example results: `"Clear"`, `"type_"`, `"Self_"`, etc.
+pub fn gen_ident<'a>(ident: &'a str) -> Cow<'a, str> {
+    if KEYWORDS.contains(&ident) {
+        Cow::Owned(format!("{}_", ident))
+    } else {
+        Cow::Borrowed(ident)
+    }
+}
+
+/// Returns `true` if `ident` can be used as an identifier in the generated bindings, in every
+/// edition.
+fn is_valid_ident(ident: &str) -> bool {
+    let mut chars = ident.chars();
+    let starts_ok = match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() || c == '_',
+        None => false,
+    };
+    starts_ok
+        && ident != "_"
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&ident)
+}
+
+/// Checks that `gen_ident` generates a valid identifier for every command, parameter and enum
+/// of the registry, so that broken bindings are reported when they are generated rather than
+/// when they are compiled.
+pub fn check_idents(registry: &Registry) -> io::Result<()> {
+    let bindings = registry
+        .cmds
+        .iter()
+        .flat_map(|cmd| Some(&cmd.proto).into_iter().chain(cmd.params.iter()));
+    let idents = bindings
+        .map(|binding| &binding.ident[..])
+        .chain(registry.enums.iter().map(|enm| &enm.ident[..]));
+
+    for ident in idents {
+        if !is_valid_ident(&gen_ident(ident)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`{}` can't be made a valid Rust identifier", ident),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Generates the native symbol name of a `Cmd`.
 ///
 /// Example results: `"glClear"`, `"wglCreateContext"`, etc.
@@ -117,3 +1127,1473 @@ pub fn gen_symbol_name(api: Api, cmd: &str) -> String {
         Api::Egl => format!("egl{}", cmd),
     }
 }
+
+/// The opening of the `unsafe` block around the body of a generated `unsafe fn`, so that the
+/// bindings compile cleanly under the `unsafe_op_in_unsafe_fn` lint, which is denied by some
+/// crates and warns by default since Rust 2024. The block is emitted for every edition.
+pub const UNSAFE_OPEN: &str = "unsafe { ";
+
+/// The closing of the block opened by `UNSAFE_OPEN`.
+pub const UNSAFE_CLOSE: &str = " }";
+
+/// Generates the `unsafe` keyword that `extern` blocks need with `Edition::Edition2024`, or an
+/// empty string for the other editions.
+pub fn gen_extern_unsafety(options: &GeneratorOptions) -> &'static str {
+    if options.edition >= Edition::Edition2024 {
+        "unsafe "
+    } else {
+        ""
+    }
+}
+
+/// Generates the `#[doc(alias = "...")]` attribute naming the native symbol of a `Cmd` if
+/// `GeneratorOptions::doc_aliases` is set, and an empty string otherwise.
+pub fn gen_doc_alias(registry: &Registry, options: &GeneratorOptions, cmd: &str) -> String {
+    if options.doc_aliases {
+        format!(
+            "#[doc(alias = \"{}\")]\n",
+            gen_symbol_name(registry.api, cmd)
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Generates the symbols to try when the symbol of a `Cmd` cannot be loaded.
+///
+/// These are the aliases of the registry followed by the symbol with each of the vendor suffixes
+/// of the options, unless the command already ends with a vendor suffix, such as
+/// `DrawArraysOES`.
+pub fn gen_fallbacks(registry: &Registry, options: &GeneratorOptions, cmd: &str) -> Vec<String> {
+    let mut fallbacks: Vec<_> = registry
+        .aliases
+        .get(cmd)
+        .into_iter()
+        .flat_map(|aliases| aliases.iter())
+        .map(|alias| gen_symbol_name(registry.api, alias))
+        .collect();
+
+    if has_vendor_suffix(registry, options, cmd) {
+        return fallbacks;
+    }
+
+    for suffix in &options.vendor_suffixes {
+        let symbol = gen_symbol_name(registry.api, &format!("{}{}", cmd, suffix));
+        if !fallbacks.contains(&symbol) {
+            fallbacks.push(symbol);
+        }
+    }
+
+    fallbacks
+}
+
+/// Whether `cmd` ends with the tag of a vendor: one of the vendor suffixes of the options or of
+/// `COMMON_VENDOR_SUFFIXES`, or the vendor of an extension of the registry, such as `NV` for
+/// `GL_NV_fence`.
+fn has_vendor_suffix(registry: &Registry, options: &GeneratorOptions, cmd: &str) -> bool {
+    let vendors = registry
+        .extensions
+        .iter()
+        .filter_map(|extension| extension.name.split('_').nth(1));
+    options
+        .vendor_suffixes
+        .iter()
+        .map(|suffix| &suffix[..])
+        .chain(COMMON_VENDOR_SUFFIXES.iter().cloned())
+        .chain(vendors)
+        .any(|suffix| !suffix.is_empty() && cmd.len() > suffix.len() && cmd.ends_with(suffix))
+}
+
+/// Generates a map from the symbol of each `Cmd`, and of each of its aliases, to the command.
+///
+/// Aliases that are commands of the registry themselves map to their own command.
+pub fn gen_symbol_map(registry: &Registry) -> BTreeMap<String, &str> {
+    let mut symbols = BTreeMap::new();
+    for cmd in &registry.cmds {
+        symbols.insert(
+            gen_symbol_name(registry.api, &cmd.proto.ident),
+            &cmd.proto.ident[..],
+        );
+    }
+    for cmd in &registry.cmds {
+        for alias in registry
+            .aliases
+            .get(&cmd.proto.ident)
+            .into_iter()
+            .flat_map(|aliases| aliases.iter())
+        {
+            symbols
+                .entry(gen_symbol_name(registry.api, alias))
+                .or_insert(&cmd.proto.ident[..]);
+        }
+    }
+    symbols
+}
+
+/// Generates the names of the commands introduced by the versions of the registry, as opposed to
+/// the commands that only come from extensions.
+pub fn gen_core_cmds(registry: &Registry) -> BTreeSet<&str> {
+    registry
+        .versions
+        .iter()
+        .flat_map(|version| version.cmds.iter())
+        .map(|cmd| &cmd[..])
+        .collect()
+}
+
+/// Returns the commands of the registry that the versions remove from the core profile, which
+/// are only available on compatibility contexts.
+pub fn gen_compatibility_cmds(registry: &Registry) -> BTreeSet<&str> {
+    registry
+        .versions
+        .iter()
+        .flat_map(|version| version.removed_cmds.iter())
+        .map(|cmd| &cmd[..])
+        .collect()
+}
+
+/// Generates the `cfg` predicate that a command is compiled with when
+/// `GeneratorOptions::extension_features` is set, such as `feature = "GL_ARB_debug_output"`,
+/// or `None` if it is always compiled.
+pub fn gen_cmd_cfg(registry: &Registry, options: &GeneratorOptions, cmd: &str) -> Option<String> {
+    if !options.extension_features || registry.versions.iter().any(|v| v.cmds.contains(cmd)) {
+        return None;
+    }
+    let features = registry
+        .extensions
+        .iter()
+        .filter(|extension| extension.cmds.contains(cmd))
+        .map(|extension| format!("feature = \"{}\"", extension.name))
+        .collect::<Vec<_>>();
+    match features.len() {
+        0 => None,
+        1 => features.into_iter().next(),
+        _ => Some(format!("any({})", features.join(", "))),
+    }
+}
+
+/// Generates the `#[cfg(...)]` attribute of a `cfg` predicate, followed by the
+/// `#[cfg_attr(docsrs, doc(cfg(...)))]` attribute showing it on docs.rs if `docs` is set.
+pub fn gen_cfg_attrs(predicate: Option<&str>, docs: bool) -> String {
+    match predicate {
+        Some(predicate) if docs => format!(
+            "#[cfg({p})] #[cfg_attr(docsrs, doc(cfg({p})))]",
+            p = predicate
+        ),
+        Some(predicate) => format!("#[cfg({})]", predicate),
+        None => String::new(),
+    }
+}
+
+/// Returns the libraries that `GeneratorOptions::link_attrs` links for an API by default, by
+/// `target_os`, the library without one being linked on the other targets.
+fn default_link_libraries(api: Api) -> &'static [(Option<&'static str>, &'static str)] {
+    match api {
+        Api::Gl | Api::GlCore => &[
+            (Some("windows"), "opengl32"),
+            (Some("macos"), "OpenGL.framework"),
+            (None, "GL"),
+        ],
+        Api::Glx => &[(None, "GL")],
+        Api::Wgl => &[(Some("windows"), "opengl32")],
+        Api::Egl => &[(Some("macos"), ""), (Some("ios"), ""), (None, "EGL")],
+        Api::Gles1 => &[(Some("ios"), "OpenGLES.framework"), (None, "GLESv1_CM")],
+        Api::Gles2 => &[(Some("ios"), "OpenGLES.framework"), (None, "GLESv2")],
+        Api::Glsc2 => &[],
+    }
+}
+
+/// Generates the `#[cfg_attr(..., link(...))]` attributes of the `extern` block of the static
+/// generators when `GeneratorOptions::link_attrs` is set, or an empty string otherwise.
+///
+/// The libraries of `GeneratorOptions::link_overrides` replace the default ones of their
+/// `target_os`. An empty library links nothing on its targets.
+pub fn gen_link_attrs(registry: &Registry, options: &GeneratorOptions) -> io::Result<String> {
+    if !options.link_attrs {
+        return Ok(String::new());
+    }
+
+    let mut libraries: Vec<(Option<&str>, &str)> = default_link_libraries(registry.api).to_vec();
+    for (target_os, library) in &options.link_overrides {
+        match libraries
+            .iter_mut()
+            .find(|&&mut (os, _)| os == Some(&target_os[..]))
+        {
+            Some(entry) => entry.1 = library,
+            None => libraries.insert(0, (Some(target_os), library)),
+        }
+    }
+    if libraries.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "`link_attrs` has no default library for {}, which must be set with `link_override`",
+                registry.api
+            ),
+        ));
+    }
+
+    let named: Vec<_> = libraries
+        .iter()
+        .filter_map(|&(os, _)| os)
+        .map(|os| format!("target_os = \"{}\"", os))
+        .collect();
+    let mut attrs = String::new();
+    for &(os, library) in &libraries {
+        if library.is_empty() {
+            continue;
+        }
+        let link = match library.strip_suffix(".framework") {
+            Some(framework) => format!("link(name = \"{}\", kind = \"framework\")", framework),
+            None => format!("link(name = \"{}\")", library),
+        };
+        let attr = match os {
+            Some(os) => format!("#[cfg_attr(target_os = \"{}\", {})]", os, link),
+            None if named.is_empty() => format!("#[{}]", link),
+            None => format!("#[cfg_attr(not(any({})), {})]", named.join(", "), link),
+        };
+        attrs.push_str(&attr);
+        attrs.push('\n');
+    }
+    Ok(attrs)
+}
+
+/// The fields of the struct that store the `FnPtr` of the commands
+/// when `GeneratorOptions::split_extensions` is set, with the name and doc of their type.
+pub const SPLIT_FIELDS: &[(&str, &str, &str)] = &[
+    (
+        "core",
+        "Core",
+        "The commands introduced by the versions of the bindings.",
+    ),
+    (
+        "ext",
+        "Extensions",
+        "The commands that only come from extensions.",
+    ),
+];
+
+/// Generates the field of the struct that stores the `FnPtr` of a
+/// `Cmd`, or `None` if the `FnPtr` is stored in the structure itself.
+pub fn gen_split_field(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    cmd: &str,
+) -> Option<&'static str> {
+    if !options.split_extensions {
+        None
+    } else if registry
+        .versions
+        .iter()
+        .any(|version| version.cmds.contains(cmd))
+    {
+        Some("core")
+    } else {
+        Some("ext")
+    }
+}
+
+/// Generates the path of the `FnPtr` of a `Cmd` from the structure of the struct generators.
+///
+/// Example results: `"Clear"`, `"core.Clear"`, `"ext.DebugMessageCallbackARB"`, etc.
+pub fn gen_field_path(registry: &Registry, options: &GeneratorOptions, cmd: &str) -> String {
+    match gen_split_field(registry, options, cmd) {
+        Some(field) => format!("{}.{}", field, gen_ident(cmd)),
+        None => gen_ident(cmd).into_owned(),
+    }
+}
+
+/// Generates the expression that calls the command `cmd` of the structure of the struct
+/// generators with the arguments `args`.
+pub fn gen_field_call(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    cmd: &str,
+    args: &str,
+) -> String {
+    let field = gen_field_path(registry, options, cmd);
+    if options.typed_fn_ptrs {
+        // The binding is named so that it can't shadow a parameter such as `f`.
+        format!(
+            "match self.{} {{ Some(__gl_fn) => __gl_fn({}), None => missing_fn_panic() }}",
+            field, args
+        )
+    } else {
+        format!(
+            "__gl_imports::mem::transmute::<*const __gl_imports::raw::c_void, {}>(self.{}.f)({})",
+            gen_pfn_name(registry.api, cmd),
+            field,
+            args
+        )
+    }
+}
+
+/// Generates the expression that checks whether the field at `place` of the structure of the
+/// struct generators has been loaded.
+///
+/// Example results: `"self.Clear.is_loaded()"`, `"self.Clear.is_some()"`, etc.
+pub fn gen_field_is_loaded(options: &GeneratorOptions, place: &str) -> String {
+    if options.typed_fn_ptrs {
+        format!("{}.is_some()", place)
+    } else {
+        format!("{}.is_loaded()", place)
+    }
+}
+
+/// Generates the expression that returns the pointer that the field at `place` of the structure
+/// of the struct generators has been loaded from, as an `Option<*const c_void>`, `c_void` being
+/// reached through the path `raw`.
+pub fn gen_field_ptr(options: &GeneratorOptions, place: &str, raw: &str) -> String {
+    if options.typed_fn_ptrs {
+        format!("{}.map(|f| f as *const {}::c_void)", place, raw)
+    } else {
+        format!(
+            "if {p}.is_loaded() {{ Some({p}.f) }} else {{ None }}",
+            p = place
+        )
+    }
+}
+
+/// Generates the name of the function that turns a loaded pointer into the value of a field of
+/// the structure of the struct generators.
+pub fn gen_field_ctor(options: &GeneratorOptions) -> &'static str {
+    if options.typed_fn_ptrs {
+        "typed_fn_ptr"
+    } else {
+        "FnPtr::new"
+    }
+}
+
+/// Generates the statements that shadow the load function `loadfn` of a `load_with` pass with a
+/// closure that remembers the result of each symbol, or an empty string if
+/// `GeneratorOptions::dedup_loads` isn't set.
+pub fn gen_load_cache(options: &GeneratorOptions) -> String {
+    if !options.dedup_loads {
+        return String::new();
+    }
+    format!(
+        "let mut load_cache = {std}::collections::HashMap::new();
+        let mut loadfn = |symbol: &'static __gl_imports::CStr| -> *const __gl_imports::raw::c_void {{
+            *load_cache.entry(symbol).or_insert_with(|| loadfn(symbol))
+        }};",
+        std = std_path(options),
+    )
+}
+
+/// Generates the `TryLoadError` type returned by `try_load_with`.
+pub fn gen_try_load_error(api: Api, options: &GeneratorOptions) -> String {
+    format!(
+        r#"
+        /// The error returned by `try_load_with`.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum TryLoadError<E> {{
+            /// The load function failed, and loading stopped at the symbol it failed for.
+            Loader(E),
+            /// The symbols of the commands introduced by the versions of the bindings that were
+            /// not loaded. Extension commands are optional.
+            Missing({std}::vec::Vec<&'static str>),
+        }}
+
+        impl<E: {std}::fmt::Display> {std}::fmt::Display for TryLoadError<E> {{
+            fn fmt(&self, f: &mut {std}::fmt::Formatter) -> {std}::fmt::Result {{
+                match *self {{
+                    TryLoadError::Loader(ref error) => write!(f, "could not load the {api} commands: {{}}", error),
+                    TryLoadError::Missing(ref missing) => write!(f, "missing {api} commands: {{}}", missing.join(", ")),
+                }}
+            }}
+        }}
+
+        impl<E: {std}::error::Error> {std}::error::Error for TryLoadError<E> {{}}
+        "#,
+        std = std_path(options),
+        api = api,
+    )
+}
+
+/// Generates the name of the function pointer type of a `Cmd`, following the C convention.
+///
+/// Example results: `"PFNGLCLEARPROC"`, `"PFNWGLCREATECONTEXTPROC"`, etc.
+pub fn gen_pfn_name(api: Api, cmd: &str) -> String {
+    format!("PFN{}PROC", gen_symbol_name(api, cmd).to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::io;
+
+    use super::Generator;
+    use registry::{
+        Api, Binding, Cmd, Enum, Extension, Fallbacks, Group, Profile, Registry, Version,
+    };
+
+    fn binding(ident: &str, ty: &'static str) -> Binding {
+        Binding {
+            ident: ident.to_string(),
+            ty: Cow::Borrowed(ty),
+            c_ty: String::new(),
+            group: None,
+            class: None,
+        }
+    }
+
+    fn keyword_registry() -> Registry {
+        let mut enums = BTreeSet::new();
+        enums.insert(Enum {
+            ident: "type".to_string(),
+            value: "0x1".to_string(),
+            cast: false,
+            alias: None,
+            ty: Cow::Borrowed("GLenum"),
+        });
+
+        let mut cmds = BTreeSet::new();
+        cmds.insert(Cmd {
+            proto: binding("Self", "()"),
+            params: vec![
+                binding("type", "types::GLenum"),
+                binding("match", "types::GLint"),
+            ],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+
+        Registry {
+            api: Api::Gl,
+            version: (1, 0),
+            profile: Profile::Core,
+            extensions: Vec::new(),
+            versions: Vec::new(),
+            enums,
+            cmds,
+            aliases: BTreeMap::new(),
+            groups: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_gen_ident() {
+        assert_eq!(super::gen_ident("Clear"), "Clear");
+        assert_eq!(super::gen_ident("type"), "type_");
+        assert_eq!(super::gen_ident("Self"), "Self_");
+        assert_eq!(super::gen_ident("type_"), "type_");
+    }
+
+    #[test]
+    fn test_gen_parameter_list() {
+        let cmd = Cmd {
+            proto: binding("DrawArrays", "()"),
+            params: vec![
+                binding("mode", "types::GLenum"),
+                binding("type", "types::GLint"),
+            ],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        };
+        for &(with_idents, with_types) in &[(true, true), (true, false), (false, true)] {
+            assert_eq!(
+                super::gen_parameter_list(&cmd, with_idents, with_types).to_string(),
+                super::gen_parameters(&cmd, with_idents, with_types).join(", "),
+            );
+        }
+        assert_eq!(
+            super::gen_parameter_list(&cmd, true, true).to_string(),
+            "mode: types::GLenum, type_: types::GLint"
+        );
+    }
+
+    #[test]
+    fn test_keywords_are_escaped() {
+        let registry = keyword_registry();
+
+        let mut dest = Vec::new();
+        super::struct_gen::StructGenerator
+            .write(&registry, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub const type_: types::GLenum = 0x1;"));
+        assert!(dest.contains(
+            "pub unsafe fn Self_(&self, type_: types::GLenum, match_: types::GLint) -> ()"
+        ));
+        assert!(dest.contains("(self.Self_.f)(type_, match_)"));
+        assert!(dest.contains("b\"glSelf\\0\""));
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write(&registry, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(
+            dest.contains("pub unsafe fn Self_(type_: types::GLenum, match_: types::GLint) -> ()")
+        );
+        assert!(dest.contains("pub mod Self_ {"));
+        assert!(dest.contains("b\"glSelf\\0\""));
+    }
+
+    #[test]
+    fn test_prelude_types_only() {
+        let options = super::GeneratorOptions {
+            prelude: Some(super::Prelude::Types),
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub mod prelude {"));
+        assert!(dest.contains("pub use super::types::*;"));
+        assert!(!dest.contains("pub use super::Self_;"));
+    }
+
+    #[test]
+    fn test_registry_docs() {
+        let mut registry = keyword_registry();
+        registry.extensions.push(Extension {
+            name: "GL_EXT_foo".to_string(),
+            comment: Some("Replaced by ARB_foo".to_string()),
+            cmds: vec!["Self".to_string()].into_iter().collect(),
+            enums: BTreeSet::new(),
+        });
+        let options = super::GeneratorOptions {
+            registry_docs: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(
+            "//! The `GL_EXT_foo` extension, from the EXT vendor.\n//!\n//! Replaced by ARB_foo\n"
+        ));
+        assert!(dest.contains("super::Self_::is_loaded()"));
+    }
+
+    #[test]
+    fn test_types_gen() {
+        let mut dest = Vec::new();
+        super::types_gen::TypesGenerator
+            .write(&keyword_registry(), &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub mod types {"));
+        assert!(dest.contains("pub type GLuint = std::os::raw::c_uint;"));
+        assert!(dest.contains("pub const type_: types::GLenum = 0x1;"));
+        assert!(!dest.contains("__gl_imports"));
+        assert!(!dest.contains("Self_"));
+    }
+
+    #[test]
+    fn test_static_symbol_prefix() {
+        let options = super::GeneratorOptions {
+            static_symbol_prefix: Some("gles_".to_string()),
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::static_gen::StaticGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("#[link_name=\"gles_glSelf\"]"));
+
+        let mut dest = Vec::new();
+        super::static_struct_gen::StaticStructGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("#[link_name=\"gles_glSelf\"]"));
+    }
+
+    #[test]
+    fn test_doc_aliases() {
+        let options = super::GeneratorOptions {
+            doc_aliases: true,
+            ..Default::default()
+        };
+
+        for name in super::available_generators() {
+            if *name == "types" || *name == "null" {
+                continue;
+            }
+            let generator = super::generator_by_name(name).unwrap();
+
+            let mut dest = Vec::new();
+            generator
+                .write_with_options(&keyword_registry(), &options, &mut dest)
+                .unwrap();
+            let dest = String::from_utf8(dest).unwrap();
+            assert!(dest.contains("#[doc(alias = \"glSelf\")]"), "{}", name);
+
+            let mut dest = Vec::new();
+            generator.write(&keyword_registry(), &mut dest).unwrap();
+            let dest = String::from_utf8(dest).unwrap();
+            assert!(!dest.contains("doc(alias"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_generator_by_name() {
+        let names: BTreeSet<_> = super::available_generators()
+            .iter()
+            .map(|name| super::generator_by_name(name).unwrap().name())
+            .collect();
+        assert_eq!(names.len(), super::available_generators().len());
+        assert_eq!(
+            super::generator_by_name("debug-struct").unwrap().name(),
+            "DebugStructGenerator"
+        );
+        assert!(super::generator_by_name("Global").is_none());
+
+        let generator = super::generator_by_name("struct").unwrap();
+        let mut dest = Vec::new();
+        keyword_registry()
+            .write_bindings(&*generator, &mut dest)
+            .unwrap();
+        assert!(String::from_utf8(dest).unwrap().contains("pub struct Gl"));
+    }
+
+    #[test]
+    fn test_null_generator() {
+        let options = super::GeneratorOptions {
+            helpers: true,
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        keyword_registry()
+            .write_bindings_with_options(super::null_gen::NullGenerator, &options, &mut dest)
+            .unwrap();
+        assert!(dest.is_empty());
+    }
+
+    #[test]
+    fn test_handle_types() {
+        let mut registry = keyword_registry();
+        let mut buffer = binding("buffer", "types::GLuint");
+        buffer.class = Some("buffer".to_string());
+        let mut arrays = binding("arrays", "*mut types::GLuint");
+        arrays.class = Some("vertex array".to_string());
+        let mut program = binding("CreateProgram", "types::GLuint");
+        program.class = Some("program".to_string());
+        registry.cmds.insert(Cmd {
+            proto: binding("BindBuffer", "()"),
+            params: vec![binding("target", "types::GLenum"), buffer],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        registry.cmds.insert(Cmd {
+            proto: binding("GenVertexArrays", "()"),
+            params: vec![binding("n", "types::GLsizei"), arrays],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        registry.cmds.insert(Cmd {
+            proto: program,
+            params: Vec::new(),
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+
+        let options = super::GeneratorOptions {
+            handle_types: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings_with_options(super::global_gen::GlobalGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub struct Buffer(pub GLuint);"));
+        assert!(dest.contains("pub struct VertexArray(pub GLuint);"));
+        assert!(dest.contains(
+            "pub unsafe fn BindBuffer(target: types::GLenum, buffer: types::handles::Buffer) -> ()"
+        ));
+        assert!(dest.contains("super::BindBuffer(target, buffer.0)"));
+        assert!(dest.contains("super::GenVertexArrays(n, arrays as *mut types::GLuint)"));
+        assert!(dest.contains("types::handles::Program(super::CreateProgram())"));
+        let typed = &dest[dest.find("pub mod typed").unwrap()..];
+        assert!(!typed.contains("Self_"));
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings_with_options(super::struct_gen::StructGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(
+            dest.contains("pub unsafe fn CreateProgram(gl: &super::Gl) -> types::handles::Program")
+        );
+        assert!(dest.contains("gl.BindBuffer(target, buffer.0)"));
+
+        let mut dest = Vec::new();
+        keyword_registry()
+            .write_bindings_with_options(super::global_gen::GlobalGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(!dest.contains("mod handles"));
+        assert!(!dest.contains("mod typed"));
+    }
+
+    #[test]
+    fn test_command_versions() {
+        let mut registry = keyword_registry();
+        for name in &["Clear", "DrawArrays", "BindVertexArray"] {
+            registry.cmds.insert(Cmd {
+                proto: binding(name, "()"),
+                params: Vec::new(),
+                alias: None,
+                vecequiv: None,
+                glx: None,
+            });
+        }
+        let version = |name: &str, number, cmds: &[&str]| Version {
+            name: name.to_string(),
+            number,
+            cmds: cmds.iter().map(|cmd| cmd.to_string()).collect(),
+            enums: BTreeSet::new(),
+            removed_cmds: BTreeSet::new(),
+        };
+        // The versions of merged registries may be out of order and repeat commands.
+        registry.versions = vec![
+            version("GL_VERSION_3_0", (3, 0), &["BindVertexArray", "DrawArrays"]),
+            version("GL_VERSION_1_0", (1, 0), &["Clear"]),
+            version("GL_VERSION_1_1", (1, 1), &["DrawArrays"]),
+        ];
+
+        let options = super::GeneratorOptions {
+            command_versions: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings_with_options(super::global_gen::GlobalGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub fn command_version(name: &str) -> Option<(u8, u8)>"));
+        assert!(dest.contains("\"glClear\" => Some((1, 0)),"));
+        assert!(dest.contains("\"glDrawArrays\" => Some((1, 1)),"));
+        assert!(dest.contains("\"glBindVertexArray\" => Some((3, 0)),"));
+        assert!(dest.contains("_ => None,"));
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings(super::global_gen::GlobalGenerator, &mut dest)
+            .unwrap();
+        assert!(!String::from_utf8(dest)
+            .unwrap()
+            .contains("fn command_version"));
+    }
+
+    #[test]
+    fn test_version_assertions() {
+        let mut registry = keyword_registry();
+        registry.version = (3, 2);
+        registry.profile = Profile::Compatibility;
+        let options = super::GeneratorOptions {
+            version_assertions: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings_with_options(super::static_gen::StaticGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(
+            "pub const GENERATED_FOR: (Api, u8, u8, Profile) = (Api::Gl, 3, 2, Profile::Compatibility);"
+        ));
+        assert!(dest.contains("3 > $major || (3 == $major && 2 >= $minor)"));
+        assert!(dest.contains("\"the bindings were generated for gl 3.2, which is lower than \""));
+        assert!(!dest.contains("#[macro_export]"));
+
+        let options = super::GeneratorOptions {
+            version_assertions: true,
+            macro_export_path: Some("bindings::gl".to_string()),
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        registry
+            .write_bindings_with_options(super::static_gen::StaticGenerator, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("#[macro_export]"));
+        assert!(dest.contains(
+            "$crate::bindings::gl::GENERATED_FOR.1 > $major || \
+             ($crate::bindings::gl::GENERATED_FOR.1 == $major && \
+             $crate::bindings::gl::GENERATED_FOR.2 >= $minor)"
+        ));
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings(super::static_gen::StaticGenerator, &mut dest)
+            .unwrap();
+        assert!(!String::from_utf8(dest).unwrap().contains("GENERATED_FOR"));
+    }
+
+    #[test]
+    fn test_common_vendor_suffixes() {
+        let options = super::GeneratorOptions {
+            vendor_suffixes: vec!["KHR".to_string(), "NV".to_string()],
+            ..Default::default()
+        }
+        .common_vendor_suffixes();
+        assert_eq!(options.vendor_suffixes, ["KHR", "NV", "EXT", "ARB", "OES"]);
+
+        let registry = keyword_registry();
+        let options = super::GeneratorOptions::default().common_vendor_suffixes();
+        assert_eq!(
+            super::gen_fallbacks(&registry, &options, "Self"),
+            ["glSelfEXT", "glSelfARB", "glSelfOES", "glSelfKHR"]
+        );
+        // Commands that already have a vendor suffix are not probed with another one.
+        assert!(super::gen_fallbacks(&registry, &options, "DrawArraysOES").is_empty());
+        // `NV` is only known as a vendor from the extensions of the registry.
+        assert_eq!(
+            super::gen_fallbacks(&registry, &options, "SetFenceNV"),
+            [
+                "glSetFenceNVEXT",
+                "glSetFenceNVARB",
+                "glSetFenceNVOES",
+                "glSetFenceNVKHR"
+            ]
+        );
+
+        let mut registry = keyword_registry();
+        registry.extensions.push(Extension {
+            name: "GL_NV_fence".to_string(),
+            comment: None,
+            cmds: BTreeSet::new(),
+            enums: BTreeSet::new(),
+        });
+        assert!(super::gen_fallbacks(&registry, &options, "SetFenceNV").is_empty());
+    }
+
+    #[test]
+    fn test_options_from_map() {
+        use super::{CommandGroup, Edition, GeneratorOptions, Prelude};
+
+        let mut map = BTreeMap::new();
+        map.insert("helpers", "true");
+        map.insert("std_path", "::reexports");
+        map.insert("vendor_suffixes", "ARB, EXT");
+        map.insert("prelude", "types_and_commands");
+        map.insert("gpu_timing", "draw,compute");
+        map.insert("link_override", "windows=libGLESv2");
+        map.insert("edition", "2021");
+        let options = GeneratorOptions::from_map(map).unwrap();
+        assert_eq!(
+            options,
+            GeneratorOptions {
+                helpers: true,
+                std_path: Some("::reexports".to_string()),
+                vendor_suffixes: vec!["ARB".to_string(), "EXT".to_string()],
+                prelude: Some(Prelude::TypesAndCommands),
+                gpu_timing: vec![CommandGroup::Draw, CommandGroup::Compute],
+                edition: Edition::Edition2021,
+                ..GeneratorOptions::default().link_override("windows", "libGLESv2")
+            }
+        );
+
+        let options = GeneratorOptions::from_map(vec![
+            (
+                "type_override",
+                "EGLNativeWindowType=*mut super::wl_egl_window",
+            ),
+            ("dedup_loads", "true"),
+            ("dedup_loads", "false"),
+        ])
+        .unwrap();
+        assert_eq!(
+            options,
+            GeneratorOptions::default()
+                .type_override("EGLNativeWindowType", "*mut super::wl_egl_window")
+        );
+
+        assert!(GeneratorOptions::from_map(vec![("helper", "true")]).is_err());
+        assert!(GeneratorOptions::from_map(vec![("helpers", "yes")]).is_err());
+        assert!(GeneratorOptions::from_map(vec![("prelude", "all")]).is_err());
+        assert!(GeneratorOptions::from_map(vec![("gpu_timing", "draw,copy")]).is_err());
+        assert!(GeneratorOptions::from_map(vec![("link_override", "libGL")]).is_err());
+        assert!(GeneratorOptions::from_map(vec![("edition", "2027")]).is_err());
+    }
+
+    #[test]
+    fn test_unsafe_blocks() {
+        let mut dest = Vec::new();
+        super::struct_gen::StructGenerator
+            .write(&keyword_registry(), &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("{ unsafe { __gl_imports::mem::transmute::<"));
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write(&keyword_registry(), &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("{ unsafe { __gl_imports::mem::transmute::<"));
+    }
+
+    #[test]
+    fn test_edition_2024() {
+        let options = super::GeneratorOptions {
+            edition: super::Edition::Edition2024,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::static_gen::StaticGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("unsafe extern \"system\" {"));
+
+        let options = super::GeneratorOptions {
+            edition: super::Edition::Edition2021,
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        super::static_gen::StaticGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(!dest.contains("unsafe extern"));
+    }
+
+    #[test]
+    fn test_generator_objects() {
+        let generators: [&dyn Generator; 6] = [
+            &super::global_gen::GlobalGenerator,
+            &super::struct_gen::StructGenerator,
+            &super::debug_struct_gen::DebugStructGenerator,
+            &super::static_gen::StaticGenerator,
+            &super::static_struct_gen::StaticStructGenerator,
+            &super::types_gen::TypesGenerator,
+        ];
+        let names: BTreeSet<_> = generators
+            .iter()
+            .map(|generator| generator.name())
+            .collect();
+        assert_eq!(names.len(), generators.len());
+        assert!(names.contains("GlobalGenerator"));
+
+        for &generator in &generators {
+            let mut dest = Vec::new();
+            generator.write(&keyword_registry(), &mut dest).unwrap();
+            let mut through_registry = Vec::new();
+            keyword_registry()
+                .write_bindings(generator, &mut through_registry)
+                .unwrap();
+            assert!(!dest.is_empty(), "{} wrote nothing", generator.name());
+            assert_eq!(dest, through_registry);
+        }
+    }
+
+    #[test]
+    fn test_link_attrs() {
+        let options = super::GeneratorOptions {
+            link_attrs: true,
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        super::static_gen::StaticGenerator
+            .write_with_options(&keyword_registry(), &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("#[cfg_attr(target_os = \"windows\", link(name = \"opengl32\"))]"));
+        assert!(dest.contains(
+            "#[cfg_attr(target_os = \"macos\", link(name = \"OpenGL\", kind = \"framework\"))]"
+        ));
+        assert!(dest.contains(
+            "#[cfg_attr(not(any(target_os = \"windows\", target_os = \"macos\")), link(name = \"GL\"))]"
+        ));
+
+        let options = options
+            .link_override("windows", "libGL")
+            .link_override("linux", "GL.1");
+        let attrs = super::gen_link_attrs(&keyword_registry(), &options).unwrap();
+        assert!(attrs.contains("#[cfg_attr(target_os = \"windows\", link(name = \"libGL\"))]"));
+        assert!(attrs.contains("#[cfg_attr(target_os = \"linux\", link(name = \"GL.1\"))]"));
+        assert!(attrs.contains("#[cfg_attr(not(any(target_os = \"linux\", target_os = \"windows\", target_os = \"macos\")), link(name = \"GL\"))]"));
+
+        let mut registry = keyword_registry();
+        registry.api = Api::Glx;
+        let attrs = super::gen_link_attrs(&registry, &options).unwrap();
+        assert!(attrs.starts_with("#[cfg_attr(target_os = \"linux\", link(name = \"GL.1\"))]"));
+
+        registry.api = Api::Glsc2;
+        let error = super::gen_link_attrs(
+            &registry,
+            &super::GeneratorOptions {
+                link_attrs: true,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_extension_features() {
+        let mut registry = keyword_registry();
+        registry.extensions.push(Extension {
+            name: "GL_ARB_self".to_string(),
+            comment: None,
+            cmds: vec!["Self".to_string()].into_iter().collect(),
+            enums: BTreeSet::new(),
+        });
+        let options = super::GeneratorOptions {
+            extension_features: true,
+            registry_docs: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(
+            "#[cfg(feature = \"GL_ARB_self\")] \
+             #[cfg_attr(docsrs, doc(cfg(feature = \"GL_ARB_self\")))]"
+        ));
+        assert!(dest.contains("#[cfg(feature = \"GL_ARB_self\")] Self_::load_with_cstr"));
+
+        let options = super::GeneratorOptions {
+            helpers: true,
+            ..options
+        };
+        let error = super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_once_lock() {
+        let registry = keyword_registry();
+        let options = super::GeneratorOptions {
+            once_lock: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub static TABLE: OnceLock<Table> = OnceLock::new();"));
+        assert!(dest.contains("Self_: FnPtr::new(metaloadfn(&mut *loadfn, b\"glSelf\\0\", &[])),"));
+        assert!(dest.contains("(storage::table().Self_.f)"));
+        assert!(!dest.contains("static mut"));
+        assert!(!dest.contains("pub fn load_subset_with"));
+
+        let options = super::GeneratorOptions {
+            generation_guard: true,
+            ..options
+        };
+        let error = super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_every_keyword_is_escaped() {
+        let mut registry = keyword_registry();
+        registry.cmds.insert(Cmd {
+            proto: binding("Keywords", "()"),
+            params: super::KEYWORDS
+                .iter()
+                .map(|keyword| binding(keyword, "types::GLint"))
+                .collect(),
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        super::check_idents(&registry).unwrap();
+
+        let mut dest = Vec::new();
+        registry
+            .write_bindings(super::global_gen::GlobalGenerator, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        for keyword in super::KEYWORDS {
+            assert!(dest.contains(&format!("{}_: types::GLint", keyword)));
+        }
+    }
+
+    #[test]
+    fn test_invalid_idents_are_rejected() {
+        for ident in &["", "_", "1D", "foo-bar"] {
+            let mut registry = keyword_registry();
+            registry.cmds.insert(Cmd {
+                proto: binding("Invalid", "()"),
+                params: vec![binding(ident, "types::GLint")],
+                alias: None,
+                vecequiv: None,
+                glx: None,
+            });
+
+            let error = registry
+                .write_bindings(super::global_gen::GlobalGenerator, &mut Vec::new())
+                .unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn test_int_enums() {
+        let mut registry = keyword_registry();
+        for &(ident, value) in &[("RGBA", "0x1908"), ("RED", "0x1903"), ("UNUSED", "0x1")] {
+            registry.enums.insert(Enum {
+                ident: ident.to_string(),
+                value: value.to_string(),
+                cast: false,
+                alias: None,
+                ty: Cow::Borrowed("GLenum"),
+            });
+        }
+        for &(ident, enums) in &[
+            ("InternalFormat", &["RGBA"][..]),
+            ("PixelFormat", &["RGBA", "RED"][..]),
+        ] {
+            registry.groups.insert(
+                ident.to_string(),
+                Group {
+                    ident: ident.to_string(),
+                    enums_type: None,
+                    enums: enums.iter().map(|enm| enm.to_string()).collect(),
+                },
+            );
+        }
+        let mut internalformat = binding("internalformat", "types::GLint");
+        internalformat.group = Some("InternalFormat".to_string());
+        let mut format = binding("format", "types::GLenum");
+        format.group = Some("PixelFormat".to_string());
+        registry.cmds.insert(Cmd {
+            proto: binding("TexImage", "()"),
+            params: vec![internalformat, format],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+
+        let options = super::GeneratorOptions {
+            int_enums: true,
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub const RGBA: types::GLenum = 0x1908;"));
+        assert!(dest
+            .contains("pub const RGBA: super::types::GLint = super::RGBA as super::types::GLint;"));
+        assert!(!dest.contains("pub const RED: super::types::GLint"));
+        assert!(!dest.contains("pub const UNUSED: super::types::GLint"));
+    }
+
+    #[test]
+    fn test_type_overrides() {
+        let options = super::GeneratorOptions::default()
+            .type_override("EGLNativeWindowType", "*mut super::wl_egl_window");
+        let mut dest = Vec::new();
+        super::gen_types(Api::Egl, &options, &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub type EGLNativeWindowType = *mut super::wl_egl_window;"));
+        assert!(dest.contains("pub type EGLNativeDisplayType = super::EGLNativeDisplayType;"));
+
+        let options = super::GeneratorOptions::default().type_override("GLhandleARB", "u64");
+        let mut dest = Vec::new();
+        super::gen_types(Api::Gl, &options, &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert_eq!(dest.matches("pub type GLhandleARB = u64;").count(), 2);
+
+        let options = super::GeneratorOptions::default().type_override("GLunknown", "u8");
+        let error = super::gen_types(Api::Gl, &options, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_size_asserts() {
+        let mut dest = Vec::new();
+        super::gen_types(Api::Gl, &Default::default(), &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(
+            "const _: () = assert!(super::__gl_imports::mem::size_of::<GLsizeiptr>() == \
+             super::__gl_imports::mem::size_of::<usize>());"
+        ));
+        assert!(dest.contains("size_of::<GLint64>() == super::__gl_imports::mem::size_of::<i64>()"));
+        assert!(dest.contains("size_of::<GLhalf>() == super::__gl_imports::mem::size_of::<u16>()"));
+        assert!(!dest.contains("size_of::<khronos_ssize_t>()"));
+
+        let mut dest = Vec::new();
+        super::gen_types(Api::Egl, &Default::default(), &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("size_of::<khronos_ssize_t>()"));
+        assert!(dest.contains("size_of::<EGLAttrib>()"));
+        assert!(!dest.contains("size_of::<GLsizeiptr>()"));
+    }
+
+    #[test]
+    fn test_xlib_path() {
+        let mut dest = Vec::new();
+        super::gen_types(Api::Glx, &Default::default(), &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("pub enum Display {}"));
+        assert!(dest.contains("pub struct XVisualInfo {"));
+        assert!(!dest.contains("pub use"));
+
+        let options = super::GeneratorOptions {
+            xlib_path: Some("::x11::xlib".to_string()),
+            ..Default::default()
+        };
+        let mut dest = Vec::new();
+        super::gen_types(Api::Glx, &options, &mut dest).unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(
+            "#[allow(unused_imports)] pub use ::x11::xlib::{Bool, Display, Font, Pixmap, \
+             Visual, VisualID, Window, XID, XVisualInfo};"
+        ));
+        assert!(!dest.contains("pub enum Display {}"));
+        assert!(!dest.contains("pub struct XVisualInfo {"));
+        assert!(dest.contains("pub type GLXDrawable = XID;"));
+
+        let mut dest = Vec::new();
+        super::gen_types(Api::Gl, &options, &mut dest).unwrap();
+        assert!(!String::from_utf8(dest).unwrap().contains("xlib"));
+
+        for path in &[
+            "x11::xlib",
+            "super::super::xlib",
+            "crate::xlib",
+            "self::xlib",
+        ] {
+            assert!(super::is_valid_path(path), "{}", path);
+        }
+        for path in &[
+            "",
+            "::",
+            "x11::",
+            "x11:xlib",
+            "x11::xlib::",
+            "x11-dl::xlib",
+            "xlib::super",
+        ] {
+            assert!(!super::is_valid_path(path), "{}", path);
+        }
+
+        let options = super::GeneratorOptions {
+            xlib_path: Some("x11::type".to_string()),
+            ..Default::default()
+        };
+        let error = super::gen_types(Api::Glx, &options, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_gen_cmd_name() {
+        let mut registry = keyword_registry();
+        registry.cmds.insert(Cmd {
+            proto: binding("GetError", "types::GLenum"),
+            params: Vec::new(),
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        let options = super::GeneratorOptions {
+            typed_get_error: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            super::gen_cmd_name(&registry, &options, "GetError"),
+            "GetErrorRaw"
+        );
+        assert_eq!(super::gen_cmd_name(&registry, &options, "Self"), "Self_");
+        assert_eq!(
+            super::gen_cmd_name(&registry, &Default::default(), "GetError"),
+            "GetError"
+        );
+
+        // The errors of EGL are `EGLint`s, which `GlError` doesn't describe.
+        registry.api = Api::Egl;
+        assert_eq!(
+            super::gen_cmd_name(&registry, &options, "GetError"),
+            "GetError"
+        );
+    }
+
+    #[test]
+    fn test_parameter_names() {
+        let mut registry = keyword_registry();
+        registry.cmds.insert(Cmd {
+            proto: binding("Viewport", "()"),
+            params: vec![
+                binding("x", "types::GLint"),
+                binding("y", "types::GLint"),
+                binding("width", "types::GLsizei"),
+                binding("height", "types::GLsizei"),
+            ],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        let params =
+            "x: types::GLint, y: types::GLint, width: types::GLsizei, height: types::GLsizei";
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write(&registry, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(&format!("pub unsafe fn Viewport({}) -> ()", params)));
+        assert!(dest.contains(&format!(
+            "pub type PFNGLVIEWPORTPROC = extern \"system\" fn({}) -> ();",
+            params
+        )));
+
+        let mut dest = Vec::new();
+        super::struct_gen::StructGenerator
+            .write(&registry, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(&format!("pub unsafe fn Viewport(&self, {}) -> ()", params)));
+
+        let mut dest = Vec::new();
+        super::static_gen::StaticGenerator
+            .write(&registry, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains(&format!("pub fn Viewport({}) -> ();", params)));
+    }
+
+    #[test]
+    fn test_helpers_scope_unsafe() {
+        let registry = gl_registry();
+        let options = super::GeneratorOptions {
+            helpers: true,
+            typed_get_error: true,
+            ..Default::default()
+        };
+
+        let mut dest = Vec::new();
+        super::global_gen::GlobalGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("unsafe { TexImage2D("));
+        assert!(dest.contains("error::GlError::from(unsafe { GetErrorRaw() })"));
+
+        let mut dest = Vec::new();
+        super::struct_gen::StructGenerator
+            .write_with_options(&registry, &options, &mut dest)
+            .unwrap();
+        let dest = String::from_utf8(dest).unwrap();
+        assert!(dest.contains("unsafe { self.TexImage2D("));
+        assert!(dest.contains("error::GlError::from(unsafe { self.GetErrorRaw() })"));
+    }
+
+    /// Counts the writes that reach an unbuffered destination.
+    #[derive(Default)]
+    struct CountingWriter {
+        writes: usize,
+        len: usize,
+    }
+
+    impl io::Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes += 1;
+            self.len += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn gl_registry() -> Registry {
+        Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+    }
+
+    #[test]
+    fn test_buffered_writes() {
+        let registry = gl_registry();
+        let mut dests: [CountingWriter; 3] = Default::default();
+        super::global_gen::GlobalGenerator
+            .write(&registry, &mut dests[0])
+            .unwrap();
+        super::struct_gen::StructGenerator
+            .write(&registry, &mut dests[1])
+            .unwrap();
+        super::debug_struct_gen::DebugStructGenerator
+            .write(&registry, &mut dests[2])
+            .unwrap();
+
+        for dest in &dests {
+            assert!(dest.len > super::WRITE_BUFFER_SIZE);
+            assert!(dest.writes <= dest.len / super::WRITE_BUFFER_SIZE + 1);
+        }
+    }
+
+    #[test]
+    fn test_parallel_sections() {
+        fn write<G: Generator>(
+            generator: G,
+            registry: &Registry,
+            options: &super::GeneratorOptions,
+        ) -> Vec<u8> {
+            let mut dest = Vec::new();
+            generator
+                .write_with_options(registry, options, &mut dest)
+                .unwrap();
+            dest
+        }
+
+        let registry = gl_registry();
+        let sequential = super::GeneratorOptions {
+            helpers: true,
+            int_enums: true,
+            pfn_table: true,
+            ..Default::default()
+        };
+        let parallel = super::GeneratorOptions {
+            parallel: true,
+            ..sequential.clone()
+        };
+
+        assert_eq!(
+            write(super::global_gen::GlobalGenerator, &registry, &sequential),
+            write(super::global_gen::GlobalGenerator, &registry, &parallel),
+        );
+        assert_eq!(
+            write(super::struct_gen::StructGenerator, &registry, &sequential),
+            write(super::struct_gen::StructGenerator, &registry, &parallel),
+        );
+        assert_eq!(
+            write(
+                super::debug_struct_gen::DebugStructGenerator,
+                &registry,
+                &sequential
+            ),
+            write(
+                super::debug_struct_gen::DebugStructGenerator,
+                &registry,
+                &parallel
+            ),
+        );
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+
+/// Writes nothing, so that a build script or a benchmark using it measures the time taken by
+/// parsing the registry in `Registry::new`, without the time taken by generating the bindings.
+///
+/// All options are ignored.
+#[allow(missing_copy_implementations)]
+pub struct NullGenerator;
+
+impl super::Generator for NullGenerator {
+    fn name(&self) -> &'static str {
+        "NullGenerator"
+    }
+
+    fn write(&self, _registry: &Registry, _dest: &mut dyn io::Write) -> io::Result<()> {
+        Ok(())
+    }
+}
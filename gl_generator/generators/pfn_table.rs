@@ -0,0 +1,129 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `GlPfnTable` structure emitted when `GeneratorOptions::pfn_table` is set.
+
+use registry::Registry;
+use std::io;
+
+/// Generates the name of the function pointer table, such as `GlPfnTable`.
+fn gen_table_name(registry: &Registry) -> String {
+    format!("{}PfnTable", super::gen_struct_name(registry.api))
+}
+
+/// Writes the function pointer table, and the methods of the structure of the struct generators
+/// converting from and to it.
+pub fn write_pfn_table<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let table = gen_table_name(registry);
+
+    writeln!(
+        dest,
+        "/// The function pointers of the commands in a `#[repr(C)]` structure, which can be passed
+        /// to C code or swapped as a whole. `None` stands for a command that is not loaded.
+        #[allow(non_snake_case, dead_code)]
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct {table} {{",
+        table = table,
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "pub {name}: Option<{pfn}>,",
+            name = super::gen_ident(&cmd.proto.ident),
+            pfn = super::gen_pfn_name(registry.api, &cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        impl {table} {{
+            /// Returns the pointer of the command with the symbol `symbol`, or null if it is not
+            /// loaded or not part of the table.
+            #[allow(dead_code, clippy::zero_ptr)]
+            pub fn symbol_ptr(&self, symbol: &str) -> *const __gl_imports::raw::c_void {{
+                match symbol {{",
+        table = table,
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "\"{symbol}\" => self.{name}.map_or(0 as *const _, |f| f as *const _),",
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            name = super::gen_ident(&cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "_ => 0 as *const _,
+                }}
+            }}
+        }}
+
+        impl {api} {{
+            /// Copies the loaded function pointers into a `{table}`.
+            #[allow(dead_code)]
+            pub fn pfn_table(&self) -> {table} {{
+                {table} {{",
+        api = super::gen_struct_name(registry.api),
+        table = table,
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{name}: {ptr}.map(|ptr| unsafe {{
+                __gl_imports::mem::transmute::<*const __gl_imports::raw::c_void, {pfn}>(ptr)
+            }}),",
+            name = super::gen_ident(&cmd.proto.ident),
+            ptr = super::gen_field_ptr(
+                options,
+                &format!(
+                    "self.{}",
+                    super::gen_field_path(registry, options, &cmd.proto.ident)
+                ),
+                "__gl_imports::raw",
+            ),
+            pfn = super::gen_pfn_name(registry.api, &cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+            }}
+
+            /// Creates the bindings from the function pointers of `table`, falling back to the
+            /// aliases of the commands that it doesn't have.
+            #[allow(dead_code)]
+            pub fn from_pfn_table(table: &{table}) -> {api} {{
+                {api}::load_with(|symbol| table.symbol_ptr(symbol))
+            }}
+        }}",
+        api = super::gen_struct_name(registry.api),
+        table = table,
+    )
+}
@@ -0,0 +1,315 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `load` function emitted when `GeneratorOptions::platform_loader` is set.
+//!
+//! The generated code opens the platform library with `dlopen` or `LoadLibraryA` itself, so the
+//! bindings don't need any dependency.
+
+use super::helpers::Dispatch;
+use registry::Registry;
+use std::io;
+use Api;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Platform {
+    /// Linux, the BSDs, Android and the other unix systems that are not made by Apple.
+    Unix,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    /// The `cfg` predicate that selects the platform.
+    fn cfg(self) -> &'static str {
+        match self {
+            Platform::Unix => "all(unix, not(any(target_os = \"macos\", target_os = \"ios\")))",
+            Platform::MacOs => "any(target_os = \"macos\", target_os = \"ios\")",
+            Platform::Windows => "windows",
+        }
+    }
+}
+
+fn egl_libraries(platform: Platform) -> &'static [&'static str] {
+    match platform {
+        Platform::Unix => &["libEGL.so.1", "libEGL.so"],
+        Platform::MacOs => &["libEGL.dylib"],
+        Platform::Windows => &["libEGL.dll"],
+    }
+}
+
+/// Returns the libraries that provide the commands of `api` on `platform`, along with the
+/// libraries and the name of the function that looks up commands, if there is one.
+fn libraries(
+    api: Api,
+    platform: Platform,
+) -> (
+    &'static [&'static str],
+    Option<(&'static [&'static str], &'static str)>,
+) {
+    match (api, platform) {
+        (Api::Gl, Platform::Unix) | (Api::GlCore, Platform::Unix) | (Api::Glx, Platform::Unix) => {
+            let libraries = &["libGL.so.1", "libGL.so"];
+            (libraries, Some((libraries, "glXGetProcAddressARB")))
+        },
+        (Api::Gl, Platform::MacOs) | (Api::GlCore, Platform::MacOs) => (
+            &["/System/Library/Frameworks/OpenGL.framework/OpenGL"],
+            None,
+        ),
+        (Api::Gl, Platform::Windows)
+        | (Api::GlCore, Platform::Windows)
+        | (Api::Wgl, Platform::Windows) => {
+            let libraries = &["opengl32.dll"];
+            (libraries, Some((libraries, "wglGetProcAddress")))
+        },
+        (Api::Glx, _) | (Api::Wgl, _) => (&[], None),
+        (Api::Egl, platform) => {
+            let libraries = egl_libraries(platform);
+            (libraries, Some((libraries, "eglGetProcAddress")))
+        },
+        (Api::Gles1, platform) => (
+            match platform {
+                Platform::Unix => &["libGLESv1_CM.so.1", "libGLESv1_CM.so"],
+                Platform::MacOs => &["libGLESv1_CM.dylib"],
+                Platform::Windows => &["libGLESv1_CM.dll"],
+            },
+            Some((egl_libraries(platform), "eglGetProcAddress")),
+        ),
+        (Api::Gles2, platform) | (Api::Glsc2, platform) => (
+            match platform {
+                Platform::Unix => &["libGLESv2.so.2", "libGLESv2.so"],
+                Platform::MacOs => &["libGLESv2.dylib"],
+                Platform::Windows => &["libGLESv2.dll"],
+            },
+            Some((egl_libraries(platform), "eglGetProcAddress")),
+        ),
+    }
+}
+
+fn quote_all(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("\"{}\"", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Writes a `LoadError` type and a `load` function, or method for `Dispatch::Method`, that loads
+/// the bindings from the platform library.
+pub fn write_platform_loader<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let std_path = super::std_path(options);
+    writeln!(
+        dest,
+        r#"
+        /// The error returned by `load` when none of the platform libraries could be opened.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct LoadError {{
+            /// The names of the libraries that were tried, empty if the platform is not supported.
+            pub libraries: &'static [&'static str],
+        }}
+
+        impl {std}::fmt::Display for LoadError {{
+            fn fmt(&self, f: &mut {std}::fmt::Formatter) -> {std}::fmt::Result {{
+                if self.libraries.is_empty() {{
+                    write!(f, "no {api} library is known for this platform")
+                }} else {{
+                    write!(f, "could not open any of the {api} libraries: {{}}", self.libraries.join(", "))
+                }}
+            }}
+        }}
+
+        impl {std}::error::Error for LoadError {{}}
+
+        /// Opening the platform library without a windowing library.
+        #[allow(dead_code)]
+        mod platform {{
+            use super::__gl_imports::{{raw, CStr}};
+            use {std}::ffi::CString;
+        "#,
+        std = std_path,
+        api = registry.api,
+    )?;
+
+    for &platform in &[Platform::Unix, Platform::MacOs, Platform::Windows] {
+        let (libraries, get_proc_address) = libraries(registry.api, platform);
+        writeln!(
+            dest,
+            "
+            #[cfg({cfg})]
+            pub const LIBRARIES: &[&str] = &[{libraries}];
+            #[cfg({cfg})]
+            const GET_PROC_ADDRESS: Option<(&'static [&'static str], &'static str)> = {get_proc_address};",
+            cfg = platform.cfg(),
+            libraries = quote_all(libraries),
+            get_proc_address = match get_proc_address {
+                Some((libraries, symbol)) => {
+                    format!("Some((&[{}], \"{}\"))", quote_all(libraries), symbol)
+                },
+                None => "None".to_string(),
+            },
+        )?;
+    }
+
+    writeln!(
+        dest,
+        r#"
+            #[cfg(not(any(unix, windows)))]
+            pub const LIBRARIES: &[&str] = &[];
+            #[cfg(not(any(unix, windows)))]
+            const GET_PROC_ADDRESS: Option<(&'static [&'static str], &'static str)> = None;
+
+            #[cfg(unix)]
+            mod sys {{
+                use super::raw;
+
+                const RTLD_NOW: raw::c_int = 2;
+
+                #[cfg_attr(any(target_os = "linux", target_os = "android"), link(name = "dl"))]
+                {unsafety}extern "C" {{
+                    fn dlopen(filename: *const raw::c_char, flag: raw::c_int) -> *mut raw::c_void;
+                    fn dlsym(handle: *mut raw::c_void, symbol: *const raw::c_char) -> *mut raw::c_void;
+                }}
+
+                pub fn open(name: *const raw::c_char) -> *mut raw::c_void {{
+                    unsafe {{ dlopen(name, RTLD_NOW) }}
+                }}
+
+                pub fn symbol(handle: *mut raw::c_void, name: *const raw::c_char) -> *const raw::c_void {{
+                    unsafe {{ dlsym(handle, name) }}
+                }}
+            }}
+
+            #[cfg(windows)]
+            mod sys {{
+                use super::raw;
+
+                #[link(name = "kernel32")]
+                {unsafety}extern "system" {{
+                    fn LoadLibraryA(filename: *const raw::c_char) -> *mut raw::c_void;
+                    fn GetProcAddress(module: *mut raw::c_void, symbol: *const raw::c_char) -> *mut raw::c_void;
+                }}
+
+                pub fn open(name: *const raw::c_char) -> *mut raw::c_void {{
+                    unsafe {{ LoadLibraryA(name) }}
+                }}
+
+                pub fn symbol(handle: *mut raw::c_void, name: *const raw::c_char) -> *const raw::c_void {{
+                    unsafe {{ GetProcAddress(handle, name) }}
+                }}
+            }}
+
+            #[cfg(not(any(unix, windows)))]
+            mod sys {{
+                use super::raw;
+                use {std}::ptr;
+
+                pub fn open(_: *const raw::c_char) -> *mut raw::c_void {{
+                    ptr::null_mut()
+                }}
+
+                pub fn symbol(_: *mut raw::c_void, _: *const raw::c_char) -> *const raw::c_void {{
+                    ptr::null()
+                }}
+            }}
+
+            /// Opens the first library of `names` that can be found.
+            ///
+            /// The library is never closed, since the loaded function pointers must stay valid for
+            /// the lifetime of the program.
+            fn open(names: &[&str]) -> Option<*mut raw::c_void> {{
+                names.iter().filter_map(|name| {{
+                    let name = CString::new(*name).unwrap();
+                    let handle = sys::open(name.as_ptr());
+                    if handle.is_null() {{ None }} else {{ Some(handle) }}
+                }}).next()
+            }}
+
+            /// Returns a load function that resolves commands with the `GetProcAddress` function of
+            /// the platform when available, and directly in the library otherwise.
+            pub fn loader() -> Result<{std}::boxed::Box<dyn FnMut(&'static CStr) -> *const raw::c_void>, super::LoadError> {{
+                let library = match open(LIBRARIES) {{
+                    Some(library) => library,
+                    None => return Err(super::LoadError {{ libraries: LIBRARIES }}),
+                }};
+
+                let get_proc_address = GET_PROC_ADDRESS.and_then(|(names, symbol)| {{
+                    let library = open(names)?;
+                    let symbol = CString::new(symbol).unwrap();
+                    let ptr = sys::symbol(library, symbol.as_ptr());
+                    if ptr.is_null() {{
+                        None
+                    }} else {{
+                        Some(unsafe {{
+                            super::__gl_imports::mem::transmute::<*const raw::c_void, extern "system" fn(*const raw::c_char) -> *const raw::c_void>(ptr)
+                        }})
+                    }}
+                }});
+
+                Ok({std}::boxed::Box::new(move |symbol| {{
+                    if let Some(get_proc_address) = get_proc_address {{
+                        let ptr = get_proc_address(symbol.as_ptr());
+                        // `wglGetProcAddress` can also return 1, 2, 3 or -1 on failure.
+                        match ptr as isize {{
+                            -1..=3 => {{}},
+                            _ => return ptr,
+                        }}
+                    }}
+                    sys::symbol(library, symbol.as_ptr())
+                }}))
+            }}
+        }}
+        "#,
+        std = std_path,
+        unsafety = super::gen_extern_unsafety(options),
+    )?;
+
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "
+            /// Loads each symbol from the platform library, without a windowing library.
+            ///
+            /// Commands are looked up with the `GetProcAddress` function of the platform when
+            /// it is available, and directly in the library otherwise.
+            #[allow(dead_code)]
+            pub fn load() -> Result<(), LoadError> {{
+                load_with_cstr(platform::loader()?);
+                Ok(())
+            }}"
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "
+            impl {api} {{
+                /// Loads each symbol from the platform library, without a windowing library.
+                ///
+                /// Commands are looked up with the `GetProcAddress` function of the platform
+                /// when it is available, and directly in the library otherwise.
+                #[allow(dead_code)]
+                pub fn load() -> Result<{api}, LoadError> {{
+                    Ok({api}::load_with_cstr(platform::loader()?))
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+        ),
+    }
+}
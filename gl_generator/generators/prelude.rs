@@ -0,0 +1,71 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `prelude` module emitted when `GeneratorOptions::prelude` is set.
+
+use super::helpers::Dispatch;
+use super::Prelude;
+use registry::Registry;
+use std::io;
+
+/// Writes a `prelude` module re-exporting the types and, depending on `prelude`, the commands of
+/// the bindings. The re-exports of the commands are gated like the commands themselves when
+/// `GeneratorOptions::extension_features` is set.
+pub fn write_prelude<W>(
+    registry: &Registry,
+    prelude: Prelude,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Re-exports the common items of the bindings, for `use prelude::*`.
+        pub mod prelude {{
+            pub use super::types::*;"
+    )?;
+
+    if prelude == Prelude::TypesAndCommands {
+        match dispatch {
+            Dispatch::Free => {
+                for cmd in &registry.cmds {
+                    writeln!(
+                        dest,
+                        "{}pub use super::{};",
+                        super::gen_cfg_attrs(
+                            super::gen_cmd_cfg(registry, options, &cmd.proto.ident)
+                                .as_ref()
+                                .map(|p| &p[..]),
+                            false,
+                        ),
+                        super::gen_ident(&cmd.proto.ident)
+                    )?;
+                }
+            },
+            Dispatch::Method => {
+                writeln!(
+                    dest,
+                    "pub use super::{};",
+                    super::gen_struct_name(registry.api)
+                )?;
+            },
+        }
+    }
+
+    writeln!(dest, "}}")
+}
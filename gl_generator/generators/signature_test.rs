@@ -0,0 +1,149 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test of the signatures of the commands emitted when
+//! `GeneratorOptions::signature_test` is set.
+
+use super::helpers::Dispatch;
+use registry::{Cmd, Registry};
+use std::io;
+
+/// Generates the snapshot of the signature of a command, such as
+/// `"Clear(mask: types::GLbitfield) -> ()"`, from the bindings of the registry rather than from
+/// `gen_parameter_list`, which generated the function being checked.
+fn gen_snapshot(name: &str, cmd: &Cmd) -> String {
+    let params: Vec<_> = cmd
+        .params
+        .iter()
+        .map(|binding| format!("{}: {}", super::gen_ident(&binding.ident), binding.ty))
+        .collect();
+    format!("{}({}) -> {}", name, params.join(", "), cmd.proto.ty)
+}
+
+/// Writes the `signature_test` module, compiled only for tests, whose `signatures` test casts
+/// each function or method calling a command to a function pointer, in a closure that is never
+/// called, with its parameter and return types inferred. The name of that type is compared with
+/// the one of the function pointer type spelled out from the snapshot of the signature, embedded
+/// when the bindings were generated, and a command whose signature drifted is reported with its
+/// snapshot.
+///
+/// `fn_ty` is the kind of function pointer that the functions coerce to, such as `"unsafe fn"`.
+pub fn write_signature_test<W>(
+    registry: &Registry,
+    dispatch: Dispatch,
+    options: &super::GeneratorOptions,
+    fn_ty: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "#[cfg(test)]
+        mod signature_test {{
+            #![allow(non_snake_case, unused_imports)]
+
+            use super::{{__gl_imports, types}};
+            use {std}::any::type_name;
+            use {std}::vec::Vec;
+
+            /// Returns the name of the type that `f` returns, without calling it, so that the
+            /// functions of the static generators don't need to be linked.
+            fn return_type_name<F: FnOnce() -> T, T>(_f: F) -> &'static str {{
+                type_name::<T>()
+            }}
+
+            /// Records `snapshot` in `drifted` if the name of the type of the function differs
+            /// from the expected one.
+            fn check(
+                drifted: &mut Vec<&'static str>,
+                snapshot: &'static str,
+                actual: &'static str,
+                expected: &'static str,
+            ) {{
+                if actual != expected {{
+                    drifted.push(snapshot);
+                }}
+            }}
+
+            /// Compares the signature of each command with its snapshot.
+            #[test]
+            fn signatures() {{
+                let mut drifted = Vec::new();",
+        std = super::std_path(options),
+    )?;
+
+    let (path, receiver) = match dispatch {
+        Dispatch::Free => ("super::".to_string(), None),
+        Dispatch::Method => {
+            let api = super::gen_struct_name(registry.api);
+            (
+                format!("super::{}::", api),
+                Some(format!("&super::{}", api)),
+            )
+        },
+    };
+
+    for cmd in &registry.cmds {
+        let name = super::gen_cmd_name(registry, options, &cmd.proto.ident);
+        // The methods take `&self` as the first parameter of their function pointer type.
+        let inferred: Vec<_> = receiver
+            .as_ref()
+            .map(|ty| &ty[..])
+            .into_iter()
+            .chain(cmd.params.iter().map(|_| "_"))
+            .collect();
+        let expected: Vec<_> = receiver
+            .as_ref()
+            .map(|ty| &ty[..])
+            .into_iter()
+            .chain(cmd.params.iter().map(|binding| &binding.ty[..]))
+            .collect();
+
+        writeln!(
+            dest,
+            "{cfg} check(
+                &mut drifted,
+                \"{snapshot}\",
+                return_type_name(|| {path}{name} as {fn_ty}({inferred}) -> _),
+                type_name::<{fn_ty}({expected}) -> {return_suffix}>(),
+            );",
+            cfg = super::gen_cfg_attrs(
+                super::gen_cmd_cfg(registry, options, &cmd.proto.ident)
+                    .as_ref()
+                    .map(|p| &p[..]),
+                false,
+            ),
+            snapshot = gen_snapshot(&name, cmd),
+            path = path,
+            name = name,
+            fn_ty = fn_ty,
+            inferred = inferred.join(", "),
+            expected = expected.join(", "),
+            return_suffix = cmd.proto.ty,
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "assert!(
+                    drifted.is_empty(),
+                    \"the signatures of these commands differ from their snapshot:\\n{{}}\",
+                    drifted.join(\"\\n\")
+                );
+            }}
+        }}"
+    )
+}
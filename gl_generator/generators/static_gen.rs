@@ -19,21 +19,107 @@ use std::io;
 pub struct StaticGenerator;
 
 impl super::Generator for StaticGenerator {
-    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
-    where
-        W: io::Write,
-    {
-        write_header(dest)?;
-        write_type_aliases(registry, dest)?;
+    fn name(&self) -> &'static str {
+        "StaticGenerator"
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        self.write_with_options(registry, &Default::default(), dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &super::GeneratorOptions,
+        mut dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let dest = &mut dest;
+
+        if options.platform_loader {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`platform_loader` is not supported by `StaticGenerator`",
+            ));
+        }
+
+        write_header(registry, options, dest)?;
+        write_type_aliases(registry, options, dest)?;
         write_enums(registry, dest)?;
-        write_fns(registry, dest)?;
+        if options.int_enums {
+            super::gen_int_enums(registry, dest)?;
+        }
+        write_pfn_types(registry, dest)?;
+        write_fns(registry, options, dest)?;
+        if options.dynamic_extensions {
+            write_dynamic_fns(registry, options, dest)?;
+        }
+        if options.wasm_stubs {
+            super::wasm_stubs::write_wasm_stubs(registry, options, true, dest)?;
+        }
+        if options.helpers
+            || options.typed_get_error
+            || options.nonzero_create
+            || options.alias_shims
+        {
+            super::helpers::write_helpers(registry, super::helpers::Dispatch::Free, options, dest)?;
+        }
+        if options.capabilities {
+            super::capabilities::write_capabilities(
+                registry,
+                super::helpers::Dispatch::Free,
+                dest,
+            )?;
+        }
+        if options.handle_types {
+            super::handles::write_typed_commands(
+                registry,
+                super::helpers::Dispatch::Free,
+                options,
+                dest,
+            )?;
+        }
+        if options.dispatch_table {
+            super::dispatch_table::write_dispatch_table(
+                registry,
+                super::helpers::Dispatch::Free,
+                options,
+                dest,
+            )?;
+        }
+        if options.signature_test {
+            super::signature_test::write_signature_test(
+                registry,
+                super::helpers::Dispatch::Free,
+                options,
+                "unsafe extern \"system\" fn",
+                dest,
+            )?;
+        }
+        if options.command_versions {
+            super::command_version::write_command_version(registry, dest)?;
+        }
+        if let Some(prelude) = options.prelude {
+            // The commands are never gated, as `extension_features` is ignored.
+            super::prelude::write_prelude(
+                registry,
+                prelude,
+                super::helpers::Dispatch::Free,
+                &Default::default(),
+                dest,
+            )?;
+        }
         Ok(())
     }
 }
 
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
-///  bindings.
-fn write_header<W>(dest: &mut W) -> io::Result<()>
+///  bindings, followed by the `GENERATED_FOR` constant if `GeneratorOptions::version_assertions`
+///  is set.
+fn write_header<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -41,17 +127,27 @@ where
         dest,
         r#"
         mod __gl_imports {{
-            pub use std::mem;
-            pub use std::os::raw;
+            #[allow(unused_imports)] // Only used by some commands and options
+            pub use {std}::mem;
+            pub use {std}::os::raw;
         }}
-    "#
-    )
+    "#,
+        std = super::std_path(options)
+    )?;
+    if options.version_assertions {
+        super::gen_generated_for(registry, options, dest)?;
+    }
+    Ok(())
 }
 
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_types`.
-fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_type_aliases<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -59,11 +155,14 @@ where
         dest,
         r#"
         pub mod types {{
-            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations, clippy::upper_case_acronyms)]
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry.api, options, dest)?;
+    if options.handle_types {
+        super::handles::write_handle_types(registry, dest)?;
+    }
 
     writeln!(
         dest,
@@ -85,31 +184,162 @@ where
     Ok(())
 }
 
+/// Creates a `PFN...PROC` type alias for each command, which is its function pointer type.
+fn write_pfn_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for cmd in &registry.cmds {
+        super::gen_pfn_item(registry.api, cmd, dest)?;
+    }
+
+    Ok(())
+}
+
 /// io::Writes all functions corresponding to the GL bindings.
 ///
-/// These are foreign functions, they don't have any content.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// These are foreign functions, they don't have any content. Their symbols start with
+/// `GeneratorOptions::static_symbol_prefix`, and the block links the library of the API with
+/// `GeneratorOptions::link_attrs`. With `GeneratorOptions::dynamic_extensions`, the extension
+/// commands are left to `write_dynamic_fns`.
+fn write_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
+    let prefix = options
+        .static_symbol_prefix
+        .as_ref()
+        .map_or("", |prefix| prefix);
+
     writeln!(
         dest,
         "
-        #[allow(non_snake_case, unused_variables, dead_code)]
-        extern \"system\" {{"
+        #[allow(non_snake_case, unused_variables, dead_code, clashing_extern_declarations)]
+        {cfg} {link} {unsafety}extern \"system\" {{",
+        cfg = super::wasm_stubs::gen_extern_cfg(options),
+        unsafety = super::gen_extern_unsafety(options),
+        link = super::gen_link_attrs(registry, options)?,
     )?;
 
+    let core_cmds = super::gen_core_cmds(registry);
     for cmd in &registry.cmds {
+        if options.dynamic_extensions && !core_cmds.contains(&cmd.proto.ident[..]) {
+            continue;
+        }
         writeln!(
             dest,
-            "#[link_name=\"{symbol}\"]
+            "{alias}#[link_name=\"{prefix}{symbol}\"]
             pub fn {name}({params}) -> {return_suffix};",
+            alias = super::gen_doc_alias(registry, options, &cmd.proto.ident),
+            prefix = prefix,
             symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
-            name = cmd.proto.ident,
-            params = super::gen_parameters(cmd, true, true).join(", "),
+            name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+            params = super::gen_parameter_list(cmd, true, true),
             return_suffix = cmd.proto.ty,
         )?;
     }
 
     writeln!(dest, "}}")
 }
+
+/// Creates the functions of the extension commands when `GeneratorOptions::dynamic_extensions` is
+/// set, which call the function pointers that `load_extensions_with` stores in the
+/// `dynamic_storage` module instead of being linked, along with a module per command with an
+/// `is_loaded` function. They keep the ABI of the foreign functions, so their types don't change.
+fn write_dynamic_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let core_cmds = super::gen_core_cmds(registry);
+    let cmds: Vec<_> = registry
+        .cmds
+        .iter()
+        .filter(|cmd| !core_cmds.contains(&cmd.proto.ident[..]))
+        .collect();
+
+    writeln!(
+        dest,
+        "#[allow(non_upper_case_globals, dead_code, unused_imports, clippy::zero_ptr)]
+        mod dynamic_storage {{
+            use super::__gl_imports::raw;"
+    )?;
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "pub static mut {name}: *const raw::c_void = 0 as *const raw::c_void;",
+            name = super::gen_ident(&cmd.proto.ident),
+        )?;
+    }
+    writeln!(
+        dest,
+        "}}
+
+        #[allow(dead_code)]
+        #[inline(never)]
+        fn missing_fn_panic(symbol: &str) -> ! {{
+            panic!(\"{api} function {{}} was not loaded by `load_extensions_with`\", symbol)
+        }}
+
+        /// Load the extension commands, which are not linked, using a custom load function such
+        /// as `eglGetProcAddress`. An extension command panics until it has been loaded.
+        #[allow(dead_code, unused_mut, unused_variables, unused_unsafe)]
+        pub fn load_extensions_with<F>(mut loadfn: F)
+            where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+        {{
+            unsafe {{",
+        api = registry.api,
+    )?;
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "dynamic_storage::{name} = loadfn(\"{symbol}\");",
+            name = super::gen_ident(&cmd.proto.ident),
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+        )?;
+    }
+    writeln!(dest, "}} }}")?;
+
+    for cmd in &cmds {
+        writeln!(
+            dest,
+            "{alias}#[allow(non_snake_case, unused_variables, dead_code, unused_unsafe)]
+            #[allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit)] #[inline]
+            pub unsafe extern \"system\" fn {fn_name}({params}) -> {return_suffix} {{{unsafe_open}
+                let ptr = dynamic_storage::{name};
+                if ptr.is_null() {{
+                    missing_fn_panic(\"{symbol}\")
+                }}
+                __gl_imports::mem::transmute::<*const __gl_imports::raw::c_void, {pfn}>(ptr)({idents})
+            {unsafe_close}}}
+
+            #[allow(non_snake_case, dead_code)]
+            pub mod {fn_name} {{
+                /// Whether the command has been loaded by `load_extensions_with`.
+                #[inline]
+                pub fn is_loaded() -> bool {{
+                    unsafe {{ !super::dynamic_storage::{name}.is_null() }}
+                }}
+            }}",
+            alias = super::gen_doc_alias(registry, options, &cmd.proto.ident),
+            unsafe_open = super::UNSAFE_OPEN,
+            unsafe_close = super::UNSAFE_CLOSE,
+            fn_name = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+            name = super::gen_ident(&cmd.proto.ident),
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            params = super::gen_parameter_list(cmd, true, true),
+            return_suffix = cmd.proto.ty,
+            pfn = super::gen_pfn_name(registry.api, &cmd.proto.ident),
+            idents = super::gen_parameter_list(cmd, true, false),
+        )?;
+    }
+
+    Ok(())
+}
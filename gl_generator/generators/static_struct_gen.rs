@@ -19,23 +19,110 @@ use std::io;
 pub struct StaticStructGenerator;
 
 impl super::Generator for StaticStructGenerator {
-    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
-    where
-        W: io::Write,
-    {
-        write_header(dest)?;
-        write_type_aliases(registry, dest)?;
+    fn name(&self) -> &'static str {
+        "StaticStructGenerator"
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        self.write_with_options(registry, &Default::default(), dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &super::GeneratorOptions,
+        mut dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let dest = &mut dest;
+
+        if options.platform_loader {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "`platform_loader` is not supported by `StaticStructGenerator`",
+            ));
+        }
+
+        write_header(registry, options, dest)?;
+        write_type_aliases(registry, options, dest)?;
         write_enums(registry, dest)?;
+        if options.int_enums {
+            super::gen_int_enums(registry, dest)?;
+        }
+        write_pfn_types(registry, dest)?;
         write_struct(registry, dest)?;
-        write_impl(registry, dest)?;
-        write_fns(registry, dest)?;
+        write_impl(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
+        if options.wasm_stubs {
+            super::wasm_stubs::write_wasm_stubs(registry, options, false, dest)?;
+        }
+        if options.helpers
+            || options.typed_get_error
+            || options.nonzero_create
+            || options.alias_shims
+        {
+            super::helpers::write_helpers(
+                registry,
+                super::helpers::Dispatch::Method,
+                options,
+                dest,
+            )?;
+        }
+        if options.capabilities {
+            super::capabilities::write_capabilities(
+                registry,
+                super::helpers::Dispatch::Method,
+                dest,
+            )?;
+        }
+        if options.handle_types {
+            super::handles::write_typed_commands(
+                registry,
+                super::helpers::Dispatch::Method,
+                options,
+                dest,
+            )?;
+        }
+        if options.dispatch_table {
+            super::dispatch_table::write_dispatch_table(
+                registry,
+                super::helpers::Dispatch::Method,
+                options,
+                dest,
+            )?;
+        }
+        if options.signature_test {
+            super::signature_test::write_signature_test(
+                registry,
+                super::helpers::Dispatch::Method,
+                options,
+                "unsafe fn",
+                dest,
+            )?;
+        }
+        if options.command_versions {
+            super::command_version::write_command_version(registry, dest)?;
+        }
+        if let Some(prelude) = options.prelude {
+            super::prelude::write_prelude(
+                registry,
+                prelude,
+                super::helpers::Dispatch::Method,
+                options,
+                dest,
+            )?;
+        }
         Ok(())
     }
 }
 
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
-///  bindings.
-fn write_header<W>(dest: &mut W) -> io::Result<()>
+///  bindings, followed by the `GENERATED_FOR` constant if `GeneratorOptions::version_assertions`
+///  is set.
+fn write_header<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -43,17 +130,28 @@ where
         dest,
         r#"
         mod __gl_imports {{
-            pub use std::mem;
-            pub use std::os::raw;
+            pub use {std}::ffi::CStr;
+            #[allow(unused_imports)] // Only used by some commands and options
+            pub use {std}::mem;
+            pub use {std}::os::raw;
         }}
-    "#
-    )
+    "#,
+        std = super::std_path(options)
+    )?;
+    if options.version_assertions {
+        super::gen_generated_for(registry, options, dest)?;
+    }
+    Ok(())
 }
 
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_types`.
-fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_type_aliases<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -61,11 +159,14 @@ where
         dest,
         r#"
         pub mod types {{
-            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations, clippy::upper_case_acronyms)]
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry.api, options, dest)?;
+    if options.handle_types {
+        super::handles::write_handle_types(registry, dest)?;
+    }
 
     writeln!(dest, "}}")
 }
@@ -82,6 +183,18 @@ where
     Ok(())
 }
 
+/// Creates a `PFN...PROC` type alias for each command, which is its function pointer type.
+fn write_pfn_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for cmd in &registry.cmds {
+        super::gen_pfn_item(registry.api, cmd, dest)?;
+    }
+
+    Ok(())
+}
+
 /// Creates a stub structure.
 ///
 /// The name of the struct corresponds to the namespace.
@@ -100,7 +213,11 @@ where
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_impl<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -110,6 +227,12 @@ where
             #[allow(dead_code)]
             pub fn load_with<F>(mut _loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
                 {api}
+            }}
+
+            /// Stub function.
+            #[allow(dead_code)]
+            pub fn load_with_cstr<F>(mut _loadfn: F) -> {api} where F: FnMut(&'static __gl_imports::CStr) -> *const __gl_imports::raw::c_void {{
+                {api}
             }}",
         api = super::gen_struct_name(registry.api),
     )?;
@@ -117,17 +240,22 @@ where
     for cmd in &registry.cmds {
         writeln!(
             dest,
-            "#[allow(non_snake_case)]
+            "{alias}#[allow(non_snake_case, unused_unsafe)]
             // #[allow(unused_variables)]
             #[allow(dead_code)]
+            #[allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::unused_unit)]
             #[inline]
-            pub unsafe fn {name}(&self, {typed_params}) -> {return_suffix} {{
-                {name}({idents})
+            pub unsafe fn {method}(&self, {typed_params}) -> {return_suffix} {{
+                {unsafe_open}{name}({idents}){unsafe_close}
             }}",
-            name = cmd.proto.ident,
-            typed_params = super::gen_parameters(cmd, true, true).join(", "),
+            alias = super::gen_doc_alias(registry, options, &cmd.proto.ident),
+            unsafe_open = super::UNSAFE_OPEN,
+            unsafe_close = super::UNSAFE_CLOSE,
+            method = super::gen_cmd_name(registry, options, &cmd.proto.ident),
+            name = super::gen_ident(&cmd.proto.ident),
+            typed_params = super::gen_parameter_list(cmd, true, true),
             return_suffix = cmd.proto.ty,
-            idents = super::gen_parameters(cmd, true, false).join(", "),
+            idents = super::gen_parameter_list(cmd, true, false),
         )?;
     }
 
@@ -136,27 +264,43 @@ where
 
 /// io::Writes all functions corresponding to the GL bindings.
 ///
-/// These are foreign functions, they don't have any content.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// These are foreign functions, they don't have any content. Their symbols start with
+/// `GeneratorOptions::static_symbol_prefix`, and the block links the library of the API with
+/// `GeneratorOptions::link_attrs`.
+fn write_fns<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
+    let prefix = options
+        .static_symbol_prefix
+        .as_ref()
+        .map_or("", |prefix| prefix);
+
     writeln!(
         dest,
         "
         #[allow(non_snake_case)]
         #[allow(unused_variables)]
         #[allow(dead_code)]
-        extern \"system\" {{"
+        #[allow(clashing_extern_declarations)]
+        {cfg} {link} {unsafety}extern \"system\" {{",
+        cfg = super::wasm_stubs::gen_extern_cfg(options),
+        unsafety = super::gen_extern_unsafety(options),
+        link = super::gen_link_attrs(registry, options)?,
     )?;
 
     for cmd in &registry.cmds {
         writeln!(
             dest,
-            "#[link_name=\"{symbol}\"] fn {name}({params}) -> {return_suffix};",
+            "#[link_name=\"{prefix}{symbol}\"] fn {name}({params}) -> {return_suffix};",
+            prefix = prefix,
             symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
-            name = cmd.proto.ident,
-            params = super::gen_parameters(cmd, true, true).join(", "),
+            name = super::gen_ident(&cmd.proto.ident),
+            params = super::gen_parameter_list(cmd, true, true),
             return_suffix = cmd.proto.ty,
         )?;
     }
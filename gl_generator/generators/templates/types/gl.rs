@@ -15,12 +15,28 @@ pub type GLfloat = super::__gl_imports::raw::c_float;
 pub type GLclampf = super::__gl_imports::raw::c_float;
 pub type GLdouble = super::__gl_imports::raw::c_double;
 pub type GLclampd = super::__gl_imports::raw::c_double;
-pub type GLeglImageOES = *const super::__gl_imports::raw::c_void;
 pub type GLchar = super::__gl_imports::raw::c_char;
 pub type GLcharARB = super::__gl_imports::raw::c_char;
 
+/// Converts a `bool` to a `GLboolean`, which is `TRUE` or `FALSE`.
+#[inline]
+pub const fn gl_bool(b: bool) -> GLboolean {
+    b as GLboolean
+}
+
+/// Converts a `GLboolean` to a `bool`. Any nonzero value is true, as in the specification.
+#[inline]
+pub const fn from_gl_bool(v: GLboolean) -> bool {
+    v != 0
+}
+
+// `GLboolean` is an alias of `u8`, whose `From<bool>` impl comes from the standard library. Both
+// types are foreign, so no other `From` impl is allowed.
+
+#[cfg(target_os = "macos")]
+pub enum __GLhandleARB {}
 #[cfg(target_os = "macos")]
-pub type GLhandleARB = *const super::__gl_imports::raw::c_void;
+pub type GLhandleARB = *const __GLhandleARB;
 #[cfg(not(target_os = "macos"))]
 pub type GLhandleARB = super::__gl_imports::raw::c_uint;
 
@@ -39,8 +55,12 @@ pub type GLsizeiptrARB = isize;
 pub type GLint64EXT = i64;
 pub type GLuint64EXT = u64;
 
+// The opaque handles point to uninhabited types, so that they keep the layout of a pointer but
+// can't be dereferenced or mixed up with other pointers.
 pub enum __GLsync {}
 pub type GLsync = *const __GLsync;
+pub enum __GLeglImageOES {}
+pub type GLeglImageOES = *const __GLeglImageOES;
 
 // compatible with OpenCL cl_context
 pub enum _cl_context {}
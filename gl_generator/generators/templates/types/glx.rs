@@ -1,12 +1,3 @@
-pub type XID = super::__gl_imports::raw::c_ulong;
-pub type Bool = super::__gl_imports::raw::c_int; // Not sure if this is correct...
-pub enum Display {}
-
-pub type Font = XID;
-pub type Pixmap = XID;
-pub enum Visual {} // TODO: not sure
-pub type VisualID = super::__gl_imports::raw::c_ulong; // TODO: not sure
-pub type Window = XID;
 pub type GLXFBConfigID = XID;
 pub type GLXFBConfig = *const super::__gl_imports::raw::c_void;
 pub type GLXContextID = XID;
@@ -24,20 +15,6 @@ pub type GLXFBConfigIDSGIX = XID;
 pub type GLXFBConfigSGIX = *const super::__gl_imports::raw::c_void;
 pub type GLXPbufferSGIX = XID;
 
-#[repr(C)]
-pub struct XVisualInfo {
-    pub visual: *mut Visual,
-    pub visualid: VisualID,
-    pub screen: super::__gl_imports::raw::c_int,
-    pub depth: super::__gl_imports::raw::c_int,
-    pub class: super::__gl_imports::raw::c_int,
-    pub red_mask: super::__gl_imports::raw::c_ulong,
-    pub green_mask: super::__gl_imports::raw::c_ulong,
-    pub blue_mask: super::__gl_imports::raw::c_ulong,
-    pub colormap_size: super::__gl_imports::raw::c_int,
-    pub bits_per_rgb: super::__gl_imports::raw::c_int,
-}
-
 #[repr(C)]
 pub struct GLXPbufferClobberEvent {
     pub event_type: super::__gl_imports::raw::c_int, // GLX_DAMAGED or GLX_SAVED
@@ -0,0 +1,35 @@
+pub enum __GLsync {}
+
+/// A sync object, which is an opaque pointer that can't be mixed up with other pointers.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GLsync(*const __GLsync);
+
+impl GLsync {
+    /// The null sync object, returned by `FenceSync` when it fails.
+    #[inline]
+    pub const fn null() -> GLsync {
+        GLsync({std}::ptr::null())
+    }
+
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    #[inline]
+    pub fn from_ptr(ptr: *const __GLsync) -> GLsync {
+        GLsync(ptr)
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const __GLsync {
+        self.0
+    }
+}
+
+// `GLsync` is passed to and returned from the commands in place of a pointer.
+const _: () = assert!(
+    super::__gl_imports::mem::size_of::<GLsync>()
+        == super::__gl_imports::mem::size_of::<*const __GLsync>()
+);
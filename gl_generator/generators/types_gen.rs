@@ -0,0 +1,94 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+
+/// Generates the `types` module and the enums only, without any command, for crates that share
+/// the types with a loader of their own.
+///
+/// `GeneratorOptions::std_path`, `GeneratorOptions::opaque_sync` and
+/// `GeneratorOptions::int_enums` are taken into account, and the other options are ignored.
+#[allow(missing_copy_implementations)]
+pub struct TypesGenerator;
+
+impl super::Generator for TypesGenerator {
+    fn name(&self) -> &'static str {
+        "TypesGenerator"
+    }
+
+    fn write(&self, registry: &Registry, dest: &mut dyn io::Write) -> io::Result<()> {
+        self.write_with_options(registry, &Default::default(), dest)
+    }
+
+    fn write_with_options(
+        &self,
+        registry: &Registry,
+        options: &super::GeneratorOptions,
+        mut dest: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let dest = &mut dest;
+
+        write_type_aliases(registry, options, dest)?;
+        write_enums(registry, dest)?;
+        if options.int_enums {
+            super::gen_int_enums(registry, dest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// The aliases refer to the standard library directly, as there is no `__gl_imports` module.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let mut types = Vec::new();
+    super::gen_types(registry.api, options, &mut types)?;
+    let types = String::from_utf8(types).unwrap();
+    let std = super::std_path(options);
+
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations, clippy::upper_case_acronyms)]
+            {types}
+        }}
+    "#,
+        types = types
+            .replace("super::__gl_imports::raw", &format!("{}::os::raw", std))
+            .replace("super::__gl_imports::mem", &format!("{}::mem", std)),
+    )
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
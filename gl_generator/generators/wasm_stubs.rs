@@ -0,0 +1,113 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The stubs replacing the commands on wasm when `GeneratorOptions::wasm_stubs` is set.
+
+use registry::Registry;
+use std::io;
+
+/// The predicate of the targets that the stubs are compiled for.
+const WASM_CFG: &str = "target_arch = \"wasm32\"";
+
+/// Generates the message of the panic of the commands on wasm.
+fn gen_message(registry: &Registry) -> String {
+    format!("{} bindings are not available on wasm", registry.api)
+}
+
+/// Generates the attribute compiling the `extern` block of the static generators for the other
+/// targets than wasm, or an empty string if `GeneratorOptions::wasm_stubs` isn't set.
+pub fn gen_extern_cfg(options: &super::GeneratorOptions) -> String {
+    if options.wasm_stubs {
+        format!("#[cfg(not({}))]", WASM_CFG)
+    } else {
+        String::new()
+    }
+}
+
+/// Generates the statement of `missing_fn_panic` that panics with the message of the stubs on
+/// wasm, where no command can be loaded, or an empty string if `GeneratorOptions::wasm_stubs`
+/// isn't set.
+pub fn gen_missing_fn_check(registry: &Registry, options: &super::GeneratorOptions) -> String {
+    if options.wasm_stubs {
+        format!(
+            "if cfg!({}) {{ panic!(\"{}\") }}",
+            WASM_CFG,
+            gen_message(registry)
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Writes the `wasm_stubs` module of the static generators, compiled only for wasm in place of
+/// their `extern` block, with a function of the same signature for each command that panics.
+///
+/// The functions are re-exported at the root of the bindings, publicly if `public` is set, in
+/// which case they are named like the functions of `StaticGenerator`.
+pub fn write_wasm_stubs<W>(
+    registry: &Registry,
+    options: &super::GeneratorOptions,
+    public: bool,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "#[cfg({cfg})]
+        #[allow(non_snake_case, unused_variables, unused_imports, dead_code)]
+        mod wasm_stubs {{
+            use super::{{__gl_imports, types}};
+
+            #[inline(never)]
+            fn unavailable(symbol: &str) -> ! {{
+                panic!(\"{message}: `{{}}` can't be called\", symbol)
+            }}",
+        cfg = WASM_CFG,
+        message = gen_message(registry),
+    )?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{alias}pub unsafe extern \"system\" fn {name}({params}) -> {return_suffix} {{ \
+                unavailable(\"{symbol}\") \
+            }}",
+            alias = if public {
+                super::gen_doc_alias(registry, options, &cmd.proto.ident)
+            } else {
+                String::new()
+            },
+            name = if public {
+                super::gen_cmd_name(registry, options, &cmd.proto.ident)
+            } else {
+                super::gen_ident(&cmd.proto.ident)
+            },
+            params = super::gen_parameter_list(cmd, true, true),
+            return_suffix = cmd.proto.ty,
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        #[cfg({cfg})]
+        {vis}use self::wasm_stubs::*;",
+        cfg = WASM_CFG,
+        vis = if public { "pub " } else { "" },
+    )
+}
@@ -0,0 +1,97 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `load_with_module` function emitted for the WGL bindings.
+
+use super::helpers::Dispatch;
+use registry::Registry;
+use std::io;
+
+/// Writes the `load_with_module` function, or method for the struct generators, which loads the
+/// commands introduced by the versions of the registry with a function looking them up in
+/// `opengl32.dll`, and the extension commands with `wglGetProcAddress`.
+pub fn write_wgl_loader<W>(registry: &Registry, dispatch: Dispatch, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// The symbols of the commands that `opengl32.dll` exports, in sorted order.
+        static WGL_MODULE_SYMBOLS: &[&str] = &["
+    )?;
+
+    for cmd in super::gen_core_cmds(registry) {
+        writeln!(dest, "\"{}\",", super::gen_symbol_name(registry.api, cmd))?;
+    }
+
+    writeln!(
+        dest,
+        "];
+
+        /// Looks `symbol` up with `module_fn` if `opengl32.dll` exports it, and with `loadfn`
+        /// otherwise. Some drivers make `wglGetProcAddress` return 1, 2, 3 or -1 instead of null
+        /// when it fails, which are returned as null.
+        #[allow(clippy::zero_ptr)]
+        fn wgl_load_symbol(
+            symbol: &'static str,
+            loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+            module_fn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+        ) -> *const __gl_imports::raw::c_void {{
+            if WGL_MODULE_SYMBOLS.binary_search(&symbol).is_ok() {{
+                return module_fn(symbol);
+            }}
+            let ptr = loadfn(symbol);
+            match ptr as isize {{
+                1 | 2 | 3 | -1 => 0 as *const _,
+                _ => ptr,
+            }}
+        }}"
+    )?;
+
+    let doc =
+        "/// Loads the WGL 1.0 commands with `module_fn`, which should call `GetProcAddress` on
+        /// `opengl32.dll`, and the extension commands with `loadfn`, which should call
+        /// `wglGetProcAddress` while a context is current. `wglGetProcAddress` only returns the
+        /// extension commands, and `opengl32.dll` only exports the others.";
+
+    match dispatch {
+        Dispatch::Free => writeln!(
+            dest,
+            "{doc}
+            #[allow(dead_code)]
+            pub fn load_with_module<F, M>(mut loadfn: F, mut module_fn: M)
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                      M: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+            {{
+                load_with(|symbol| wgl_load_symbol(symbol, &mut loadfn, &mut module_fn))
+            }}",
+            doc = doc,
+        ),
+        Dispatch::Method => writeln!(
+            dest,
+            "impl {api} {{
+                {doc}
+                #[allow(dead_code)]
+                pub fn load_with_module<F, M>(mut loadfn: F, mut module_fn: M) -> {api}
+                    where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                          M: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+                {{
+                    {api}::load_with(|symbol| wgl_load_symbol(symbol, &mut loadfn, &mut module_fn))
+                }}
+            }}",
+            api = super::gen_struct_name(registry.api),
+            doc = doc,
+        ),
+    }
+}
@@ -59,9 +59,16 @@
 //! - `NativePixmapType`
 //! - `NativeWindowType`
 //!
+//! `khronos_utime_nanoseconds_t` and `khronos_uint64_t` must be 64 bits and `khronos_ssize_t`
+//! pointer-sized, as in `khrplatform.h`; the bindings fail to compile otherwise.
+//!
 
 #[macro_use]
 extern crate log;
+#[cfg(feature = "proc-macro2")]
+extern crate proc_macro2;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate xml;
 
 #[cfg(feature = "unstable_generator_utils")]
@@ -73,9 +80,14 @@ mod registry;
 
 pub use generators::debug_struct_gen::DebugStructGenerator;
 pub use generators::global_gen::GlobalGenerator;
+pub use generators::null_gen::NullGenerator;
 pub use generators::static_gen::StaticGenerator;
 pub use generators::static_struct_gen::StaticStructGenerator;
 pub use generators::struct_gen::StructGenerator;
-pub use generators::Generator;
+pub use generators::types_gen::TypesGenerator;
+pub use generators::{
+    available_generators, generator_by_name, CommandGroup, Edition, Generator, GeneratorOptions,
+    Prelude,
+};
 
 pub use registry::*;
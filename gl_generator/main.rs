@@ -0,0 +1,484 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Writes the bindings of an API to stdout or to a file, for inspecting or regenerating them
+//! without a build script.
+
+extern crate gl_generator;
+
+use gl_generator::{
+    available_generators, generator_by_name, Api, Fallbacks, Generator, GeneratorOptions, Profile,
+    Registry,
+};
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: gl_generator [OPTIONS] <API> <VERSION>
+       gl_generator [OPTIONS] --api <API> --version <VERSION>
+
+Writes the bindings of API, one of gl, glx, wgl, egl, glcore, gles1, gles2 or
+glsc2, at VERSION, such as 4.6, to stdout, and prints the hash of the registry
+and the number of commands and enums to stderr.
+
+Options:
+    --api <API>              the API, instead of the first argument
+    --version <VERSION>      the version, instead of the second argument
+    --profile <PROFILE>      core (default) or compatibility
+    --generator <GENERATOR>  global (default), struct, debug-struct, static,
+                             static-struct, types or null
+    --option <KEY[=VALUE]>   set the generator option KEY, such as helpers or
+                             std_path=::reexports; a flag without a value is
+                             set to true; can be repeated
+    --ext, --extension <NAMES>
+                             also include the extensions NAMES, separated by
+                             commas, such as GL_KHR_debug or GL_ARB_*; `*` and
+                             `?` match any characters and any one character;
+                             can be repeated
+    --no-fallbacks           don't fall back to the aliases of the commands
+    -o, --output <FILE>      write the bindings to FILE instead of stdout
+    -h, --help               print this message";
+
+/// The parsed command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Args {
+    api: Api,
+    version: (u8, u8),
+    profile: Profile,
+    fallbacks: Fallbacks,
+    /// The extension names or patterns, as given.
+    extensions: Vec<String>,
+    /// The name of the generator, one of `available_generators`.
+    generator: String,
+    options: GeneratorOptions,
+    output: Option<String>,
+}
+
+fn parse_version(version: &str) -> Result<(u8, u8), String> {
+    let mut parts = version.splitn(2, '.');
+    match (
+        parts.next().and_then(|major| major.parse().ok()),
+        parts.next().and_then(|minor| minor.parse().ok()),
+    ) {
+        (Some(major), Some(minor)) => Ok((major, minor)),
+        _ => Err(format!(
+            "invalid version `{}`, expected MAJOR.MINOR",
+            version
+        )),
+    }
+}
+
+fn parse_profile(profile: &str) -> Result<Profile, String> {
+    match profile {
+        "core" => Ok(Profile::Core),
+        "compatibility" => Ok(Profile::Compatibility),
+        _ => Err(format!("unknown profile `{}`", profile)),
+    }
+}
+
+fn parse_generator(generator: &str) -> Result<String, String> {
+    match generator_by_name(generator) {
+        Some(_) => Ok(generator.replace('-', "_")),
+        None => Err(format!(
+            "unknown generator `{}`, expected one of {}",
+            generator,
+            available_generators().join(", ")
+        )),
+    }
+}
+
+/// Applies a `KEY=VALUE` or `KEY` argument of `--option` to `options`, a lone key setting a flag.
+fn parse_option(options: &mut GeneratorOptions, option: &str) -> Result<(), String> {
+    let mut parts = option.splitn(2, '=');
+    let key = parts.next().unwrap_or("");
+    options.set(key, parts.next().unwrap_or("true"))
+}
+
+/// Parses the arguments following the name of the program, returning `None` if the usage has
+/// been asked for.
+fn parse_args<I>(args: I) -> Result<Option<Args>, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut positional = Vec::new();
+    let mut api = None;
+    let mut version = None;
+    let mut profile = Profile::Core;
+    let mut fallbacks = Fallbacks::All;
+    let mut extensions = Vec::new();
+    let mut generator = "global".to_string();
+    let mut options = GeneratorOptions::default();
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("`{}` expects a value", name))
+        };
+        match &arg[..] {
+            "-h" | "--help" => return Ok(None),
+            "--api" => api = Some(value("--api")?.parse()?),
+            "--version" => version = Some(parse_version(&value("--version")?)?),
+            "--profile" => profile = parse_profile(&value("--profile")?)?,
+            "--generator" => generator = parse_generator(&value("--generator")?)?,
+            "--ext" | "--extension" => extensions.extend(
+                value(&arg)?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|ext| !ext.is_empty())
+                    .map(str::to_string),
+            ),
+            "--option" => parse_option(&mut options, &value("--option")?)?,
+            "--no-fallbacks" => fallbacks = Fallbacks::None,
+            "-o" | "--output" => output = Some(value("--output")?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    // The positional arguments fill in whichever of the API and the version wasn't given as an
+    // option, in that order.
+    let mut positional = positional.into_iter();
+    let api = match api {
+        Some(api) => api,
+        None => match positional.next() {
+            Some(api) => api.parse()?,
+            None => return Err("expected an API and a version".to_string()),
+        },
+    };
+    let version = match version {
+        Some(version) => version,
+        None => match positional.next() {
+            Some(version) => parse_version(&version)?,
+            None => return Err("expected a version".to_string()),
+        },
+    };
+    if let Some(arg) = positional.next() {
+        return Err(format!("unexpected argument `{}`", arg));
+    }
+
+    Ok(Some(Args {
+        api,
+        version,
+        profile,
+        fallbacks,
+        extensions,
+        generator,
+        options,
+        output,
+    }))
+}
+
+/// Returns whether `name` matches `pattern`, in which `*` matches any sequence of characters and
+/// `?` matches any single character.
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` in the pattern and of the character of the name it was
+    // tried against, to backtrack to when the rest of the pattern fails to match.
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(&'*') => {
+                star = Some((p, n));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            },
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Expands the extension names and patterns of the command line into the matching names of
+/// `available`, sorted by name and without duplicates.
+fn resolve_extensions(patterns: &[String], available: &[String]) -> Result<Vec<String>, String> {
+    let mut extensions = Vec::new();
+    for pattern in patterns {
+        let len = extensions.len();
+        extensions.extend(
+            available
+                .iter()
+                .filter(|name| matches_glob(pattern, name))
+                .cloned(),
+        );
+        if extensions.len() == len {
+            return Err(if pattern.contains(&['*', '?'][..]) {
+                format!("no extension matches `{}`", pattern)
+            } else {
+                format!("unknown extension `{}`", pattern)
+            });
+        }
+    }
+    extensions.sort();
+    extensions.dedup();
+    Ok(extensions)
+}
+
+/// Checks the version and the extensions of `args` against the registry of their API, and builds
+/// the registry they describe.
+fn create_registry(args: &Args) -> Result<Registry, String> {
+    if !Registry::available_versions(args.api).contains(&args.version) {
+        return Err(format!(
+            "{} has no version {}.{}",
+            args.api, args.version.0, args.version.1
+        ));
+    }
+
+    let available = Registry::available_extensions(args.api);
+    let extensions = resolve_extensions(&args.extensions, &available)
+        .map_err(|error| format!("{} for {}", error, args.api))?;
+
+    Ok(Registry::new(
+        args.api,
+        args.version,
+        args.profile,
+        args.fallbacks,
+        extensions.iter().map(|e| &e[..]).collect::<Vec<_>>(),
+    ))
+}
+
+/// The generator selected by `args`.
+fn generator(args: &Args) -> Box<dyn Generator> {
+    generator_by_name(&args.generator).expect("the generator was checked by `parse_args`")
+}
+
+/// Writes the bindings of `registry` with the generator and the options selected by `args`.
+fn write_bindings<W>(args: &Args, registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    registry.write_bindings_with_options(&*generator(args), &args.options, dest)
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        },
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            process::exit(2);
+        },
+    };
+
+    let registry = match create_registry(&args) {
+        Ok(registry) => registry,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(2);
+        },
+    };
+
+    eprintln!(
+        "registry {:016x}: {} commands, {} enums, {} extensions, written by {}",
+        registry.fingerprint(),
+        registry.cmds.len(),
+        registry.enums.len(),
+        registry.extensions.len(),
+        generator(&args).name()
+    );
+
+    let result = match args.output {
+        Some(ref path) => File::create(path).and_then(|mut file| {
+            write_bindings(&args, &registry, &mut file)?;
+            file.flush()
+        }),
+        None => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            write_bindings(&args, &registry, &mut stdout).and_then(|()| stdout.flush())
+        },
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_glob, parse_args, resolve_extensions, Args};
+    use gl_generator::{Api, Fallbacks, GeneratorOptions, Profile};
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(
+            parse(&["gl", "4.6"]),
+            Ok(Some(Args {
+                api: Api::Gl,
+                version: (4, 6),
+                profile: Profile::Core,
+                fallbacks: Fallbacks::All,
+                extensions: Vec::new(),
+                generator: "global".to_string(),
+                options: GeneratorOptions::default(),
+                output: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(
+            parse(&[
+                "--generator",
+                "debug-struct",
+                "gles2",
+                "--extension",
+                "GL_KHR_debug",
+                "3.2",
+                "--profile",
+                "compatibility",
+                "--extension",
+                "GL_OES_vertex_array_object",
+                "--no-fallbacks",
+                "--option",
+                "helpers",
+                "--option",
+                "std_path=::reexports",
+                "-o",
+                "gl.rs",
+            ]),
+            Ok(Some(Args {
+                api: Api::Gles2,
+                version: (3, 2),
+                profile: Profile::Compatibility,
+                fallbacks: Fallbacks::None,
+                extensions: vec![
+                    "GL_KHR_debug".to_string(),
+                    "GL_OES_vertex_array_object".to_string(),
+                ],
+                generator: "debug_struct".to_string(),
+                options: GeneratorOptions {
+                    helpers: true,
+                    std_path: Some("::reexports".to_string()),
+                    ..GeneratorOptions::default()
+                },
+                output: Some("gl.rs".to_string()),
+            }))
+        );
+        assert_eq!(parse(&["gl", "4.6", "--help"]), Ok(None));
+    }
+
+    #[test]
+    fn test_named_api_and_extension_lists() {
+        let args = parse(&[
+            "--ext",
+            "GL_KHR_debug, GL_ARB_*",
+            "--version",
+            "4.5",
+            "--extension",
+            "GL_EXT_texture_filter_anisotropic",
+            "--api",
+            "glcore",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(args.api, Api::GlCore);
+        assert_eq!(args.version, (4, 5));
+        assert_eq!(
+            args.extensions,
+            vec![
+                "GL_KHR_debug".to_string(),
+                "GL_ARB_*".to_string(),
+                "GL_EXT_texture_filter_anisotropic".to_string(),
+            ]
+        );
+
+        let args = parse(&["--version", "3.2", "gles2"]).unwrap().unwrap();
+        assert_eq!((args.api, args.version), (Api::Gles2, (3, 2)));
+        let args = parse(&["--api", "egl", "1.5"]).unwrap().unwrap();
+        assert_eq!((args.api, args.version), (Api::Egl, (1, 5)));
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("GL_KHR_debug", "GL_KHR_debug"));
+        assert!(!matches_glob("GL_KHR_debug", "GL_KHR_debug2"));
+        assert!(matches_glob("GL_ARB_*", "GL_ARB_sync"));
+        assert!(matches_glob("GL_*_debug", "GL_KHR_debug"));
+        assert!(matches_glob("*", ""));
+        assert!(matches_glob("GL_?HR_*", "GL_KHR_debug"));
+        assert!(!matches_glob("GL_?_debug", "GL_KHR_debug"));
+        assert!(matches_glob("*_a*b", "x_aab_ab"));
+        assert!(!matches_glob("*_a*b", "x_aab_a"));
+    }
+
+    #[test]
+    fn test_resolve_extensions() {
+        let available = vec![
+            "GL_ARB_sync".to_string(),
+            "GL_ARB_texture_storage".to_string(),
+            "GL_KHR_debug".to_string(),
+        ];
+        let resolve = |patterns: &[&str]| {
+            let patterns: Vec<_> = patterns.iter().map(|p| p.to_string()).collect();
+            resolve_extensions(&patterns, &available)
+        };
+
+        assert_eq!(
+            resolve(&["GL_KHR_debug", "GL_ARB_*", "GL_ARB_sync"]),
+            Ok(available.clone())
+        );
+        assert_eq!(resolve(&[]), Ok(Vec::new()));
+        assert!(resolve(&["GL_KHR_robustness"])
+            .unwrap_err()
+            .contains("unknown extension"));
+        assert!(resolve(&["GL_NV_*"])
+            .unwrap_err()
+            .contains("no extension matches"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["gl"]).is_err());
+        assert!(parse(&["gl", "4.6", "extra"]).is_err());
+        assert!(parse(&["vulkan", "1.0"]).is_err());
+        assert!(parse(&["gl", "4"]).is_err());
+        assert!(parse(&["gl", "four.six"]).is_err());
+        assert!(parse(&["gl", "4.6", "--profile", "es"]).is_err());
+        assert!(parse(&["gl", "4.6", "--generator"]).is_err());
+        assert!(parse(&["gl", "4.6", "--verbose"]).is_err());
+        assert!(parse(&["gl", "4.6", "--generator", "debug"]).is_err());
+        assert!(parse(&["gl", "4.6", "--option", "helpers=yes"]).is_err());
+        assert!(parse(&["gl", "4.6", "--option", "verbose"]).is_err());
+        assert!(parse(&["--api", "gl"]).is_err());
+        assert!(parse(&["--api", "gl", "--version", "4.6", "gles2"]).is_err());
+    }
+}
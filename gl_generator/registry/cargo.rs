@@ -0,0 +1,114 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Cargo features that bindings generated with `GeneratorOptions::extension_features` are
+//! gated on, for build scripts that keep them in sync with the registry.
+
+use std::io;
+
+use registry::Registry;
+
+impl Registry {
+    /// Returns the names of the Cargo features that the bindings generated from this registry with
+    /// `GeneratorOptions::extension_features` can be gated on, which are the names of its
+    /// extensions, such as `GL_ARB_debug_output`, sorted.
+    pub fn extension_features(&self) -> Vec<&str> {
+        self.extensions.iter().map(|ext| &ext.name[..]).collect()
+    }
+
+    /// Writes the `cargo:rustc-check-cfg` instruction declaring every feature of
+    /// `extension_features` as an expected value of the `feature` cfg, so that `rustc` doesn't
+    /// warn about the ones that `Cargo.toml` doesn't declare. Meant to be printed to the standard
+    /// output of a build script.
+    pub fn write_cargo_check_cfg(&self, dest: &mut dyn io::Write) -> io::Result<()> {
+        let values: Vec<_> = self
+            .extension_features()
+            .iter()
+            .map(|feature| format!("\"{}\"", feature))
+            .collect();
+        writeln!(
+            dest,
+            "cargo:rustc-check-cfg=cfg(feature, values({}))",
+            values.join(", ")
+        )
+    }
+
+    /// Writes a `cargo:rustc-cfg` instruction enabling each feature of `extension_features`, so
+    /// that all the items of the bindings are compiled whatever the features selected by Cargo.
+    /// Meant to be printed to the standard output of a build script.
+    pub fn write_cargo_enable_features(&self, dest: &mut dyn io::Write) -> io::Result<()> {
+        for feature in self.extension_features() {
+            writeln!(dest, "cargo:rustc-cfg=feature=\"{}\"", feature)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a `[features]` table declaring each feature of `extension_features` without
+    /// dependencies, such as `GL_ARB_debug_output = []`, to be copied into `Cargo.toml`.
+    pub fn write_features_toml(&self, dest: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(dest, "[features]")?;
+        for feature in self.extension_features() {
+            writeln!(dest, "{} = []", feature)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use registry::{Api, Fallbacks, Profile, Registry};
+
+    fn registry() -> Registry {
+        Registry::new(
+            Api::Gl,
+            (1, 1),
+            Profile::Core,
+            Fallbacks::None,
+            ["GL_KHR_debug", "GL_ARB_vertex_array_object"],
+        )
+    }
+
+    #[test]
+    fn test_extension_features() {
+        assert_eq!(
+            registry().extension_features(),
+            ["GL_ARB_vertex_array_object", "GL_KHR_debug"]
+        );
+    }
+
+    #[test]
+    fn test_write_cargo_metadata() {
+        let registry = registry();
+
+        let mut dest = Vec::new();
+        registry.write_cargo_check_cfg(&mut dest).unwrap();
+        registry.write_cargo_enable_features(&mut dest).unwrap();
+        assert_eq!(
+            String::from_utf8(dest).unwrap(),
+            "cargo:rustc-check-cfg=cfg(feature, values(\"GL_ARB_vertex_array_object\", \"GL_KHR_debug\"))\n\
+             cargo:rustc-cfg=feature=\"GL_ARB_vertex_array_object\"\n\
+             cargo:rustc-cfg=feature=\"GL_KHR_debug\"\n"
+        );
+    }
+
+    #[test]
+    fn test_write_features_toml() {
+        let mut dest = Vec::new();
+        registry().write_features_toml(&mut dest).unwrap();
+        assert_eq!(
+            String::from_utf8(dest).unwrap(),
+            "[features]\nGL_ARB_vertex_array_object = []\nGL_KHR_debug = []\n"
+        );
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A comparison of two registries, for tracking how the API changes between versions.
+
+use std::collections::BTreeMap;
+
+use registry::{Cmd, Enum, Registry};
+
+/// The differences between two registries, as returned by `Registry::diff`.
+///
+/// Every list is sorted by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegistryDiff {
+    /// The commands that are only in the other registry.
+    pub added_cmds: Vec<Cmd>,
+    /// The commands that are only in this registry.
+    pub removed_cmds: Vec<Cmd>,
+    /// The commands whose return or parameter types differ, as `(old, new)` pairs.
+    pub changed_cmds: Vec<(Cmd, Cmd)>,
+    /// The enums that are only in the other registry.
+    pub added_enums: Vec<Enum>,
+    /// The enums that are only in this registry.
+    pub removed_enums: Vec<Enum>,
+    /// The enums whose value or type differ, as `(old, new)` pairs.
+    pub changed_enums: Vec<(Enum, Enum)>,
+}
+
+impl RegistryDiff {
+    /// Returns `true` if the registries have the same commands and enums.
+    pub fn is_empty(&self) -> bool {
+        self.added_cmds.is_empty()
+            && self.removed_cmds.is_empty()
+            && self.changed_cmds.is_empty()
+            && self.added_enums.is_empty()
+            && self.removed_enums.is_empty()
+            && self.changed_enums.is_empty()
+    }
+}
+
+impl Registry {
+    /// Compares the commands and enums of this registry with those of `other`, which is treated
+    /// as the newer of the two.
+    ///
+    /// Commands and enums are matched by name. A command has changed if its return type or the
+    /// type of one of its parameters differs, while renaming a parameter is not a change. An enum
+    /// has changed if its value or type differs.
+    pub fn diff(&self, other: &Registry) -> RegistryDiff {
+        let old_cmds: BTreeMap<_, _> = self
+            .cmds
+            .iter()
+            .map(|cmd| (&cmd.proto.ident[..], cmd))
+            .collect();
+        let new_cmds: BTreeMap<_, _> = other
+            .cmds
+            .iter()
+            .map(|cmd| (&cmd.proto.ident[..], cmd))
+            .collect();
+        let old_enums: BTreeMap<_, _> =
+            self.enums.iter().map(|enm| (&enm.ident[..], enm)).collect();
+        let new_enums: BTreeMap<_, _> = other
+            .enums
+            .iter()
+            .map(|enm| (&enm.ident[..], enm))
+            .collect();
+
+        let mut diff = RegistryDiff::default();
+        for (name, &new) in &new_cmds {
+            match old_cmds.get(name) {
+                Some(&old) if !same_signature(old, new) => {
+                    diff.changed_cmds.push((old.clone(), new.clone()))
+                },
+                Some(_) => {},
+                None => diff.added_cmds.push(new.clone()),
+            }
+        }
+        for (name, &old) in &old_cmds {
+            if !new_cmds.contains_key(name) {
+                diff.removed_cmds.push(old.clone());
+            }
+        }
+        for (name, &new) in &new_enums {
+            match old_enums.get(name) {
+                Some(&old) if old.value != new.value || old.ty != new.ty => {
+                    diff.changed_enums.push((old.clone(), new.clone()))
+                },
+                Some(_) => {},
+                None => diff.added_enums.push(new.clone()),
+            }
+        }
+        for (name, &old) in &old_enums {
+            if !new_enums.contains_key(name) {
+                diff.removed_enums.push(old.clone());
+            }
+        }
+        diff
+    }
+}
+
+/// Returns `true` if `a` and `b` have the same return type and parameter types.
+fn same_signature(a: &Cmd, b: &Cmd) -> bool {
+    a.proto.ty == b.proto.ty
+        && a.proto.c_ty == b.proto.c_ty
+        && a.params.len() == b.params.len()
+        && a.params
+            .iter()
+            .zip(&b.params)
+            .all(|(a, b)| a.ty == b.ty && a.c_ty == b.c_ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    use registry::{Api, Binding, Cmd, Enum, Fallbacks, Profile, Registry};
+
+    fn binding(ident: &str, ty: &'static str) -> Binding {
+        Binding {
+            ident: ident.to_string(),
+            ty: Cow::Borrowed(ty),
+            c_ty: String::new(),
+            group: None,
+            class: None,
+        }
+    }
+
+    fn cmd(name: &str, params: Vec<Binding>) -> Cmd {
+        Cmd {
+            proto: binding(name, "()"),
+            params,
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        }
+    }
+
+    fn enm(name: &str, value: &str) -> Enum {
+        Enum {
+            ident: name.to_string(),
+            value: value.to_string(),
+            cast: false,
+            alias: None,
+            ty: Cow::Borrowed("GLenum"),
+        }
+    }
+
+    fn registry(cmds: Vec<Cmd>, enums: Vec<Enum>) -> Registry {
+        Registry {
+            api: Api::Gl,
+            version: (4, 6),
+            profile: Profile::Core,
+            extensions: Vec::new(),
+            versions: Vec::new(),
+            enums: enums.into_iter().collect(),
+            cmds: cmds.into_iter().collect(),
+            aliases: BTreeMap::new(),
+            groups: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = registry(
+            vec![
+                cmd("Clear", vec![binding("mask", "types::GLbitfield")]),
+                cmd("Flush", vec![]),
+                cmd("Viewport", vec![binding("x", "types::GLint")]),
+            ],
+            vec![enm("RED", "0x1903"), enm("GREEN", "0x1904")],
+        );
+        let new = registry(
+            vec![
+                cmd("Clear", vec![binding("bits", "types::GLbitfield")]),
+                cmd("Finish", vec![]),
+                cmd("Viewport", vec![binding("x", "types::GLsizei")]),
+            ],
+            vec![
+                enm("RED", "0x1903"),
+                enm("GREEN", "0x1905"),
+                enm("BLUE", "0x1905"),
+            ],
+        );
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.added_cmds, [cmd("Finish", vec![])]);
+        assert_eq!(diff.removed_cmds, [cmd("Flush", vec![])]);
+        assert_eq!(diff.changed_cmds.len(), 1);
+        assert_eq!(diff.changed_cmds[0].0.proto.ident, "Viewport");
+        assert_eq!(diff.changed_cmds[0].1.params[0].ty, "types::GLsizei");
+        assert_eq!(diff.added_enums, [enm("BLUE", "0x1905")]);
+        assert!(diff.removed_enums.is_empty());
+        assert_eq!(
+            diff.changed_enums,
+            [(enm("GREEN", "0x1904"), enm("GREEN", "0x1905"))]
+        );
+
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(new.diff(&old).removed_cmds, [cmd("Finish", vec![])]);
+    }
+
+    #[test]
+    fn test_diff_between_versions() {
+        let gl45 = Registry::new(Api::Gl, (4, 5), Profile::Core, Fallbacks::All, []);
+        let gl46 = Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, []);
+
+        let diff = gl45.diff(&gl46);
+        assert!(diff
+            .added_cmds
+            .iter()
+            .any(|cmd| cmd.proto.ident == "SpecializeShader"));
+        assert!(diff
+            .added_enums
+            .iter()
+            .any(|enm| enm.ident == "SPIR_V_BINARY"));
+        assert!(diff.removed_cmds.is_empty());
+        assert!(diff.changed_cmds.is_empty());
+    }
+}
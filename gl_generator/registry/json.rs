@@ -0,0 +1,273 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A machine readable summary of a registry, for tooling that wants to know what the generated
+//! bindings contain without parsing Rust.
+
+use std::io;
+
+use registry::{Binding, Registry};
+
+impl Registry {
+    /// Writes a JSON summary of the registry to `dest`.
+    ///
+    /// The output is a single object with the following keys, always in this order:
+    ///
+    /// - `"api"`: the API name, e.g. `"gl"`.
+    /// - `"version"`: the requested version, e.g. `"4.5"`.
+    /// - `"profile"`: `"core"` or `"compatibility"`.
+    /// - `"extensions"`: the requested extensions found in the registry, sorted by name.
+    /// - `"enums"`: the constants, sorted by name, as `{"name", "value", "type"}` objects. `value`
+    ///   is a string holding the value as written in the generated bindings.
+    /// - `"commands"`: the commands, sorted by name, as `{"name", "return", "params"}` objects.
+    ///   `params` is an array of `{"name", "type"}` objects. Types are the C types of the
+    ///   registry, e.g. `"const GLchar *"`.
+    /// - `"aliases"`: an object mapping command names to the names of their aliases.
+    ///
+    /// Names are given without the API prefix, as they appear in the generated bindings.
+    pub fn write_json_summary(&self, dest: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(dest, "{{")?;
+        writeln!(dest, "  \"api\": {},", string(&self.api.to_string()))?;
+        writeln!(
+            dest,
+            "  \"version\": {},",
+            string(&format!("{}.{}", self.version.0, self.version.1))
+        )?;
+        writeln!(
+            dest,
+            "  \"profile\": {},",
+            string(&self.profile.to_string())
+        )?;
+
+        let extensions: Vec<_> = self
+            .extensions
+            .iter()
+            .map(|ext| string(&ext.name))
+            .collect();
+        writeln!(dest, "  \"extensions\": [{}],", extensions.join(", "))?;
+
+        writeln!(dest, "  \"enums\": [")?;
+        let mut enums = self.enums.iter().peekable();
+        while let Some(enm) = enums.next() {
+            writeln!(
+                dest,
+                "    {{\"name\": {}, \"value\": {}, \"type\": {}}}{}",
+                string(&enm.ident),
+                string(&enm.value),
+                string(&enm.ty),
+                separator(enums.peek().is_some()),
+            )?;
+        }
+        writeln!(dest, "  ],")?;
+
+        writeln!(dest, "  \"commands\": [")?;
+        let mut cmds = self.cmds.iter().peekable();
+        while let Some(cmd) = cmds.next() {
+            let params: Vec<_> = cmd.params.iter().map(param).collect();
+            writeln!(
+                dest,
+                "    {{\"name\": {}, \"return\": {}, \"params\": [{}]}}{}",
+                string(&cmd.proto.ident),
+                string(&cmd.proto.c_ty),
+                params.join(", "),
+                separator(cmds.peek().is_some()),
+            )?;
+        }
+        writeln!(dest, "  ],")?;
+
+        writeln!(dest, "  \"aliases\": {{")?;
+        let mut aliases = self.aliases.iter().peekable();
+        while let Some((name, alternatives)) = aliases.next() {
+            let alternatives: Vec<_> = alternatives.iter().map(|alt| string(alt)).collect();
+            writeln!(
+                dest,
+                "    {}: [{}]{}",
+                string(name),
+                alternatives.join(", "),
+                separator(aliases.peek().is_some()),
+            )?;
+        }
+        writeln!(dest, "  }}")?;
+        writeln!(dest, "}}")
+    }
+
+    /// Returns a 64-bit FNV-1a hash of the JSON summary of the registry.
+    ///
+    /// The hash only depends on the contents of the registry, so it is stable across runs and
+    /// platforms and changes whenever the generated bindings would expose a different API.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+        self.write_json_summary(&mut hasher).unwrap();
+        hasher.0
+    }
+}
+
+/// Feeds everything written to it into a 64-bit FNV-1a hash.
+struct Fnv1a(u64);
+
+impl io::Write for Fnv1a {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn param(binding: &Binding) -> String {
+    format!(
+        "{{\"name\": {}, \"type\": {}}}",
+        string(&binding.ident),
+        string(&binding.c_ty)
+    )
+}
+
+fn separator(more: bool) -> &'static str {
+    if more {
+        ","
+    } else {
+        ""
+    }
+}
+
+/// Quotes and escapes `src` as a JSON string.
+fn string(src: &str) -> String {
+    let mut out = String::with_capacity(src.len() + 2);
+    out.push('"');
+    for c in src.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use registry::{Api, Binding, Cmd, Enum, Extension, Profile, Registry};
+
+    fn binding(ident: &str, c_ty: &str) -> Binding {
+        Binding {
+            ident: ident.to_string(),
+            ty: Cow::Borrowed("()"),
+            c_ty: c_ty.to_string(),
+            group: None,
+            class: None,
+        }
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(super::string("GLenum"), "\"GLenum\"");
+        assert_eq!(super::string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(super::string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        use std::io::Write;
+
+        let mut hasher = super::Fnv1a(0xcbf2_9ce4_8422_2325);
+        hasher.write_all(b"a").unwrap();
+        assert_eq!(hasher.0, 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_write_json_summary() {
+        let extensions = vec![Extension {
+            name: "GL_EXT_foo".to_string(),
+            comment: None,
+            cmds: BTreeSet::new(),
+            enums: BTreeSet::new(),
+        }];
+
+        let mut enums = BTreeSet::new();
+        enums.insert(Enum {
+            ident: "TEXTURE0".to_string(),
+            value: "0x84C0".to_string(),
+            cast: false,
+            alias: None,
+            ty: Cow::Borrowed("GLenum"),
+        });
+
+        let mut cmds = BTreeSet::new();
+        cmds.insert(Cmd {
+            proto: binding("GetString", "const GLubyte *"),
+            params: vec![binding("name", "GLenum")],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        cmds.insert(Cmd {
+            proto: binding("Flush", "void"),
+            params: vec![],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+
+        let mut aliases = BTreeMap::new();
+        aliases.insert("Flush".to_string(), vec!["FlushEXT".to_string()]);
+
+        let registry = Registry {
+            api: Api::Gl,
+            version: (4, 5),
+            profile: Profile::Core,
+            extensions,
+            versions: Vec::new(),
+            enums,
+            cmds,
+            aliases,
+            groups: BTreeMap::new(),
+        };
+
+        let mut dest = Vec::new();
+        registry.write_json_summary(&mut dest).unwrap();
+        assert_eq!(
+            String::from_utf8(dest).unwrap(),
+            r#"{
+  "api": "gl",
+  "version": "4.5",
+  "profile": "core",
+  "extensions": ["GL_EXT_foo"],
+  "enums": [
+    {"name": "TEXTURE0", "value": "0x84C0", "type": "GLenum"}
+  ],
+  "commands": [
+    {"name": "Flush", "return": "void", "params": []},
+    {"name": "GetString", "return": "const GLubyte *", "params": [{"name": "name", "type": "GLenum"}]}
+  ],
+  "aliases": {
+    "Flush": ["FlushEXT"]
+  }
+}
+"#
+        );
+    }
+}
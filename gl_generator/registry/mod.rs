@@ -20,12 +20,22 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::io;
 use std::ops::{Add, AddAssign};
+use std::str::FromStr;
 
-use Generator;
+use {Generator, GeneratorOptions};
 
+mod cargo;
+mod diff;
+mod json;
 mod parse;
+mod snapshot;
+#[cfg(feature = "proc-macro2")]
+mod tokens;
 
-const BYTE_ORDER_MARK: &'static [u8] = &[0xef, 0xbb, 0xbf];
+pub use self::diff::RegistryDiff;
+pub use self::snapshot::{BindingsDiff, LineChange};
+
+const BYTE_ORDER_MARK: &[u8] = &[0xef, 0xbb, 0xbf];
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Api {
@@ -54,6 +64,28 @@ impl fmt::Display for Api {
     }
 }
 
+impl FromStr for Api {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Api, String> {
+        match s {
+            "gl" => Ok(Api::Gl),
+            "glx" => Ok(Api::Glx),
+            "wgl" => Ok(Api::Wgl),
+            "egl" => Ok(Api::Egl),
+            "glcore" => Ok(Api::GlCore),
+            "gles1" => Ok(Api::Gles1),
+            "gles2" => Ok(Api::Gles2),
+            "glsc2" => Ok(Api::Glsc2),
+            _ => Err(format!(
+                "unknown API `{}`, expected `gl`, `glx`, `wgl`, `egl`, `glcore`, `gles1`, \
+                 `gles2` or `glsc2`",
+                s
+            )),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Fallbacks {
     All,
@@ -66,6 +98,15 @@ pub enum Profile {
     Compatibility,
 }
 
+impl fmt::Display for Profile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Profile::Core => write!(fmt, "core"),
+            Profile::Compatibility => write!(fmt, "compatibility"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Enum {
     pub ident: String,
@@ -85,7 +126,12 @@ impl Hash for Enum {
 pub struct Binding {
     pub ident: String,
     pub ty: Cow<'static, str>,
+    /// The type as written in the C prototype of the registry, e.g. `const GLchar *`.
+    pub c_ty: String,
     pub group: Option<String>,
+    /// The kind of object that a `GLuint` names, from the `class` attribute of the registry,
+    /// e.g. `buffer` or `vertex array`.
+    pub class: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -116,9 +162,43 @@ pub struct GlxOpcode {
     pub name: Option<String>,
 }
 
+/// A version of the API included in the registry, along with what it introduced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    /// The name of the feature in the registry, e.g. `GL_VERSION_4_3`.
+    pub name: String,
+    pub number: (u8, u8),
+    /// The commands introduced by this version that are part of the registry.
+    pub cmds: BTreeSet<String>,
+    /// The enums introduced by this version that are part of the registry.
+    pub enums: BTreeSet<String>,
+    /// The commands of the registry that this version removes from the core profile, which are
+    /// only part of the registry for the compatibility profile.
+    pub removed_cmds: BTreeSet<String>,
+}
+
+/// An extension included in the registry, along with what it introduced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Extension {
+    /// The name of the extension, e.g. `GL_ARB_debug_output`.
+    pub name: String,
+    /// The `comment` attribute of the extension in the registry, if any.
+    pub comment: Option<String>,
+    /// The commands required by this extension that are part of the registry.
+    pub cmds: BTreeSet<String>,
+    /// The enums required by this extension that are part of the registry.
+    pub enums: BTreeSet<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Registry {
     pub api: Api,
+    pub version: (u8, u8),
+    pub profile: Profile,
+    /// The requested extensions that were found in the registry, sorted by name.
+    pub extensions: Vec<Extension>,
+    /// The versions up to and including `version`, in ascending order.
+    pub versions: Vec<Version>,
     pub enums: BTreeSet<Enum>,
     pub cmds: BTreeSet<Cmd>,
     pub aliases: BTreeMap<String, Vec<String>>,
@@ -147,20 +227,7 @@ impl Registry {
             profile,
         };
 
-        let src_raw = match api {
-            Api::Gl | Api::GlCore | Api::Gles1 | Api::Gles2 | Api::Glsc2 => khronos_api::GL_XML,
-            Api::Glx => khronos_api::GLX_XML,
-            Api::Wgl => khronos_api::WGL_XML,
-            Api::Egl => khronos_api::EGL_XML,
-        };
-
-        let src = if &src_raw[..BYTE_ORDER_MARK.len()] == BYTE_ORDER_MARK {
-            &src_raw[BYTE_ORDER_MARK.len()..]
-        } else {
-            src_raw
-        };
-
-        let mut registry = parse::from_xml(src, &filter, true);
+        let mut registry = parse::from_xml(registry_xml(api), &filter, true);
         if filter.extensions.iter().any(|e| e.starts_with("GL_ANGLE_")) {
             registry += parse::from_xml(khronos_api::GL_ANGLE_EXT_XML, &filter, false);
         }
@@ -174,12 +241,201 @@ impl Registry {
         registry
     }
 
+    /// Returns the versions of `api` in the registry, in ascending order. Passing another version
+    /// to `Registry::new` panics.
+    pub fn available_versions(api: Api) -> Vec<(u8, u8)> {
+        let (mut versions, _) = parse::scan_xml(registry_xml(api), api);
+        versions.sort();
+        versions
+    }
+
+    /// Returns the names of the extensions of the registry that support `api`, sorted by name.
+    /// Passing an extension that doesn't support `api` to `Registry::new` panics.
+    pub fn available_extensions(api: Api) -> Vec<String> {
+        let (_, mut extensions) = parse::scan_xml(registry_xml(api), api);
+        let angle_xml = match api {
+            Api::Egl => khronos_api::EGL_ANGLE_EXT_XML,
+            Api::Glx | Api::Wgl => &[],
+            _ => khronos_api::GL_ANGLE_EXT_XML,
+        };
+        if !angle_xml.is_empty() {
+            extensions.extend(parse::scan_xml(angle_xml, api).1);
+        }
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+
+    /// Writes the bindings generated by `generator` to `output`.
+    ///
+    /// Fails with `io::ErrorKind::InvalidData` if a command, parameter or enum of the registry
+    /// can't be given a valid Rust identifier.
     pub fn write_bindings<W, G>(&self, generator: G, output: &mut W) -> io::Result<()>
     where
         G: Generator,
         W: io::Write,
     {
-        generator.write(&self, output)
+        ::generators::check_idents(self)?;
+        generator.write(self, output)
+    }
+
+    /// Like `write_bindings`, but enables the optional features selected in `options`.
+    pub fn write_bindings_with_options<W, G>(
+        &self,
+        generator: G,
+        options: &GeneratorOptions,
+        output: &mut W,
+    ) -> io::Result<()>
+    where
+        G: Generator,
+        W: io::Write,
+    {
+        ::generators::check_idents(self)?;
+        if options.stable_output {
+            let mut dest = Vec::new();
+            self.write_bindings_unformatted(generator, options, &mut dest)?;
+            let src = String::from_utf8(dest)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            output.write_all(snapshot::canonicalize(&src).as_bytes())
+        } else {
+            self.write_bindings_unformatted(generator, options, output)
+        }
+    }
+
+    /// Writes the bindings as `write_bindings_with_options` does, ignoring
+    /// `GeneratorOptions::stable_output`.
+    fn write_bindings_unformatted<W, G>(
+        &self,
+        generator: G,
+        options: &GeneratorOptions,
+        output: &mut W,
+    ) -> io::Result<()>
+    where
+        G: Generator,
+        W: io::Write,
+    {
+        if options.skip_extensions {
+            generator.write_with_options(&self.without_extensions(), options, output)
+        } else {
+            generator.write_with_options(self, options, output)
+        }
+    }
+
+    /// Returns a copy of the registry without the extensions, and without the commands and enums
+    /// that are not introduced by any of the versions.
+    fn without_extensions(&self) -> Registry {
+        let cmds: BTreeSet<_> = self
+            .cmds
+            .iter()
+            .filter(|cmd| {
+                self.versions
+                    .iter()
+                    .any(|version| version.cmds.contains(&cmd.proto.ident))
+            })
+            .cloned()
+            .collect();
+        let enums = self
+            .enums
+            .iter()
+            .filter(|enm| {
+                self.versions
+                    .iter()
+                    .any(|version| version.enums.contains(&enm.ident))
+            })
+            .cloned()
+            .collect();
+        let aliases = self
+            .aliases
+            .iter()
+            .filter(|&(cmd, _)| cmds.iter().any(|c| &c.proto.ident == cmd))
+            .map(|(cmd, aliases)| (cmd.clone(), aliases.clone()))
+            .collect();
+
+        Registry {
+            api: self.api,
+            version: self.version,
+            profile: self.profile,
+            extensions: Vec::new(),
+            versions: self.versions.clone(),
+            enums,
+            cmds,
+            aliases,
+            groups: self.groups.clone(),
+        }
+    }
+
+    /// Removes the commands and enums that are not reachable from `used`, so that only what an
+    /// application references is generated.
+    ///
+    /// `used` names commands as in the bindings (`"Clear"`) or by their symbol (`"glClear"`), and
+    /// enums as in the bindings (`"COLOR_BUFFER_BIT"`). A command keeps its aliases, which are
+    /// loaded as fallbacks, and the command it is an alias of, and brings in the enums of the
+    /// groups of its parameters. Names that are not part of the registry are ignored. The type
+    /// aliases are always generated, as they are shared by the commands.
+    pub fn retain_reachable(&mut self, used: &[&str]) {
+        let api = self.api;
+        let mut cmds: BTreeSet<String> = self
+            .cmds
+            .iter()
+            .map(|cmd| &cmd.proto.ident)
+            .filter(|ident| {
+                used.iter().any(|&name| {
+                    name == ident.as_str() || name == ::generators::gen_symbol_name(api, ident)
+                })
+            })
+            .cloned()
+            .collect();
+
+        // Follow the aliases until no new command is found.
+        loop {
+            let found: Vec<String> = self
+                .cmds
+                .iter()
+                .filter(|cmd| cmds.contains(&cmd.proto.ident))
+                .filter_map(|cmd| cmd.alias.clone())
+                .chain(
+                    cmds.iter()
+                        .filter_map(|cmd| self.aliases.get(cmd))
+                        .flat_map(|aliases| aliases.iter().cloned()),
+                )
+                .filter(|ident| {
+                    !cmds.contains(ident) && self.cmds.iter().any(|cmd| &cmd.proto.ident == ident)
+                })
+                .collect();
+            if found.is_empty() {
+                break;
+            }
+            cmds.extend(found);
+        }
+
+        let mut enums: BTreeSet<String> = used.iter().map(|&name| name.to_string()).collect();
+        for cmd in self
+            .cmds
+            .iter()
+            .filter(|cmd| cmds.contains(&cmd.proto.ident))
+        {
+            for param in &cmd.params {
+                if let Some(group) = param
+                    .group
+                    .as_ref()
+                    .and_then(|group| self.groups.get(group))
+                {
+                    enums.extend(group.enums.iter().cloned());
+                }
+            }
+        }
+
+        self.cmds.retain(|cmd| cmds.contains(&cmd.proto.ident));
+        self.enums.retain(|enm| enums.contains(&enm.ident));
+        self.aliases.retain(|cmd, _| cmds.contains(cmd));
+        for version in &mut self.versions {
+            version.cmds.retain(|cmd| cmds.contains(cmd));
+            version.enums.retain(|enm| enums.contains(enm));
+        }
+        for extension in &mut self.extensions {
+            extension.cmds.retain(|cmd| cmds.contains(cmd));
+            extension.enums.retain(|enm| enums.contains(enm));
+        }
     }
 
     /// Returns a set of all the types used in the supplied registry. This is useful
@@ -207,8 +463,115 @@ impl Add for Registry {
 
 impl AddAssign for Registry {
     fn add_assign(&mut self, other: Self) {
+        for extension in other.extensions {
+            match self
+                .extensions
+                .iter()
+                .position(|e| e.name == extension.name)
+            {
+                Some(i) => {
+                    self.extensions[i].cmds.extend(extension.cmds);
+                    self.extensions[i].enums.extend(extension.enums);
+                },
+                None => self.extensions.push(extension),
+            }
+        }
+        self.extensions.sort_by(|a, b| a.name.cmp(&b.name));
+        for version in other.versions {
+            match self.versions.iter().position(|v| v.name == version.name) {
+                Some(i) => {
+                    self.versions[i].cmds.extend(version.cmds);
+                    self.versions[i].enums.extend(version.enums);
+                    self.versions[i].removed_cmds.extend(version.removed_cmds);
+                },
+                None => self.versions.push(version),
+            }
+        }
         self.enums.extend(other.enums);
         self.cmds.extend(other.cmds);
         self.aliases.extend(other.aliases);
     }
 }
+
+/// Returns the XML registry that describes `api`, without its byte order mark.
+fn registry_xml(api: Api) -> &'static [u8] {
+    let src = match api {
+        Api::Gl | Api::GlCore | Api::Gles1 | Api::Gles2 | Api::Glsc2 => khronos_api::GL_XML,
+        Api::Glx => khronos_api::GLX_XML,
+        Api::Wgl => khronos_api::WGL_XML,
+        Api::Egl => khronos_api::EGL_XML,
+    };
+
+    if &src[..BYTE_ORDER_MARK.len()] == BYTE_ORDER_MARK {
+        &src[BYTE_ORDER_MARK.len()..]
+    } else {
+        src
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use registry::{Api, Fallbacks, Profile, Registry};
+
+    #[test]
+    fn test_retain_reachable() {
+        let mut registry = Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, []);
+        registry.retain_reachable(&["Clear", "glGenFramebuffers", "TRIANGLES", "NotACommand"]);
+
+        let cmds: Vec<_> = registry
+            .cmds
+            .iter()
+            .map(|cmd| &cmd.proto.ident[..])
+            .collect();
+        assert_eq!(cmds, ["Clear", "GenFramebuffers"]);
+        assert_eq!(
+            registry.aliases.get("GenFramebuffers"),
+            Some(&vec!["GenFramebuffersEXT".to_string()])
+        );
+
+        // `TRIANGLES` is named, and `COLOR_BUFFER_BIT` is in the group of the `mask` of `Clear`.
+        let enums: Vec<_> = registry.enums.iter().map(|enm| &enm.ident[..]).collect();
+        assert!(enums.contains(&"TRIANGLES"));
+        assert!(enums.contains(&"COLOR_BUFFER_BIT"));
+        assert!(!enums.contains(&"TEXTURE_2D"));
+
+        assert!(registry.versions[0].cmds.contains("Clear"));
+        assert!(registry
+            .versions
+            .iter()
+            .all(|version| version.cmds.iter().all(|cmd| cmds.contains(&&cmd[..]))));
+    }
+
+    #[test]
+    fn test_api_from_str() {
+        for &api in &[
+            Api::Gl,
+            Api::Glx,
+            Api::Wgl,
+            Api::Egl,
+            Api::GlCore,
+            Api::Gles1,
+            Api::Gles2,
+            Api::Glsc2,
+        ] {
+            assert_eq!(api.to_string().parse::<Api>(), Ok(api));
+        }
+        assert!("vulkan".parse::<Api>().is_err());
+    }
+
+    #[test]
+    fn test_available_versions_and_extensions() {
+        let versions = Registry::available_versions(Api::Gl);
+        assert_eq!(versions.first(), Some(&(1, 0)));
+        assert!(versions.contains(&(4, 6)));
+        assert!(Registry::available_versions(Api::Gles2).contains(&(3, 2)));
+        assert!(!Registry::available_versions(Api::Gles2).contains(&(4, 6)));
+
+        let extensions = Registry::available_extensions(Api::Gl);
+        assert!(extensions.contains(&"GL_KHR_debug".to_string()));
+        assert!(extensions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(!extensions.contains(&"GL_OES_vertex_array_object".to_string()));
+        assert!(Registry::available_extensions(Api::Gles2)
+            .contains(&"GL_OES_vertex_array_object".to_string()));
+    }
+}
@@ -22,7 +22,7 @@ use xml::attribute::OwnedAttribute;
 use xml::reader::XmlEvent;
 use xml::EventReader as XmlEventReader;
 
-use registry::{Binding, Cmd, Enum, GlxOpcode, Group, Registry};
+use registry::{self, Binding, Cmd, Enum, GlxOpcode, Group, Registry, Version};
 use {Api, Fallbacks, Profile};
 
 pub fn from_xml<R: io::Read>(src: R, filter: &Filter, require_feature: bool) -> Registry {
@@ -33,6 +33,38 @@ pub fn from_xml<R: io::Read>(src: R, filter: &Filter, require_feature: bool) ->
         .parse(filter, require_feature)
 }
 
+/// Returns the versions of the features of `api` and the names of the extensions supporting it,
+/// in the order of the registry, without parsing the rest of the registry.
+pub fn scan_xml<R: io::Read>(src: R, api: Api) -> (Vec<(u8, u8)>, Vec<String>) {
+    let mut versions = Vec::new();
+    let mut extensions = Vec::new();
+    let events = XmlEventReader::new(src)
+        .into_iter()
+        .map(Result::unwrap)
+        .filter_map(ParseEvent::from_xml);
+    for event in events {
+        match event {
+            ParseEvent::Start(ref name, ref a) if name == "feature" => {
+                let feature_api = get_attribute(a, "api").unwrap();
+                if api_from_str(&feature_api) == Ok(Some(api)) {
+                    versions.push(parse_version(&get_attribute(a, "number").unwrap()));
+                }
+            },
+            ParseEvent::Start(ref name, ref a) if name == "extension" => {
+                let supported = get_attribute(a, "supported").unwrap();
+                if supported
+                    .split('|')
+                    .any(|supported| api_from_str(supported) == Ok(Some(api)))
+                {
+                    extensions.push(get_attribute(a, "name").unwrap());
+                }
+            },
+            _ => {},
+        }
+    }
+    (versions, extensions)
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Attribute {
     key: String,
@@ -109,6 +141,50 @@ fn profile_from_str(src: &str) -> Result<Profile, ()> {
     }
 }
 
+fn parse_version(src: &str) -> (u8, u8) {
+    let mut parts = src.splitn(2, '.').map(|part| part.parse().unwrap());
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => (major, minor),
+        _ => panic!("Unexpected version format: {}", src),
+    }
+}
+
+/// Returns the idents of the commands and enums of the registry that are required by `requires`.
+fn required_items(
+    requires: &[Require],
+    cmds: &BTreeSet<Cmd>,
+    enums: &BTreeSet<Enum>,
+) -> (BTreeSet<String>, BTreeSet<String>) {
+    let requires_cmd = |c: &&Cmd| {
+        requires.iter().any(|require| {
+            ["gl", "wgl", "glX", "egl"].iter().any(|prefix| {
+                require
+                    .commands
+                    .contains(&(prefix.to_string() + &c.proto.ident))
+            })
+        })
+    };
+    let requires_enum = |e: &&Enum| {
+        requires.iter().any(|require| {
+            ["GL_", "WGL_", "GLX_", "EGL_"]
+                .iter()
+                .any(|prefix| require.enums.contains(&(prefix.to_string() + &e.ident)))
+        })
+    };
+
+    (
+        cmds.iter()
+            .filter(requires_cmd)
+            .map(|c| c.proto.ident.clone())
+            .collect(),
+        enums
+            .iter()
+            .filter(requires_enum)
+            .map(|e| e.ident.clone())
+            .collect(),
+    )
+}
+
 fn underscore_numeric_prefix(src: &str) -> String {
     match src.chars().next() {
         Some(c) if c.is_numeric() => format!("_{}", src),
@@ -117,20 +193,14 @@ fn underscore_numeric_prefix(src: &str) -> String {
 }
 
 fn underscore_keyword(ident: String) -> String {
-    match ident.as_ref() {
-        "in" => "in_".to_string(),
-        "ref" => "ref_".to_string(),
-        "type" => "type_".to_string(),
-        _ => ident,
+    match ::generators::gen_ident(&ident) {
+        Cow::Owned(escaped) => escaped,
+        Cow::Borrowed(_) => ident,
     }
 }
 
 fn trim_str<'a>(s: &'a str, trim: &str) -> &'a str {
-    if s.starts_with(trim) {
-        &s[trim.len()..]
-    } else {
-        s
-    }
+    s.strip_prefix(trim).unwrap_or(s)
 }
 
 fn trim_enum_prefix(ident: &str, api: Api) -> String {
@@ -173,11 +243,11 @@ fn make_enum(ident: String, ty: Option<String>, value: String, alias: Option<Str
     };
 
     Enum {
-        ident: ident,
-        value: value,
-        cast: cast,
-        alias: alias,
-        ty: ty,
+        ident,
+        value,
+        cast,
+        alias,
+        ty,
     }
 }
 
@@ -213,11 +283,11 @@ fn make_egl_enum(ident: String, ty: Option<String>, value: String, alias: Option
     };
 
     Enum {
-        ident: ident,
-        value: value,
-        cast: cast,
-        alias: alias,
-        ty: ty,
+        ident,
+        value,
+        cast,
+        alias,
+        ty,
     }
 }
 
@@ -273,6 +343,7 @@ struct Remove {
 #[derive(Clone)]
 struct Extension {
     pub name: String,
+    pub comment: Option<String>,
     /// which apis this extension is defined for (see Feature.api)
     pub supported: Vec<Api>,
     pub requires: Vec<Require>,
@@ -312,8 +383,8 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                 // add enum namespace
                 ParseEvent::Start(ref name, ref attributes) if name == "enums" => {
                     enums.extend(self.consume_enums(filter.api));
-                    let enums_group = get_attribute(&attributes, "group");
-                    let enums_type = get_attribute(&attributes, "type");
+                    let enums_group = get_attribute(attributes, "group");
+                    let enums_type = get_attribute(attributes, "type");
                     if let Some(group) = enums_group.and_then(|name| groups.get_mut(&name)) {
                         group.enums_type = enums_type;
                     }
@@ -328,13 +399,13 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
 
                 ParseEvent::Start(ref name, ref attributes) if name == "feature" => {
                     debug!("Parsing feature: {:?}", attributes);
-                    features.push(Feature::convert(&mut self, &attributes));
+                    features.push(Feature::convert(&mut self, attributes));
                 },
 
                 ParseEvent::Start(ref name, _) if name == "extensions" => loop {
                     match self.next().unwrap() {
                         ParseEvent::Start(ref name, ref attributes) if name == "extension" => {
-                            extensions.push(Extension::convert(&mut self, &attributes));
+                            extensions.push(Extension::convert(&mut self, attributes));
                         },
                         ParseEvent::End(ref name) if name == "extensions" => break,
                         event => panic!("Unexpected message {:?}", event),
@@ -358,8 +429,8 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
             // XXX: verify that the string comparison with <= actually works as desired
             if feature.api == filter.api && feature.number <= filter.version {
                 for require in &feature.requires {
-                    desired_enums.extend(require.enums.iter().map(|x| x.clone()));
-                    desired_cmds.extend(require.commands.iter().map(|x| x.clone()));
+                    desired_enums.extend(require.enums.iter().cloned());
+                    desired_cmds.extend(require.commands.iter().cloned());
                 }
 
                 for remove in &feature.removes {
@@ -393,8 +464,8 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                     );
                 }
                 for require in &extension.requires {
-                    desired_enums.extend(require.enums.iter().map(|x| x.clone()));
-                    desired_cmds.extend(require.commands.iter().map(|x| x.clone()));
+                    desired_enums.extend(require.enums.iter().cloned());
+                    desired_cmds.extend(require.commands.iter().cloned());
                 }
             }
         }
@@ -413,10 +484,57 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                 || desired_cmds.contains(&("egl".to_string() + &c.proto.ident))
         };
 
+        let enums: BTreeSet<Enum> = enums.into_iter().filter(is_desired_enum).collect();
+        let cmds: BTreeSet<Cmd> = cmds.into_iter().filter(is_desired_cmd).collect();
+
+        let versions = features
+            .iter()
+            .filter(|feature| feature.api == filter.api && feature.number <= filter.version)
+            .map(|feature| {
+                let (new_cmds, new_enums) = required_items(&feature.requires, &cmds, &enums);
+                let core_removes: Vec<_> = feature
+                    .removes
+                    .iter()
+                    .filter(|remove| remove.profile == Profile::Core)
+                    .map(|remove| Require {
+                        enums: remove.enums.clone(),
+                        commands: remove.commands.clone(),
+                    })
+                    .collect();
+                let (removed_cmds, _) = required_items(&core_removes, &cmds, &enums);
+                Version {
+                    name: feature.name.clone(),
+                    number: parse_version(&feature.number),
+                    cmds: new_cmds,
+                    enums: new_enums,
+                    removed_cmds,
+                }
+            })
+            .collect();
+
+        let mut desired_extensions: Vec<_> = extensions
+            .iter()
+            .filter(|extension| filter.extensions.contains(&extension.name))
+            .map(|extension| {
+                let (new_cmds, new_enums) = required_items(&extension.requires, &cmds, &enums);
+                registry::Extension {
+                    name: extension.name.clone(),
+                    comment: extension.comment.clone(),
+                    cmds: new_cmds,
+                    enums: new_enums,
+                }
+            })
+            .collect();
+        desired_extensions.sort_by(|a, b| a.name.cmp(&b.name));
+
         Registry {
             api: filter.api,
-            enums: enums.into_iter().filter(is_desired_enum).collect(),
-            cmds: cmds.into_iter().filter(is_desired_cmd).collect(),
+            version: parse_version(&filter.version),
+            profile: filter.profile,
+            extensions: desired_extensions,
+            versions,
+            enums,
+            cmds,
             aliases: if filter.fallbacks == Fallbacks::None {
                 BTreeMap::new()
             } else {
@@ -482,7 +600,7 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                     let n = name.clone();
 
                     if one == n {
-                        ones.push(FromXml::convert(self, &attributes));
+                        ones.push(FromXml::convert(self, attributes));
                     } else if "type" == n {
                         // XXX: GL1.1 contains types, which we never care about anyway.
                         // Make sure consume_two doesn't get used for things which *do*
@@ -490,7 +608,7 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                         warn!("Ignoring type!");
                         continue;
                     } else if two == n {
-                        twos.push(FromXml::convert(self, &attributes));
+                        twos.push(FromXml::convert(self, attributes));
                     } else {
                         panic!("Unexpected element: <{:?} {:?}>", n, &attributes);
                     }
@@ -540,10 +658,10 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
     }
 
     fn consume_enum(&mut self, api: Api, attributes: &[Attribute]) -> Enum {
-        let ident = trim_enum_prefix(&get_attribute(&attributes, "name").unwrap(), api).to_string();
-        let value = get_attribute(&attributes, "value").unwrap();
-        let alias = get_attribute(&attributes, "alias");
-        let ty = get_attribute(&attributes, "type");
+        let ident = trim_enum_prefix(&get_attribute(attributes, "name").unwrap(), api).to_string();
+        let value = get_attribute(attributes, "value").unwrap();
+        let alias = get_attribute(attributes, "alias");
+        let ty = get_attribute(attributes, "type");
         self.consume_end_element("enum");
 
         match api {
@@ -557,7 +675,7 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         loop {
             match self.next().unwrap() {
                 ParseEvent::Start(ref name, ref attributes) if name == "group" => {
-                    let ident = get_attribute(&attributes, "name").unwrap();
+                    let ident = get_attribute(attributes, "name").unwrap();
                     let group = Group {
                         ident: ident.clone(),
                         enums_type: None,
@@ -577,7 +695,7 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         loop {
             match self.next().unwrap() {
                 ParseEvent::Start(ref name, ref attributes) if name == "enum" => {
-                    let enum_name = get_attribute(&attributes, "name");
+                    let enum_name = get_attribute(attributes, "name");
                     enums.push(trim_enum_prefix(&enum_name.unwrap(), api));
                     self.consume_end_element("enum");
                 },
@@ -619,8 +737,8 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
 
     fn consume_cmd(&mut self, api: Api) -> Cmd {
         // consume command prototype
-        self.consume_start_element("proto");
-        let mut proto = self.consume_binding("proto", &[]);
+        let attributes = self.consume_start_element("proto");
+        let mut proto = self.consume_binding("proto", &attributes);
         proto.ident = trim_cmd_prefix(&proto.ident, api).to_string();
 
         let mut params = Vec::new();
@@ -633,18 +751,18 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                     params.push(self.consume_binding("param", attributes));
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "alias" => {
-                    alias = get_attribute(&attributes, "name");
+                    alias = get_attribute(attributes, "name");
                     alias = alias.map(|t| trim_cmd_prefix(&t, api).to_string());
                     self.consume_end_element("alias");
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "vecequiv" => {
-                    vecequiv = get_attribute(&attributes, "vecequiv");
+                    vecequiv = get_attribute(attributes, "vecequiv");
                     self.consume_end_element("vecequiv");
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "glx" => {
                     glx = Some(GlxOpcode {
-                        opcode: get_attribute(&attributes, "opcode").unwrap(),
-                        name: get_attribute(&attributes, "name"),
+                        opcode: get_attribute(attributes, "opcode").unwrap(),
+                        name: get_attribute(attributes, "name"),
                     });
                     self.consume_end_element("glx");
                 },
@@ -654,11 +772,11 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         }
 
         Cmd {
-            proto: proto,
-            params: params,
-            alias: alias,
-            vecequiv: vecequiv,
-            glx: glx,
+            proto,
+            params,
+            alias,
+            vecequiv,
+            glx,
         }
     }
 
@@ -689,9 +807,11 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         }
 
         Binding {
-            ident: ident,
+            ident,
+            c_ty: ty.trim().to_string(),
             ty: to_rust_ty(ty),
-            group: get_attribute(&attributes, "group"),
+            group: get_attribute(attributes, "group"),
+            class: get_attribute(attributes, "class"),
         }
     }
 }
@@ -714,8 +834,8 @@ impl FromXml for Require {
         debug!("Doing a FromXml on Require");
         let (enums, commands) = parser.consume_two("enum", "command", "require");
         Require {
-            enums: enums,
-            commands: commands,
+            enums,
+            commands,
         }
     }
 }
@@ -728,9 +848,9 @@ impl FromXml for Remove {
         let (enums, commands) = parser.consume_two("enum", "command", "remove");
 
         Remove {
-            profile: profile,
-            enums: enums,
-            commands: commands,
+            profile,
+            enums,
+            commands,
         }
     }
 }
@@ -748,9 +868,9 @@ impl FromXml for Feature {
         let (require, remove) = parser.consume_two("require", "remove", "feature");
 
         Feature {
-            api: api,
-            name: name,
-            number: number,
+            api,
+            name,
+            number,
             requires: require,
             removes: remove,
         }
@@ -761,6 +881,7 @@ impl FromXml for Extension {
     fn convert<P: Parse>(parser: &mut P, a: &[Attribute]) -> Extension {
         debug!("Doing a FromXml on Extension");
         let name = get_attribute(a, "name").unwrap();
+        let comment = get_attribute(a, "comment");
         let supported = get_attribute(a, "supported")
             .unwrap()
             .split('|')
@@ -772,7 +893,7 @@ impl FromXml for Extension {
         loop {
             match parser.next().unwrap() {
                 ParseEvent::Start(ref name, ref attributes) if name == "require" => {
-                    require.push(FromXml::convert(parser, &attributes));
+                    require.push(FromXml::convert(parser, attributes));
                 },
                 ParseEvent::End(ref name) if name == "extension" => break,
                 event => panic!("Unexpected message {:?}", event),
@@ -780,8 +901,9 @@ impl FromXml for Extension {
         }
 
         Extension {
-            name: name,
-            supported: supported,
+            name,
+            comment,
+            supported,
             requires: require,
         }
     }
@@ -794,6 +916,10 @@ impl FromXml for String {
 }
 
 /// Converts a C style type definition to the Rust equivalent
+///
+/// Each level of pointer keeps its constness: `const T *` becomes `*const T` and `T *` becomes
+/// `*mut T`, so `const GLchar **` becomes `*mut *const GLchar` and `const GLchar *const*` becomes
+/// `*const *const GLchar`.
 pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
     let ty = match ty.as_ref().trim() {
         // gl.xml types
@@ -851,9 +977,9 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "GLuint64EXT *" => "*mut types::GLuint64EXT",
         "GLushort *" => "*mut types::GLushort",
         "GLvoid *" => "*mut types::GLvoid",
-        "GLvoid **" => "*const *mut types::GLvoid",
+        "GLvoid **" => "*mut *mut types::GLvoid",
         "void *" => "*mut __gl_imports::raw::c_void",
-        "void **" => "*const *mut __gl_imports::raw::c_void",
+        "void **" => "*mut *mut __gl_imports::raw::c_void",
         "const GLboolean *" => "*const types::GLboolean",
         "const GLbyte *" => "*const types::GLbyte",
         "const GLchar *" => "*const types::GLchar",
@@ -881,17 +1007,17 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "const GLvdpauSurfaceNV *" => "*const types::GLvdpauSurfaceNV",
         "const GLvoid *" => "*const types::GLvoid",
         "const void*" | "const void *" => "*const __gl_imports::raw::c_void",
-        "const void **" => "*const *const __gl_imports::raw::c_void",
+        "const void **" => "*mut *const __gl_imports::raw::c_void",
         "const void *const*" => "*const *const __gl_imports::raw::c_void",
-        "const GLboolean **" => "*const *const types::GLboolean",
-        "const GLchar **" => "*const *const types::GLchar",
-        "const GLcharARB **" => "*const *const types::GLcharARB",
-        "const GLvoid **" => "*const *const types::GLvoid",
+        "const GLboolean **" => "*mut *const types::GLboolean",
+        "const GLchar **" => "*mut *const types::GLchar",
+        "const GLcharARB **" => "*mut *const types::GLcharARB",
+        "const GLvoid **" => "*mut *const types::GLvoid",
         "const GLchar *const*" => "*const *const types::GLchar",
         "const GLvoid *const*" => "*const *const types::GLvoid",
-        "struct _cl_context *" => "*const types::_cl_context",
-        "struct _cl_event *" => "*const types::_cl_event",
-        "GLuint[2]" => "[Gluint; 2]",
+        "struct _cl_context *" => "*mut types::_cl_context",
+        "struct _cl_event *" => "*mut types::_cl_event",
+        "GLuint[2]" => "*mut [types::GLuint; 2]",
 
         // glx.xml types
         "Bool" => "types::Bool",
@@ -922,7 +1048,7 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "VLServer" => "types::VLServer",
         "Window" => "types::Window",
         "__GLXextFuncPtr" => "types::__GLXextFuncPtr",
-        "const GLXContext" => "const types::GLXContext",
+        "const GLXContext" => "types::GLXContext",
         "float" => "__gl_imports::raw::c_float",
         "int" => "__gl_imports::raw::c_int",
         "int64_t" => "i64",
@@ -1006,7 +1132,7 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "const HGPUNV *" => "*const types::HGPUNV",
         "const LAYERPLANEDESCRIPTOR *" => "*const types::LAYERPLANEDESCRIPTOR",
         "const LPVOID *" => "*const types::LPVOID",
-        "const PIXELFORMATDESCRIPTOR *" => "*const types::IXELFORMATDESCRIPTOR",
+        "const PIXELFORMATDESCRIPTOR *" => "*const types::PIXELFORMATDESCRIPTOR",
         "const USHORT *" => "*const types::USHORT",
         // "const char *"              => "*const __gl_imports::raw::c_char",
         // "const int *"               => "*const __gl_imports::raw::c_int",
@@ -1064,7 +1190,7 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "EGLSetBlobFuncANDROID" => "types::EGLSetBlobFuncANDROID",
         "EGLGetBlobFuncANDROID" => "types::EGLGetBlobFuncANDROID",
         "EGLClientPixmapHI" => "types::EGLClientPixmapHI",
-        "struct EGLClientPixmapHI *" => "*const types::EGLClientPixmapHI",
+        "struct EGLClientPixmapHI *" => "*mut types::EGLClientPixmapHI",
         "const EGLAttribKHR *" => "*const types::EGLAttribKHR",
         "const EGLuint64KHR *" => "*const types::EGLuint64KHR",
         "EGLAttribKHR *" => "*mut types::EGLAttribKHR",
@@ -1077,10 +1203,10 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
         "EGLOutputPortEXT *" => "*mut types::EGLOutputPortEXT",
         "EGLuint64KHR *" => "*mut types::EGLuint64KHR",
         "const struct AHardwareBuffer *" => "*const __gl_imports::raw::c_void", // humm
-        "char *" => "*const __gl_imports::raw::c_char",
-        "struct wl_buffer *" => "*const __gl_imports::raw::c_void",
-        "struct wl_display *" => "*const __gl_imports::raw::c_void",
-        "struct wl_resource *" => "*const __gl_imports::raw::c_void",
+        "char *" => "*mut __gl_imports::raw::c_char",
+        "struct wl_buffer *" => "*mut __gl_imports::raw::c_void",
+        "struct wl_display *" => "*mut __gl_imports::raw::c_void",
+        "struct wl_resource *" => "*mut __gl_imports::raw::c_void",
 
         "GLeglClientBufferEXT" => "types::GLeglClientBufferEXT",
         "GLVULKANPROCNV" => "types::GLVULKANPROCNV",
@@ -1100,6 +1226,39 @@ pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
 
 #[cfg(test)]
 mod tests {
+    mod to_rust_ty {
+        use registry::parse;
+
+        #[test]
+        fn test_pointer_constness() {
+            // C types of real commands, such as `glShaderSource`, `glShaderSourceARB`,
+            // `glGetPointerv`, `glMultiDrawElementsEXT` and `glGetActiveUniform`.
+            let cases = [
+                ("const GLchar *const*", "*const *const types::GLchar"),
+                ("const GLcharARB **", "*mut *const types::GLcharARB"),
+                ("const GLchar **", "*mut *const types::GLchar"),
+                ("void **", "*mut *mut __gl_imports::raw::c_void"),
+                ("const void **", "*mut *const __gl_imports::raw::c_void"),
+                (
+                    "const void *const*",
+                    "*const *const __gl_imports::raw::c_void",
+                ),
+                ("const GLvoid *const*", "*const *const types::GLvoid"),
+                ("const GLboolean **", "*mut *const types::GLboolean"),
+                ("GLchar *", "*mut types::GLchar"),
+                ("const GLchar *", "*const types::GLchar"),
+                ("const void *", "*const __gl_imports::raw::c_void"),
+                ("void *", "*mut __gl_imports::raw::c_void"),
+                ("struct _cl_context *", "*mut types::_cl_context"),
+                ("const GLXContext", "types::GLXContext"),
+            ];
+
+            for &(c_ty, rust_ty) in &cases {
+                assert_eq!(parse::to_rust_ty(c_ty), rust_ty, "{}", c_ty);
+            }
+        }
+    }
+
     mod underscore_numeric_prefix {
         use registry::parse;
 
@@ -1125,6 +1284,10 @@ mod tests {
             assert_eq!(parse::underscore_keyword("in".to_string()), "in_");
             assert_eq!(parse::underscore_keyword("ref".to_string()), "ref_");
             assert_eq!(parse::underscore_keyword("type".to_string()), "type_");
+            assert_eq!(parse::underscore_keyword("async".to_string()), "async_");
+            assert_eq!(parse::underscore_keyword("await".to_string()), "await_");
+            assert_eq!(parse::underscore_keyword("dyn".to_string()), "dyn_");
+            assert_eq!(parse::underscore_keyword("try".to_string()), "try_");
         }
 
         #[test]
@@ -1174,7 +1337,7 @@ mod tests {
             assert_eq!(e.value, "value");
             assert_eq!(e.alias, Some("BAR".to_string()));
             assert_eq!(e.ty, "GLenum");
-            assert_eq!(e.cast, false);
+            assert!(!e.cast);
         }
 
         #[test]
@@ -0,0 +1,360 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Canonical formatting of the bindings, for checking them into version control and verifying
+//! that they are up to date.
+
+use std::error;
+use std::fmt;
+
+use registry::Registry;
+use {Generator, GeneratorOptions};
+
+/// The largest number of pairs of lines that `diff_lines` compares to find the lines that are
+/// common to both sides. Larger differences are reported as all the differing lines being
+/// replaced, which is correct if not minimal.
+const MAX_DIFF_COST: usize = 4_000_000;
+
+/// A line that differs between the existing bindings and the generated ones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineChange {
+    /// A line that is only in the existing bindings, with its number in them, starting at 1.
+    Removed(usize, String),
+    /// A line that is only in the generated bindings, with its number in them, starting at 1.
+    Added(usize, String),
+}
+
+/// The differences between existing bindings and the ones generated by
+/// `Registry::verify_bindings`, which is the error it fails with.
+///
+/// Displaying it gives one line per change, in order, with the removed lines starting with `-`
+/// and the added lines with `+`, followed by their line number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindingsDiff {
+    /// The lines that differ, in the order of the bindings, the removed lines of a change coming
+    /// before the added ones.
+    pub changes: Vec<LineChange>,
+}
+
+impl BindingsDiff {
+    /// Returns `true` if the bindings are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for BindingsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            match *change {
+                LineChange::Removed(number, ref line) => writeln!(f, "-{}: {}", number, line)?,
+                LineChange::Added(number, ref line) => writeln!(f, "+{}: {}", number, line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for BindingsDiff {}
+
+impl Registry {
+    /// Generates the bindings of `generator` in memory with `GeneratorOptions::stable_output`
+    /// set, and compares them line by line with `existing`, such as the contents of bindings
+    /// checked into version control.
+    ///
+    /// Fails with the `BindingsDiff` of the two if they differ, which can be printed for the logs
+    /// of CI.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bindings can't be generated, which only happens when `options` are invalid
+    /// for `generator`, as `write_bindings_with_options` would report.
+    pub fn verify_bindings<G>(
+        &self,
+        generator: G,
+        options: &GeneratorOptions,
+        existing: &str,
+    ) -> Result<(), BindingsDiff>
+    where
+        G: Generator,
+    {
+        let options = GeneratorOptions {
+            stable_output: true,
+            ..options.clone()
+        };
+        let mut dest = Vec::new();
+        if let Err(error) = self.write_bindings_with_options(generator, &options, &mut dest) {
+            panic!("the bindings could not be generated: {}", error);
+        }
+        let generated = String::from_utf8(dest).expect("the generators only write UTF-8");
+
+        let diff = diff_lines(existing, &generated);
+        if diff.is_empty() {
+            Ok(())
+        } else {
+            Err(diff)
+        }
+    }
+}
+
+/// Formats bindings canonically for `GeneratorOptions::stable_output`: every line is stripped of
+/// its indentation and trailing whitespace, blank lines are removed and the output ends with a
+/// single newline. The generators never emit string literals spanning several lines, whose
+/// contents this would change.
+pub fn canonicalize(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    for line in src.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Compares `old` and `new` line by line, keeping the longest sequence of lines that they have in
+/// common.
+fn diff_lines(old: &str, new: &str) -> BindingsDiff {
+    let old: Vec<_> = old.lines().collect();
+    let new: Vec<_> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|&(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|&(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut diff = BindingsDiff::default();
+    let removed = |i: usize| LineChange::Removed(prefix + i + 1, old_mid[i].to_string());
+    let added = |j: usize| LineChange::Added(prefix + j + 1, new_mid[j].to_string());
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_COST {
+        diff.changes.extend((0..old_mid.len()).map(removed));
+        diff.changes.extend((0..new_mid.len()).map(added));
+        return diff;
+    }
+
+    // `common[i][j]` is the length of the longest common sequence of `old_mid[i..]` and
+    // `new_mid[j..]`.
+    let width = new_mid.len() + 1;
+    let mut common = vec![0usize; (old_mid.len() + 1) * width];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            common[i * width + j] = if old_mid[i] == new_mid[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            i += 1;
+            j += 1;
+        } else if j == new_mid.len()
+            || (i < old_mid.len() && common[(i + 1) * width + j] >= common[i * width + j + 1])
+        {
+            diff.changes.push(removed(i));
+            i += 1;
+        } else {
+            diff.changes.push(added(j));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    use super::LineChange;
+    use registry::{Api, Binding, Cmd, Enum, Profile, Registry, Version};
+    use {DebugStructGenerator, Generator, GeneratorOptions, GlobalGenerator};
+    use {StaticGenerator, StaticStructGenerator, StructGenerator};
+
+    fn binding(ident: &str, ty: &'static str) -> Binding {
+        Binding {
+            ident: ident.to_string(),
+            ty: Cow::Borrowed(ty),
+            c_ty: String::new(),
+            group: None,
+            class: None,
+        }
+    }
+
+    /// A registry with a few commands and enums, small enough for its bindings to be reviewed.
+    fn snapshot_registry() -> Registry {
+        let mut enums = BTreeSet::new();
+        enums.insert(Enum {
+            ident: "COLOR_BUFFER_BIT".to_string(),
+            value: "0x00004000".to_string(),
+            cast: false,
+            alias: None,
+            ty: Cow::Borrowed("GLenum"),
+        });
+
+        let mut cmds = BTreeSet::new();
+        cmds.insert(Cmd {
+            proto: binding("Clear", "()"),
+            params: vec![binding("mask", "types::GLbitfield")],
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+        cmds.insert(Cmd {
+            proto: binding("GetError", "types::GLenum"),
+            params: Vec::new(),
+            alias: None,
+            vecequiv: None,
+            glx: None,
+        });
+
+        Registry {
+            api: Api::Gl,
+            version: (1, 0),
+            profile: Profile::Core,
+            extensions: Vec::new(),
+            versions: vec![Version {
+                name: "GL_VERSION_1_0".to_string(),
+                number: (1, 0),
+                cmds: cmds.iter().map(|cmd| cmd.proto.ident.clone()).collect(),
+                enums: enums.iter().map(|enm| enm.ident.clone()).collect(),
+                removed_cmds: BTreeSet::new(),
+            }],
+            enums,
+            cmds,
+            aliases: BTreeMap::new(),
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Verifies the bindings of `generator` for `snapshot_registry` against the snapshot named
+    /// `name`, which is rewritten instead when the `UPDATE_SNAPSHOTS` environment variable is set.
+    fn check_snapshot<G: Generator>(generator: G, name: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("registry/snapshots")
+            .join(name);
+        let registry = snapshot_registry();
+        let options = GeneratorOptions {
+            stable_output: true,
+            ..Default::default()
+        };
+
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            let mut dest = Vec::new();
+            registry
+                .write_bindings_with_options(generator, &options, &mut dest)
+                .unwrap();
+            fs::write(&path, dest).unwrap();
+            return;
+        }
+
+        let existing = fs::read_to_string(&path).unwrap();
+        if let Err(error) = registry.verify_bindings(generator, &options, &existing) {
+            panic!(
+                "the bindings differ from `{}`, rerun with `UPDATE_SNAPSHOTS=1` if this is \
+                 expected:\n{}",
+                path.display(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_global_gen_snapshot() {
+        check_snapshot(GlobalGenerator, "global_gen.snap");
+    }
+
+    #[test]
+    fn test_struct_gen_snapshot() {
+        check_snapshot(StructGenerator, "struct_gen.snap");
+    }
+
+    #[test]
+    fn test_debug_struct_gen_snapshot() {
+        check_snapshot(DebugStructGenerator, "debug_struct_gen.snap");
+    }
+
+    #[test]
+    fn test_static_gen_snapshot() {
+        check_snapshot(StaticGenerator, "static_gen.snap");
+    }
+
+    #[test]
+    fn test_static_struct_gen_snapshot() {
+        check_snapshot(StaticStructGenerator, "static_struct_gen.snap");
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        assert_eq!(
+            super::canonicalize("  fn f() {  \n\n\n      g();\r\n  }\n\n"),
+            "fn f() {\ng();\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert!(super::diff_lines("a\nb\n", "a\nb\n").is_empty());
+
+        let diff = super::diff_lines("a\nb\nc\nd\n", "a\nx\nc\nd\ne\n");
+        assert_eq!(
+            diff.changes,
+            [
+                LineChange::Removed(2, "b".to_string()),
+                LineChange::Added(2, "x".to_string()),
+                LineChange::Added(5, "e".to_string()),
+            ]
+        );
+        assert_eq!(diff.to_string(), "-2: b\n+2: x\n+5: e\n");
+    }
+
+    #[test]
+    fn test_verify_bindings() {
+        let registry = snapshot_registry();
+        let mut existing = Vec::new();
+        registry
+            .write_bindings_with_options(
+                GlobalGenerator,
+                &GeneratorOptions {
+                    stable_output: true,
+                    ..Default::default()
+                },
+                &mut existing,
+            )
+            .unwrap();
+        let existing = String::from_utf8(existing).unwrap();
+        registry
+            .verify_bindings(GlobalGenerator, &Default::default(), &existing)
+            .unwrap();
+
+        let stale = existing.replace("COLOR_BUFFER_BIT", "DEPTH_BUFFER_BIT");
+        let diff = registry
+            .verify_bindings(GlobalGenerator, &Default::default(), &stale)
+            .unwrap_err();
+        assert!(diff.changes.iter().any(|change| match *change {
+            LineChange::Added(_, ref line) => line.contains("COLOR_BUFFER_BIT"),
+            LineChange::Removed(..) => false,
+        }));
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generating the bindings as a `proc_macro2::TokenStream`, for procedural macros that splice
+//! them in place instead of including a file written by a build script.
+
+use proc_macro2::{LexError, TokenStream};
+use std::io;
+
+use registry::Registry;
+use {Generator, GeneratorOptions};
+
+impl Registry {
+    /// Generates the bindings of `generator` as a token stream, enabling the optional features
+    /// selected in `options`.
+    ///
+    /// The bindings are written as text and then parsed, so every token has the call site span
+    /// of the macro that returns them. Fails like `write_bindings_with_options`, and with
+    /// `io::ErrorKind::InvalidData` if the bindings can't be parsed.
+    pub fn write_token_stream<G>(
+        &self,
+        generator: G,
+        options: &GeneratorOptions,
+    ) -> io::Result<TokenStream>
+    where
+        G: Generator,
+    {
+        let mut dest = Vec::new();
+        self.write_bindings_with_options(generator, options, &mut dest)?;
+        let src =
+            String::from_utf8(dest).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        src.parse().map_err(|e: LexError| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the bindings are not valid tokens: {}", e),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenTree;
+
+    use registry::{Api, Fallbacks, Profile, Registry};
+    use TypesGenerator;
+
+    #[test]
+    fn test_write_token_stream() {
+        let registry = Registry::new(Api::Gl, (1, 0), Profile::Core, Fallbacks::All, []);
+        let tokens = registry
+            .write_token_stream(TypesGenerator, &Default::default())
+            .unwrap();
+
+        let idents: Vec<_> = tokens
+            .into_iter()
+            .filter_map(|token| match token {
+                TokenTree::Ident(ident) => Some(ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(idents[..3], ["pub", "mod", "types"]);
+        assert!(idents.contains(&"GLenum".to_string()));
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `gl_bindings!` macro, which generates OpenGL bindings in place with `gl_generator`, for
+//! crates that don't want a build script.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[macro_use]
+//! extern crate gl_generator_macro;
+//!
+//! mod gl {
+//!     gl_bindings!(
+//!         api: gl,
+//!         version: "3.3",
+//!         profile: core,
+//!         extensions: ["GL_KHR_debug"],
+//!         generator: global,
+//!     );
+//! }
+//! ```
+//!
+//! The arguments are:
+//!
+//! - `api`: one of `gl`, `glx`, `wgl`, `egl`, `glcore`, `gles1`, `gles2` or `glsc2`.
+//! - `version`: the version as a string, such as `"4.6"`.
+//! - `profile`: `core` (default) or `compatibility`.
+//! - `fallbacks`: `all` (default) or `none`.
+//! - `extensions`: the names of the extensions to include, such as `["GL_KHR_debug"]`.
+//! - `generator`: `global` (default), `struct`, `debug_struct`, `static`, `static_struct` or
+//!   `types`.
+//!
+//! The bindings are generated every time the crate is compiled, which takes noticeably longer
+//! than including bindings generated by a build script for large registries.
+
+extern crate gl_generator;
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use gl_generator::{
+    Api, DebugStructGenerator, Fallbacks, GeneratorOptions, GlobalGenerator, Profile, Registry,
+    StaticGenerator, StaticStructGenerator, StructGenerator, TypesGenerator,
+};
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitStr, Token};
+
+/// Generates the bindings described by the arguments in place. See the crate documentation for
+/// the arguments.
+#[proc_macro]
+pub fn gl_bindings(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    syn::parse::<Args>(input)
+        .and_then(|args| expand(&args))
+        .unwrap_or_else(compile_error)
+        .into()
+}
+
+/// Reports `error` with `compile_error!`.
+///
+/// `syn::Error::to_compile_error` expands to `::core::compile_error!`, which doesn't resolve in
+/// crates of the 2015 edition.
+fn compile_error(error: syn::Error) -> proc_macro2::TokenStream {
+    error
+        .into_iter()
+        .map(|error| {
+            let message = error.to_string();
+            quote_spanned!(error.span()=> compile_error!(#message);)
+        })
+        .collect()
+}
+
+/// The generators that the `generator` argument can select.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum GeneratorKind {
+    Global,
+    Struct,
+    DebugStruct,
+    Static,
+    StaticStruct,
+    Types,
+}
+
+/// The parsed arguments of the macro, with the literals that the registry is checked against.
+struct Args {
+    api: Api,
+    version: (u8, u8),
+    version_lit: LitStr,
+    profile: Profile,
+    fallbacks: Fallbacks,
+    extensions: Vec<LitStr>,
+    generator: GeneratorKind,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Args> {
+        let mut api = None;
+        let mut version = None;
+        let mut profile = None;
+        let mut fallbacks = None;
+        let mut extensions = None;
+        let mut generator = None;
+
+        while !input.is_empty() {
+            let key = input.call(Ident::parse_any)?;
+            input.parse::<Token![:]>()?;
+            match &key.to_string()[..] {
+                "api" => set(&mut api, &key, parse_api(&input.call(Ident::parse_any)?)?)?,
+                "version" => {
+                    let lit: LitStr = input.parse()?;
+                    let parsed = parse_version(&lit)?;
+                    set(&mut version, &key, (parsed, lit))?
+                },
+                "profile" => set(
+                    &mut profile,
+                    &key,
+                    parse_profile(&input.call(Ident::parse_any)?)?,
+                )?,
+                "fallbacks" => set(
+                    &mut fallbacks,
+                    &key,
+                    parse_fallbacks(&input.call(Ident::parse_any)?)?,
+                )?,
+                "extensions" => {
+                    let content;
+                    syn::bracketed!(content in input);
+                    let names = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    set(&mut extensions, &key, names.into_iter().collect())?
+                },
+                "generator" => set(
+                    &mut generator,
+                    &key,
+                    parse_generator(&input.call(Ident::parse_any)?)?,
+                )?,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "unknown argument `{}`, expected `api`, `version`, `profile`, \
+                             `fallbacks`, `extensions` or `generator`",
+                            key
+                        ),
+                    ))
+                },
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let missing =
+            |name: &str| syn::Error::new(Span::call_site(), format!("missing `{}`", name));
+        let (version, version_lit) = version.ok_or_else(|| missing("version"))?;
+        Ok(Args {
+            api: api.ok_or_else(|| missing("api"))?,
+            version,
+            version_lit,
+            profile: profile.unwrap_or(Profile::Core),
+            fallbacks: fallbacks.unwrap_or(Fallbacks::All),
+            extensions: extensions.unwrap_or_default(),
+            generator: generator.unwrap_or(GeneratorKind::Global),
+        })
+    }
+}
+
+/// Sets the value of the argument `key`, which must not have been given before.
+fn set<T>(slot: &mut Option<T>, key: &Ident, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new(
+            key.span(),
+            format!("`{}` is given more than once", key),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn parse_api(ident: &Ident) -> syn::Result<Api> {
+    ident
+        .to_string()
+        .parse()
+        .map_err(|message: String| syn::Error::new(ident.span(), message))
+}
+
+fn parse_version(lit: &LitStr) -> syn::Result<(u8, u8)> {
+    let value = lit.value();
+    let mut parts = value.splitn(2, '.');
+    match (
+        parts.next().and_then(|major| major.parse().ok()),
+        parts.next().and_then(|minor| minor.parse().ok()),
+    ) {
+        (Some(major), Some(minor)) => Ok((major, minor)),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            format!("invalid version `{}`, expected MAJOR.MINOR", value),
+        )),
+    }
+}
+
+fn parse_profile(ident: &Ident) -> syn::Result<Profile> {
+    match &ident.to_string()[..] {
+        "core" => Ok(Profile::Core),
+        "compatibility" => Ok(Profile::Compatibility),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "unknown profile `{}`, expected `core` or `compatibility`",
+                ident
+            ),
+        )),
+    }
+}
+
+fn parse_fallbacks(ident: &Ident) -> syn::Result<Fallbacks> {
+    match &ident.to_string()[..] {
+        "all" => Ok(Fallbacks::All),
+        "none" => Ok(Fallbacks::None),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            format!("unknown fallbacks `{}`, expected `all` or `none`", ident),
+        )),
+    }
+}
+
+fn parse_generator(ident: &Ident) -> syn::Result<GeneratorKind> {
+    match &ident.to_string()[..] {
+        "global" => Ok(GeneratorKind::Global),
+        "struct" => Ok(GeneratorKind::Struct),
+        "debug_struct" => Ok(GeneratorKind::DebugStruct),
+        "static" => Ok(GeneratorKind::Static),
+        "static_struct" => Ok(GeneratorKind::StaticStruct),
+        "types" => Ok(GeneratorKind::Types),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "unknown generator `{}`, expected `global`, `struct`, `debug_struct`, `static`, \
+                 `static_struct` or `types`",
+                ident
+            ),
+        )),
+    }
+}
+
+/// Checks the version and the extensions of `args` against the registry, and generates the
+/// bindings they describe.
+fn expand(args: &Args) -> syn::Result<proc_macro2::TokenStream> {
+    if !Registry::available_versions(args.api).contains(&args.version) {
+        return Err(syn::Error::new(
+            args.version_lit.span(),
+            format!(
+                "{} has no version {}.{}",
+                args.api, args.version.0, args.version.1
+            ),
+        ));
+    }
+
+    let available = Registry::available_extensions(args.api);
+    let mut extensions = Vec::new();
+    for lit in &args.extensions {
+        let name = lit.value();
+        if available.binary_search(&name).is_err() {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("{} has no extension `{}`", args.api, name),
+            ));
+        }
+        extensions.push(name);
+    }
+
+    let registry = Registry::new(
+        args.api,
+        args.version,
+        args.profile,
+        args.fallbacks,
+        extensions.iter().map(|e| &e[..]).collect::<Vec<_>>(),
+    );
+
+    let options = GeneratorOptions::default();
+    match args.generator {
+        GeneratorKind::Global => registry.write_token_stream(GlobalGenerator, &options),
+        GeneratorKind::Struct => registry.write_token_stream(StructGenerator, &options),
+        GeneratorKind::DebugStruct => registry.write_token_stream(DebugStructGenerator, &options),
+        GeneratorKind::Static => registry.write_token_stream(StaticGenerator, &options),
+        GeneratorKind::StaticStruct => registry.write_token_stream(StaticStructGenerator, &options),
+        GeneratorKind::Types => registry.write_token_stream(TypesGenerator, &options),
+    }
+    .map_err(|error| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("failed to generate the bindings: {}", error),
+        )
+    })
+}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate gl_generator_macro;
+
+mod api {
+    gl_bindings!(api: vulkan, version: "1.0");
+}
+
+mod version {
+    gl_bindings!(api: gl, version: "4");
+}
+
+mod missing {
+    gl_bindings!(version: "4.6");
+}
+
+mod duplicate {
+    gl_bindings!(api: gl, version: "4.6", api: gles2);
+}
+
+mod unknown {
+    gl_bindings!(api: gl, version: "4.6", generators: struct);
+}
+
+fn main() {}
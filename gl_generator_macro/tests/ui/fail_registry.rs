@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate gl_generator_macro;
+
+mod version {
+    gl_bindings!(api: gl, version: "3.5");
+}
+
+mod extension {
+    gl_bindings!(api: gles2, version: "2.0", extensions: ["GL_OES_texture_3D", "GL_ARB_sync"]);
+}
+
+fn main() {}
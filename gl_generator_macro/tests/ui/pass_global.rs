@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate gl_generator_macro;
+
+mod gl {
+    gl_bindings!(
+        api: gl,
+        version: "3.3",
+        profile: core,
+        extensions: ["GL_KHR_debug"],
+        generator: global,
+    );
+}
+
+fn main() {
+    let _: gl::types::GLenum = gl::DEBUG_OUTPUT;
+    assert!(!gl::DebugMessageCallback::is_loaded());
+    assert!(!gl::Clear::is_loaded());
+}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate gl_generator_macro;
+
+mod gles {
+    gl_bindings!(api: gles2, version: "3.0", generator: struct, fallbacks: none);
+}
+
+fn main() {
+    let gl = gles::Gles2::load_with(|_| std::ptr::null());
+    assert!(!gl.Clear.is_loaded());
+}
@@ -0,0 +1,84 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate gl_generator;
+
+use gl_generator::{
+    Api, DebugStructGenerator, Fallbacks, GeneratorOptions, GlobalGenerator, Prelude, Profile,
+    Registry, StructGenerator,
+};
+use std::env;
+use std::fs::File;
+use std::path::Path;
+
+/// The features selecting the version of the bindings, which is 3.0 without any of them.
+const VERSIONS: &[(&str, (u8, u8))] = &[("gles20", (2, 0)), ("gles31", (3, 1)), ("gles32", (3, 2))];
+
+/// The features adding an extension to the bindings.
+const EXTENSIONS: &[(&str, &str)] = &[
+    (
+        "ext_ext_disjoint_timer_query",
+        "GL_EXT_disjoint_timer_query",
+    ),
+    (
+        "ext_ext_texture_filter_anisotropic",
+        "GL_EXT_texture_filter_anisotropic",
+    ),
+    ("ext_khr_debug", "GL_KHR_debug"),
+    ("ext_oes_egl_image", "GL_OES_EGL_image"),
+    ("ext_oes_vertex_array_object", "GL_OES_vertex_array_object"),
+];
+
+/// Returns `true` if the feature `name` of the crate is enabled.
+fn has_feature(name: &str) -> bool {
+    env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase())).is_some()
+}
+
+fn main() {
+    let dest = env::var("OUT_DIR").unwrap();
+    let mut file = File::create(Path::new(&dest).join("bindings.rs")).unwrap();
+
+    // Conflicting features are reported by `compile_error!` in `lib.rs`, so the first one wins
+    // here.
+    let version = VERSIONS
+        .iter()
+        .find(|&&(feature, _)| has_feature(feature))
+        .map_or((3, 0), |&(_, version)| version);
+    let extensions: Vec<_> = EXTENSIONS
+        .iter()
+        .filter(|&&(feature, _)| has_feature(feature))
+        .map(|&(_, extension)| extension)
+        .collect();
+
+    let options = GeneratorOptions {
+        prelude: Some(Prelude::TypesAndCommands),
+        ..Default::default()
+    };
+
+    let registry = Registry::new(
+        Api::Gles2,
+        version,
+        Profile::Core,
+        Fallbacks::All,
+        extensions,
+    );
+    if has_feature("debug_api") {
+        registry.write_bindings_with_options(DebugStructGenerator, &options, &mut file)
+    } else if has_feature("struct_api") {
+        registry.write_bindings_with_options(StructGenerator, &options, &mut file)
+    } else {
+        registry.write_bindings_with_options(GlobalGenerator, &options, &mut file)
+    }
+    .unwrap();
+}
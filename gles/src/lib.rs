@@ -0,0 +1,100 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Usage
+//!
+//! The bindings are generated for OpenGL ES 3.0 with the global generator by default. Cargo
+//! features of the crate select other bindings:
+//!
+//! - `gles20`, `gles31` or `gles32` for OpenGL ES 2.0, 3.1 or 3.2.
+//! - `struct_api` for a `gles::Gles2` structure holding the function pointers, or `debug_api` for
+//!   one that also logs every call, instead of global functions.
+//! - `ext_ext_disjoint_timer_query`, `ext_ext_texture_filter_anisotropic`, `ext_khr_debug`,
+//!   `ext_oes_egl_image` and `ext_oes_vertex_array_object` for the corresponding extensions.
+//!
+//! ```toml
+//! [dependencies]
+//! gles = { version = "0.1.0", features = ["gles20", "ext_oes_vertex_array_object"] }
+//! ```
+//!
+#![cfg_attr(
+    not(any(feature = "struct_api", feature = "debug_api")),
+    doc = r#"
+The function pointers are loaded with the `load_with` function, which takes a loader function
+from your context library, such as `eglGetProcAddress`. All OpenGL ES function calls are
+`unsafe`.
+
+~~~no_run
+# extern crate gles;
+# use std::os::raw;
+# fn get_proc_address(_: &str) -> *const raw::c_void { std::ptr::null() }
+# fn main() {
+gles::load_with(|s| get_proc_address(s));
+
+unsafe {
+    gles::ClearColor(0.0, 0.0, 0.0, 1.0);
+    gles::Clear(gles::COLOR_BUFFER_BIT);
+}
+
+if gles::Viewport::is_loaded() {
+    // do something...
+}
+# }
+~~~
+
+`use gles::prelude::*;` brings the type aliases and the commands into scope at once.
+"#
+)]
+#![cfg_attr(
+    any(feature = "struct_api", feature = "debug_api"),
+    doc = r#"
+The function pointers are loaded into a `gles::Gles2` with its `load_with` function, which takes
+a loader function from your context library, such as `eglGetProcAddress`, and the commands are
+its methods. All OpenGL ES function calls are `unsafe`.
+
+~~~no_run
+# extern crate gles;
+# use std::os::raw;
+# fn get_proc_address(_: &str) -> *const raw::c_void { std::ptr::null() }
+# fn main() {
+let gl = gles::Gles2::load_with(|s| get_proc_address(s));
+
+unsafe {
+    gl.ClearColor(0.0, 0.0, 0.0, 1.0);
+    gl.Clear(gles::COLOR_BUFFER_BIT);
+}
+
+if gl.Viewport.is_loaded() {
+    // do something...
+}
+# }
+~~~
+
+`use gles::prelude::*;` brings the type aliases and the `Gles2` structure into scope at once.
+"#
+)]
+#![crate_name = "gles"]
+#![crate_type = "lib"]
+
+#[cfg(any(
+    all(feature = "gles20", feature = "gles31"),
+    all(feature = "gles20", feature = "gles32"),
+    all(feature = "gles31", feature = "gles32")
+))]
+compile_error!("only one of the `gles20`, `gles31` and `gles32` features of `gles` can be enabled");
+
+#[cfg(all(feature = "struct_api", feature = "debug_api"))]
+compile_error!("the `struct_api` and `debug_api` features of `gles` can't be enabled together");
+
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
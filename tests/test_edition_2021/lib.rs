@@ -0,0 +1,25 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The bindings of the struct generator with `Edition::Edition2021`, compiled without warnings in
+//! that edition.
+#![deny(warnings)]
+
+include!(concat!(env!("OUT_DIR"), "/test_edition_2021.rs"));
+
+#[test]
+fn test_load_with() {
+    let gl = gl::Gl::load_with(|_| std::ptr::null());
+    assert!(!gl.Clear.is_loaded());
+}
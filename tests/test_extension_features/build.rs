@@ -0,0 +1,46 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate gl_generator;
+
+use gl_generator::*;
+use std::env;
+use std::fs::File;
+use std::io;
+use std::path::*;
+
+fn main() {
+    let dest = env::var("OUT_DIR").unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_extension_features.rs")).unwrap();
+
+    let options = GeneratorOptions {
+        extension_features: true,
+        registry_docs: true,
+        generation_guard: true,
+        prelude: Some(Prelude::TypesAndCommands),
+        ..Default::default()
+    };
+
+    let registry = Registry::new(
+        Api::Gl,
+        (1, 1),
+        Profile::Core,
+        Fallbacks::None,
+        ["GL_ARB_vertex_array_object", "GL_KHR_debug"],
+    );
+    registry
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    registry.write_cargo_check_cfg(&mut io::stdout()).unwrap();
+}
@@ -0,0 +1,67 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod gl {
+    include!(concat!(env!("OUT_DIR"), "/test_extension_features.rs"));
+}
+
+#[cfg(test)]
+use std::os::raw;
+
+/// Returns a dummy pointer for every symbol, as the commands are never called.
+#[cfg(test)]
+fn loader(_: &str) -> *const raw::c_void {
+    std::ptr::dangling::<raw::c_void>()
+}
+
+#[cfg(feature = "GL_ARB_vertex_array_object")]
+#[test]
+fn test_enabled_extension() {
+    gl::load_with(loader);
+    assert!(gl::BindVertexArray::is_loaded());
+    assert!(gl::GL_ARB_vertex_array_object::is_loaded());
+    assert!(gl::command_ptr("glBindVertexArray").is_some());
+    assert!(gl::load_extension_with(
+        loader,
+        "GL_ARB_vertex_array_object"
+    ));
+    let _: unsafe fn(gl::types::GLuint) = gl::prelude::BindVertexArray;
+}
+
+#[cfg(not(feature = "GL_KHR_debug"))]
+#[test]
+fn test_disabled_extension() {
+    gl::load_with(loader);
+    assert!(gl::command_ptr("glDebugMessageCallback").is_none());
+    assert!(!gl::load_extension_with(loader, "GL_KHR_debug"));
+    assert_eq!(
+        gl::load_subset_with(loader, &["DebugMessageCallback"]),
+        Err(vec!["DebugMessageCallback"])
+    );
+}
+
+#[cfg(feature = "GL_KHR_debug")]
+#[test]
+fn test_enabled_debug_extension() {
+    gl::load_with(loader);
+    assert!(gl::DebugMessageCallback::is_loaded());
+    assert!(gl::GL_KHR_debug::is_loaded());
+}
+
+#[test]
+fn test_core_commands() {
+    gl::load_with(loader);
+    assert!(gl::Clear::is_loaded());
+    assert!(gl::try_load_with(|symbol| Ok::<_, ()>(loader(symbol))).is_ok());
+}
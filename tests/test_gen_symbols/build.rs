@@ -22,7 +22,7 @@ use std::path::*;
 
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
-    let mut file = File::create(&Path::new(&dest).join("test_gen_symbols.rs")).unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_gen_symbols.rs")).unwrap();
 
     writeln!(&mut file, "mod gl {{").unwrap();
     Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
@@ -65,6 +65,31 @@ fn main() {
         .write_bindings(GlobalGenerator, &mut file)
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
+
+    writeln!(
+        &mut file,
+        "mod egl_native {{ #![allow(dead_code)] {} pub enum wl_egl_window {{}}",
+        build_egl_symbols()
+    )
+    .unwrap();
+    let options = GeneratorOptions::default()
+        .type_override("EGLNativeWindowType", "*mut super::wl_egl_window");
+    Registry::new(Api::Egl, (1, 5), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod xlib {{ {} }}", build_xlib_symbols()).unwrap();
+
+    writeln!(&mut file, "mod glx_xlib {{").unwrap();
+    let options = GeneratorOptions {
+        xlib_path: Some("::xlib".to_string()),
+        ..Default::default()
+    };
+    Registry::new(Api::Glx, (1, 4), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
 }
 
 fn build_egl_symbols() -> &'static str {
@@ -73,7 +98,7 @@ fn build_egl_symbols() -> &'static str {
 
         use std::os::raw;
 
-        pub type khronos_utime_nanoseconds_t = raw::c_int;
+        pub type khronos_utime_nanoseconds_t = u64;
         pub type khronos_uint64_t = u64;
         pub type khronos_ssize_t = isize;
         pub type EGLNativeDisplayType = *const raw::c_void;
@@ -85,3 +110,36 @@ fn build_egl_symbols() -> &'static str {
         pub type NativeWindowType = *const raw::c_void;
     "
 }
+
+/// The Xlib types as the `x11` crate defines them.
+fn build_xlib_symbols() -> &'static str {
+    "
+        #![allow(dead_code, non_snake_case, clippy::upper_case_acronyms)]
+
+        use std::os::raw;
+
+        pub type XID = raw::c_ulong;
+        pub type Bool = raw::c_int;
+        pub enum _XDisplay {}
+        pub type Display = _XDisplay;
+        pub type Font = XID;
+        pub type Pixmap = XID;
+        pub enum Visual {}
+        pub type VisualID = raw::c_ulong;
+        pub type Window = XID;
+
+        #[repr(C)]
+        pub struct XVisualInfo {
+            pub visual: *mut Visual,
+            pub visualid: VisualID,
+            pub screen: raw::c_int,
+            pub depth: raw::c_int,
+            pub class: raw::c_int,
+            pub red_mask: raw::c_ulong,
+            pub green_mask: raw::c_ulong,
+            pub blue_mask: raw::c_ulong,
+            pub colormap_size: raw::c_int,
+            pub bits_per_rgb: raw::c_int,
+        }
+    "
+}
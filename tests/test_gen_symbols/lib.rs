@@ -54,18 +54,25 @@ pub fn compile_test_glsc2() {
 
 pub fn compile_test_glx() {
     unsafe {
-        let _ = glx::GetProcAddress(std::mem::MaybeUninit::uninit().assume_init());
-        glx::SwapBuffers(
-            std::mem::MaybeUninit::uninit().assume_init(),
-            std::mem::MaybeUninit::uninit().assume_init(),
-        );
+        let _ = glx::GetProcAddress(std::ptr::null());
+        glx::SwapBuffers(std::ptr::null_mut(), 0);
+    }
+}
+
+pub fn compile_test_glx_xlib() {
+    unsafe {
+        let display: *mut xlib::Display = glx_xlib::GetCurrentDisplay();
+        let visual: *mut xlib::XVisualInfo =
+            glx_xlib::ChooseVisual(display, 0, std::ptr::null_mut());
+        let _: xlib::Bool =
+            glx_xlib::QueryExtension(display, std::ptr::null_mut(), std::ptr::null_mut());
+        let _ = glx_xlib::CreateContext(display, visual, std::ptr::null(), 1);
     }
 }
 
 pub fn compile_test_wgl() {
     unsafe {
-        let _: wgl::types::HGLRC =
-            wgl::CreateContext(std::mem::MaybeUninit::uninit().assume_init());
+        let _: wgl::types::HGLRC = wgl::CreateContext(std::ptr::null());
     }
 }
 
@@ -84,6 +91,19 @@ pub fn compile_test_egl() {
         ];
 
         let _ = egl::GetDisplay(egl::DEFAULT_DISPLAY);
-        egl::Terminate(std::mem::MaybeUninit::uninit().assume_init());
+        egl::Terminate(std::ptr::null());
+    }
+}
+
+pub fn compile_test_egl_type_override() {
+    unsafe {
+        let window: *mut egl_native::wl_egl_window = std::ptr::null_mut();
+        let _: egl_native::types::EGLNativeWindowType = window;
+        let _ = egl_native::CreateWindowSurface(
+            std::ptr::null(),
+            std::ptr::null(),
+            window,
+            std::ptr::null(),
+        );
     }
 }
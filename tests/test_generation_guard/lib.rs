@@ -0,0 +1,54 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod gl {
+    include!(concat!(env!("OUT_DIR"), "/test_generation_guard.rs"));
+}
+
+// The bindings are global, so the whole sequence runs in a single test.
+#[test]
+fn test_unload_and_reload() {
+    use std::os::raw;
+    use std::panic;
+
+    extern "system" fn get_error() -> gl::types::GLenum {
+        gl::NO_ERROR
+    }
+
+    fn loader(name: &str) -> *const raw::c_void {
+        match name {
+            "glGetError" => get_error as *const raw::c_void,
+            _ => std::ptr::null(),
+        }
+    }
+
+    gl::load_with(loader);
+    assert!(gl::GetError::is_loaded());
+    assert_eq!(unsafe { gl::GetError() }, gl::NO_ERROR);
+
+    gl::unload();
+    assert!(!gl::GetError::is_loaded());
+    // Without the check the call would reach `missing_fn_panic` through an `extern` pointer,
+    // which can't unwind.
+    if cfg!(debug_assertions) {
+        let error = panic::catch_unwind(|| unsafe { gl::GetError() }).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<String>().unwrap(),
+            "glGetError was called after `unload` and before the bindings were loaded again"
+        );
+    }
+
+    gl::load_with(loader);
+    assert_eq!(unsafe { gl::GetError() }, gl::NO_ERROR);
+}
@@ -0,0 +1,73 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate gl_generator;
+
+use gl_generator::*;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::*;
+
+/// The classes that newer registries give the object names of a few commands, as the command,
+/// the parameter, or `None` for the return value, and the class.
+const CLASSES: &[(&str, Option<&str>, &str)] = &[
+    ("BindBuffer", Some("buffer"), "buffer"),
+    ("BindTexture", Some("texture"), "texture"),
+    ("CreateProgram", None, "program"),
+    ("DeleteTextures", Some("textures"), "texture"),
+    ("GenBuffers", Some("buffers"), "buffer"),
+    ("UseProgram", Some("program"), "program"),
+];
+
+fn main() {
+    let dest = env::var("OUT_DIR").unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_handle_types.rs")).unwrap();
+
+    let mut registry = Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, []);
+    registry.cmds = registry
+        .cmds
+        .into_iter()
+        .map(|mut cmd| {
+            for &(name, param, class) in CLASSES {
+                if cmd.proto.ident != name {
+                    continue;
+                }
+                let binding = match param {
+                    Some(param) => cmd.params.iter_mut().find(|p| p.ident == param).unwrap(),
+                    None => &mut cmd.proto,
+                };
+                binding.class = Some(class.to_string());
+            }
+            cmd
+        })
+        .collect();
+
+    let options = GeneratorOptions {
+        handle_types: true,
+        ..Default::default()
+    };
+
+    writeln!(&mut file, "#[deny(warnings)] pub mod gl_global {{").unwrap();
+    registry
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "#[deny(warnings)] pub mod gl_struct {{").unwrap();
+    registry
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+}
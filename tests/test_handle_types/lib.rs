@@ -0,0 +1,111 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+include!(concat!(env!("OUT_DIR"), "/test_handle_types.rs"));
+
+#[cfg(test)]
+mod fake {
+    use gl_struct::types::*;
+    use std::cell::RefCell;
+    use std::os::raw;
+    use std::slice;
+
+    thread_local! {
+        /// The calls received by the fake commands, formatted as strings.
+        pub static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record(call: String) {
+        CALLS.with(|calls| calls.borrow_mut().push(call));
+    }
+
+    /// Returns and clears the calls recorded on this thread.
+    pub fn take_calls() -> Vec<String> {
+        CALLS.with(|calls| calls.borrow_mut().drain(..).collect())
+    }
+
+    extern "system" fn bind_texture(target: GLenum, texture: GLuint) {
+        record(format!("BindTexture({:#x}, {})", target, texture));
+    }
+
+    extern "system" fn create_program() -> GLuint {
+        3
+    }
+
+    extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+        let buffers = unsafe { slice::from_raw_parts_mut(buffers, n as usize) };
+        for (i, buffer) in buffers.iter_mut().enumerate() {
+            *buffer = i as GLuint + 1;
+        }
+    }
+
+    extern "system" fn use_program(program: GLuint) {
+        record(format!("UseProgram({})", program));
+    }
+
+    pub fn loader(name: &str) -> *const raw::c_void {
+        match name {
+            "glBindTexture" => bind_texture as *const raw::c_void,
+            "glCreateProgram" => create_program as *const raw::c_void,
+            "glGenBuffers" => gen_buffers as *const raw::c_void,
+            "glUseProgram" => use_program as *const raw::c_void,
+            _ => std::ptr::null(),
+        }
+    }
+}
+
+#[test]
+fn test_struct_typed() {
+    use gl_struct::typed;
+    use gl_struct::types::handles::{Buffer, Program, Texture};
+
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    let program = unsafe { typed::CreateProgram(&gl) };
+    assert_eq!(program, Program(3));
+
+    let mut buffers = [Buffer::default(); 2];
+    unsafe {
+        typed::GenBuffers(&gl, 2, buffers.as_mut_ptr());
+        typed::UseProgram(&gl, program);
+        typed::BindTexture(&gl, gl_struct::TEXTURE_2D, Texture(4));
+    }
+    assert_eq!(buffers, [Buffer(1), Buffer(2)]);
+    assert_eq!(
+        fake::take_calls(),
+        ["UseProgram(3)", "BindTexture(0xde1, 4)"]
+    );
+}
+
+#[test]
+fn test_global_typed() {
+    use gl_global::types::handles::Program;
+
+    gl_global::load_with(fake::loader);
+    unsafe {
+        let program = gl_global::typed::CreateProgram();
+        gl_global::typed::UseProgram(program);
+        gl_global::UseProgram(Program(5).0);
+    }
+    assert_eq!(fake::take_calls(), ["UseProgram(3)", "UseProgram(5)"]);
+}
+
+#[test]
+fn test_handle_layout() {
+    use gl_global::types::handles::Texture;
+    use gl_global::types::GLuint;
+    use std::mem;
+
+    assert_eq!(mem::size_of::<Texture>(), mem::size_of::<GLuint>());
+    assert_eq!(mem::align_of::<Texture>(), mem::align_of::<GLuint>());
+}
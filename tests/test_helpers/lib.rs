@@ -0,0 +1,723 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+include!(concat!(env!("OUT_DIR"), "/test_helpers.rs"));
+
+#[cfg(test)]
+mod fake {
+    use gl_struct::types::*;
+    use std::cell::RefCell;
+    use std::os::raw;
+    use std::slice;
+
+    thread_local! {
+        /// The calls received by the fake commands, formatted as strings.
+        pub static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+
+        /// The errors that the fake `GetError` returns, in order.
+        pub static ERRORS: RefCell<Vec<GLenum>> = const { RefCell::new(Vec::new()) };
+
+        /// The label that the fake `ObjectLabel` sets, followed by its terminating NUL.
+        pub static LABEL: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+
+        /// The contents of the buffer that the fake `MapBufferRange` maps, aligned for `f32`.
+        pub static BUFFER: RefCell<[f32; 4]> = const { RefCell::new([0.0, 1.0, 2.0, 3.0]) };
+    }
+
+    pub fn record(call: String) {
+        CALLS.with(|calls| calls.borrow_mut().push(call));
+    }
+
+    /// Returns and clears the calls recorded on this thread.
+    pub fn take_calls() -> Vec<String> {
+        CALLS.with(|calls| calls.borrow_mut().drain(..).collect())
+    }
+
+    /// Makes the fake `GetError` return `error` once the previous errors have been returned.
+    pub fn push_error(error: GLenum) {
+        ERRORS.with(|errors| errors.borrow_mut().push(error));
+    }
+
+    extern "system" fn get_error() -> GLenum {
+        ERRORS.with(|errors| {
+            let mut errors = errors.borrow_mut();
+            if errors.is_empty() {
+                0
+            } else {
+                errors.remove(0)
+            }
+        })
+    }
+
+    extern "system" fn create_program() -> GLuint {
+        0
+    }
+
+    extern "system" fn create_shader(ty: GLenum) -> GLuint {
+        record(format!("CreateShader({:#x})", ty));
+        7
+    }
+
+    /// Knows `MAX_TEXTURE_SIZE`, `MAX_VIEWPORT_DIMS`, `MAX_VERTEX_ATTRIBS`, `MAX_LABEL_LENGTH`
+    /// and `MAX_DEBUG_GROUP_STACK_DEPTH`.
+    extern "system" fn get_integerv(pname: GLenum, data: *mut GLint) {
+        let values: &[GLint] = match pname {
+            0x0d33 => &[4096],
+            0x0d3a => &[8192, 4096],
+            0x8869 => &[16],
+            0x82e8 => &[8],
+            0x826c => &[3],
+            _ => &[],
+        };
+        let data = unsafe { slice::from_raw_parts_mut(data, values.len()) };
+        data.copy_from_slice(values);
+    }
+
+    extern "system" fn uniform1i(location: GLint, v0: GLint) {
+        record(format!("Uniform1i({}, {})", location, v0));
+    }
+
+    extern "system" fn uniform1fv(location: GLint, count: GLsizei, value: *const GLfloat) {
+        let value = unsafe { slice::from_raw_parts(value, count as usize) };
+        record(format!("Uniform1fv({}, {:?})", location, value));
+    }
+
+    extern "system" fn uniform4fv(location: GLint, count: GLsizei, value: *const GLfloat) {
+        let value = unsafe { slice::from_raw_parts(value, count as usize * 4) };
+        record(format!("Uniform4fv({}, {}, {:?})", location, count, value));
+    }
+
+    extern "system" fn uniform3uiv(location: GLint, count: GLsizei, value: *const GLuint) {
+        let value = unsafe { slice::from_raw_parts(value, count as usize * 3) };
+        record(format!("Uniform3uiv({}, {}, {:?})", location, count, value));
+    }
+
+    extern "system" fn uniform_matrix2x3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    ) {
+        let value = unsafe { slice::from_raw_parts(value, count as usize * 6) };
+        record(format!(
+            "UniformMatrix2x3fv({}, {}, {}, {:?})",
+            location, count, transpose, value
+        ));
+    }
+
+    extern "system" fn uniform_matrix4fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    ) {
+        let value = unsafe { slice::from_raw_parts(value, count as usize * 16) };
+        record(format!(
+            "UniformMatrix4fv({}, {}, {}, {:?})",
+            location, count, transpose, value
+        ));
+    }
+
+    extern "system" fn object_label(
+        identifier: GLenum,
+        name: GLuint,
+        length: GLsizei,
+        label: *const GLchar,
+    ) {
+        let label = unsafe { slice::from_raw_parts(label as *const u8, length as usize) };
+        record(format!(
+            "ObjectLabel({:#x}, {}, {:?})",
+            identifier,
+            name,
+            String::from_utf8_lossy(label)
+        ));
+        LABEL.with(|stored| {
+            let mut stored = stored.borrow_mut();
+            stored.clear();
+            stored.extend_from_slice(label);
+            stored.push(0);
+        });
+    }
+
+    /// Writes the stored label with its NUL, and counts the NUL in `length` like some drivers.
+    extern "system" fn get_object_label(
+        _: GLenum,
+        _: GLuint,
+        buf_size: GLsizei,
+        length: *mut GLsizei,
+        label: *mut GLchar,
+    ) {
+        LABEL.with(|stored| {
+            let stored = stored.borrow();
+            let count = stored.len().min(buf_size as usize);
+            unsafe {
+                let label = slice::from_raw_parts_mut(label as *mut u8, count);
+                label.copy_from_slice(&stored[..count]);
+                *length = count as GLsizei;
+            }
+        });
+    }
+
+    extern "system" fn push_debug_group(
+        source: GLenum,
+        id: GLuint,
+        length: GLsizei,
+        message: *const GLchar,
+    ) {
+        let message = unsafe { slice::from_raw_parts(message as *const u8, length as usize) };
+        record(format!(
+            "PushDebugGroup({:#x}, {}, {:?})",
+            source,
+            id,
+            String::from_utf8_lossy(message)
+        ));
+    }
+
+    extern "system" fn pop_debug_group() {
+        record("PopDebugGroup()".to_string());
+    }
+
+    extern "system" fn tex_image_2d(
+        target: GLenum,
+        level: GLint,
+        internalformat: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        border: GLint,
+        format: GLenum,
+        ty: GLenum,
+        _: *const raw::c_void,
+    ) {
+        record(format!(
+            "TexImage2D({:#x}, {}, {:#x}, {}, {}, {}, {:#x}, {:#x})",
+            target, level, internalformat, width, height, border, format, ty
+        ));
+    }
+
+    extern "system" fn shader_source(
+        shader: GLuint,
+        count: GLsizei,
+        string: *const *const GLchar,
+        length: *const GLint,
+    ) {
+        let strings = unsafe { slice::from_raw_parts(string, count as usize) };
+        let lengths = unsafe { slice::from_raw_parts(length, count as usize) };
+        let srcs: Vec<_> = strings
+            .iter()
+            .zip(lengths)
+            .map(|(&string, &length)| {
+                let bytes = unsafe { slice::from_raw_parts(string as *const u8, length as usize) };
+                String::from_utf8(bytes.to_vec()).unwrap()
+            })
+            .collect();
+        record(format!("ShaderSource({}, {:?})", shader, srcs));
+    }
+
+    /// Calls the new callback right away with two messages, one of them NUL-terminated.
+    extern "system" fn debug_message_callback(
+        callback: GLDEBUGPROC,
+        user_param: *const raw::c_void,
+    ) {
+        let callback = callback.unwrap();
+        let user_param = user_param as *mut raw::c_void;
+        let message = "out of range";
+        callback(
+            ::gl_struct::DEBUG_SOURCE_API,
+            ::gl_struct::DEBUG_TYPE_ERROR,
+            1,
+            ::gl_struct::DEBUG_SEVERITY_HIGH,
+            message.len() as GLsizei,
+            message.as_ptr() as *const GLchar,
+            user_param,
+        );
+        callback(
+            0x1234,
+            ::gl_struct::DEBUG_TYPE_MARKER,
+            2,
+            ::gl_struct::DEBUG_SEVERITY_NOTIFICATION,
+            -1,
+            b"marker\0".as_ptr() as *const GLchar,
+            user_param,
+        );
+    }
+
+    extern "system" fn map_buffer_range(
+        target: GLenum,
+        offset: GLintptr,
+        length: GLsizeiptr,
+        access: GLbitfield,
+    ) -> *mut raw::c_void {
+        record(format!(
+            "MapBufferRange({:#x}, {}, {}, {:#x})",
+            target, offset, length, access
+        ));
+        let buffer = BUFFER.with(|buffer| buffer.as_ptr());
+        unsafe { (buffer as *mut u8).offset(offset) as *mut raw::c_void }
+    }
+
+    pub fn loader(name: &str) -> *const raw::c_void {
+        match name {
+            "glCreateProgram" => create_program as *const raw::c_void,
+            "glCreateShader" => create_shader as *const raw::c_void,
+            "glDebugMessageCallback" => debug_message_callback as *const raw::c_void,
+            "glGetError" => get_error as *const raw::c_void,
+            "glGetIntegerv" => get_integerv as *const raw::c_void,
+            "glGetObjectLabel" => get_object_label as *const raw::c_void,
+            "glMapBufferRange" => map_buffer_range as *const raw::c_void,
+            "glObjectLabel" => object_label as *const raw::c_void,
+            "glPopDebugGroup" => pop_debug_group as *const raw::c_void,
+            "glPushDebugGroup" => push_debug_group as *const raw::c_void,
+            "glShaderSource" => shader_source as *const raw::c_void,
+            "glTexImage2D" => tex_image_2d as *const raw::c_void,
+            "glUniform1i" => uniform1i as *const raw::c_void,
+            "glUniform1fv" => uniform1fv as *const raw::c_void,
+            "glUniform4fv" => uniform4fv as *const raw::c_void,
+            "glUniform3uiv" => uniform3uiv as *const raw::c_void,
+            "glUniformMatrix2x3fv" => uniform_matrix2x3fv as *const raw::c_void,
+            "glUniformMatrix4fv" => uniform_matrix4fv as *const raw::c_void,
+            _ => std::ptr::null(),
+        }
+    }
+}
+
+#[test]
+fn test_struct_set_uniform() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+
+    gl.set_uniform(1, &7);
+    gl.set_uniform(2, &[0.5f32, 1.5][..]);
+    gl.set_uniform(3, &[1.0f32, 2.0, 3.0, 4.0]);
+    gl.set_uniform(4, &[[1u32, 2, 3], [4, 5, 6]][..]);
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "Uniform1i(1, 7)",
+            "Uniform1fv(2, [0.5, 1.5])",
+            "Uniform4fv(3, 1, [1.0, 2.0, 3.0, 4.0])",
+            "Uniform3uiv(4, 2, [1, 2, 3, 4, 5, 6])",
+        ]
+    );
+}
+
+#[test]
+fn test_global_set_uniform() {
+    gl_global::load_with(fake::loader);
+
+    gl_global::set_uniform(5, &[[0.0f32, 0.25, 0.5, 0.75]][..]);
+    gl_global::set_uniform(6, &-1);
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "Uniform4fv(5, 1, [0.0, 0.25, 0.5, 0.75])",
+            "Uniform1i(6, -1)"
+        ]
+    );
+}
+
+#[test]
+fn test_uniform_matrix() {
+    let identity = [
+        [1.0f32, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    gl.uniform_matrix4fv(1, false, &identity);
+    gl.uniform_matrix2x3fv_slice(2, true, &[[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]; 2]);
+
+    gl_global::load_with(fake::loader);
+    gl_global::uniform_matrix4fv_slice(3, true, &[]);
+    gl_global::uniform_matrix2x3fv(4, false, &[[0.5, 1.5, 2.5], [3.5, 4.5, 5.5]]);
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "UniformMatrix4fv(1, 1, 0, [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, \
+             0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0])",
+            "UniformMatrix2x3fv(2, 2, 1, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, \
+             1.0, 2.0, 3.0, 4.0, 5.0, 6.0])",
+            "UniformMatrix4fv(3, 0, 1, [])",
+            "UniformMatrix2x3fv(4, 1, 0, [0.5, 1.5, 2.5, 3.5, 4.5, 5.5])",
+        ]
+    );
+}
+
+#[test]
+fn test_internal_format_try_from() {
+    use gl_struct::internal_format::InternalFormat;
+    use std::convert::TryFrom;
+
+    assert_eq!(
+        InternalFormat::try_from(gl_struct::RGBA8),
+        Ok(InternalFormat::RGBA8)
+    );
+    assert_eq!(
+        InternalFormat::try_from(gl_struct::DEPTH24_STENCIL8),
+        Ok(InternalFormat::DEPTH24_STENCIL8)
+    );
+    assert_eq!(
+        InternalFormat::try_from(gl_struct::TRIANGLES),
+        Err(gl_struct::TRIANGLES)
+    );
+    assert_eq!(
+        gl_struct::types::GLenum::from(InternalFormat::R11F_G11F_B10F),
+        gl_struct::R11F_G11F_B10F
+    );
+}
+
+#[test]
+fn test_tex_image_2d() {
+    use gl_struct::internal_format::InternalFormat;
+
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    unsafe {
+        gl.tex_image_2d(
+            gl_struct::TEXTURE_2D,
+            0,
+            InternalFormat::RGBA8,
+            4,
+            2,
+            0,
+            gl_struct::RGBA,
+            gl_struct::UNSIGNED_BYTE,
+            std::ptr::null(),
+        );
+    }
+
+    gl_global::load_with(fake::loader);
+    unsafe {
+        gl_global::tex_image_2d(
+            gl_global::TEXTURE_2D,
+            1,
+            gl_global::internal_format::InternalFormat::DEPTH24_STENCIL8,
+            8,
+            8,
+            0,
+            gl_global::DEPTH_STENCIL,
+            gl_global::UNSIGNED_INT_24_8,
+            std::ptr::null(),
+        );
+    }
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "TexImage2D(0xde1, 0, 0x8058, 4, 2, 0, 0x1908, 0x1401)",
+            "TexImage2D(0xde1, 1, 0x88f0, 8, 8, 0, 0x84f9, 0x84fa)",
+        ]
+    );
+}
+
+#[test]
+fn test_shader_source() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    gl.shader_source(1, "void main() {}");
+    gl.shader_sources(2, &["#version 330\n", "void main() {}"]);
+
+    gl_global::load_with(fake::loader);
+    gl_global::shader_source(3, "");
+    gl_global::shader_sources(4, &[]);
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "ShaderSource(1, [\"void main() {}\"])",
+            "ShaderSource(2, [\"#version 330\\n\", \"void main() {}\"])",
+            "ShaderSource(3, [\"\"])",
+            "ShaderSource(4, [])",
+        ]
+    );
+}
+
+#[test]
+fn test_gl_error() {
+    use gl_struct::error::GlError;
+    use std::convert::TryFrom;
+
+    assert_eq!(
+        GlError::try_from(gl_struct::INVALID_OPERATION),
+        Ok(GlError::InvalidOperation)
+    );
+    assert_eq!(
+        GlError::try_from(gl_struct::NO_ERROR),
+        Err(gl_struct::NO_ERROR)
+    );
+    assert_eq!(
+        gl_struct::types::GLenum::from(GlError::OutOfMemory),
+        gl_struct::OUT_OF_MEMORY
+    );
+    assert_eq!(
+        GlError::InvalidOperation.to_string(),
+        "invalid operation: the command is not legal for the current state"
+    );
+
+    let error: Box<dyn std::error::Error> =
+        gl_global::error::GlError::InvalidFramebufferOperation.into();
+    assert_eq!(
+        format!("{}", error),
+        "invalid framebuffer operation: the framebuffer object is not complete"
+    );
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "GL_INVALID_VALUE, 0x8031 (uploading) at ")
+)]
+fn test_check_error() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    assert!(gl.check_error("idle").is_empty());
+
+    fake::push_error(gl_struct::INVALID_VALUE);
+    fake::push_error(0x8031);
+    assert_eq!(
+        gl.check_error("uploading"),
+        [gl_struct::INVALID_VALUE, 0x8031]
+    );
+}
+
+#[test]
+fn test_check_gl_error() {
+    // The macro is exported and doesn't need `check_error` to be imported.
+    gl_global::load_with(fake::loader);
+    check_gl_error!();
+    check_gl_error!("drawing");
+
+    fake::push_error(gl_global::INVALID_ENUM);
+    let errors = std::panic::catch_unwind(|| check_gl_error!("binding"));
+    assert_eq!(errors.is_err(), cfg!(debug_assertions));
+    if !cfg!(debug_assertions) {
+        assert_eq!(gl_global::check_error("idle"), [gl_global::INVALID_ENUM]);
+    }
+    assert_eq!(gl_global::check_error("idle"), []);
+}
+
+#[test]
+fn test_load_debug_callback() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    gl.load_debug_callback(|source, ty, severity, message| {
+        fake::record(format!("{:?} {:?} {:?} {}", source, ty, severity, message))
+    });
+
+    gl_global::load_with(fake::loader);
+    gl_global::load_debug_callback(|_, _, _, message| panic!("{}", message));
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "Api Error High out of range",
+            "Unknown(4660) Marker Notification marker",
+        ]
+    );
+}
+
+#[test]
+fn test_typed_get_error() {
+    use gl_typed_struct::error::GlError;
+    use gl_typed_struct::types::GLenum;
+
+    let gl = gl_typed_struct::Gl::load_with(fake::loader);
+    fake::push_error(gl_typed_struct::INVALID_VALUE);
+    fake::push_error(0x8031);
+    match unsafe { gl.GetError() } {
+        GlError::InvalidValue => {},
+        error => panic!("unexpected error {:?}", error),
+    }
+    assert_eq!(unsafe { gl.GetError() }, GlError::Other(0x8031));
+    assert_eq!(unsafe { gl.GetError() }, GlError::NoError);
+
+    fake::push_error(gl_typed_struct::INVALID_ENUM);
+    assert_eq!(unsafe { gl.GetErrorRaw() }, gl_typed_struct::INVALID_ENUM);
+
+    assert_eq!(GlError::from(gl_typed_struct::NO_ERROR), GlError::NoError);
+    assert_eq!(GLenum::from(GlError::NoError), gl_typed_struct::NO_ERROR);
+    assert_eq!(GLenum::from(GlError::Other(0x8031)), 0x8031);
+    assert_eq!(GlError::NoError.to_string(), "no error");
+    assert_eq!(GlError::Other(0x8031).to_string(), "unknown error 0x8031");
+
+    gl_typed_global::load_with(fake::loader);
+    fake::push_error(gl_typed_global::OUT_OF_MEMORY);
+    assert_eq!(
+        unsafe { gl_typed_global::GetError() },
+        gl_typed_global::error::GlError::OutOfMemory
+    );
+}
+
+#[test]
+fn test_nonzero_create() {
+    let gl = gl_typed_struct::Gl::load_with(fake::loader);
+    assert_eq!(unsafe { gl.TryCreateProgram() }, None);
+    assert_eq!(unsafe { gl.CreateProgram() }, 0);
+    assert_eq!(
+        unsafe { gl.TryCreateShader(gl_typed_struct::VERTEX_SHADER) }.map(|shader| shader.get()),
+        Some(7)
+    );
+
+    gl_typed_global::load_with(fake::loader);
+    assert_eq!(unsafe { gl_typed_global::TryCreateProgram() }, None);
+    assert_eq!(
+        unsafe { gl_typed_global::TryCreateShader(gl_typed_global::FRAGMENT_SHADER) }
+            .map(|shader| shader.get()),
+        Some(7)
+    );
+
+    assert_eq!(
+        fake::take_calls(),
+        ["CreateShader(0x8b31)", "CreateShader(0x8b30)"]
+    );
+}
+
+#[test]
+fn test_alias_shims() {
+    let gl = gl_typed_struct::Gl::load_with(fake::loader);
+    unsafe { gl.Uniform1iARB(1, 2) };
+
+    gl_typed_global::load_with(fake::loader);
+    unsafe { gl_typed_global::Uniform1iARB(3, 4) };
+
+    assert_eq!(fake::take_calls(), ["Uniform1i(1, 2)", "Uniform1i(3, 4)"]);
+}
+
+#[test]
+fn test_capabilities() {
+    let gl = gl_typed_struct::Gl::load_with(fake::loader);
+    let capabilities = gl_typed_struct::Capabilities::query(&gl);
+    assert_eq!(capabilities.max_texture_size, 4096);
+    assert_eq!(capabilities.max_viewport_dims, [8192, 4096]);
+    assert_eq!(capabilities.max_vertex_attribs, 16);
+    assert_eq!(capabilities.max_samples, 0);
+
+    gl_typed_global::load_with(fake::loader);
+    let capabilities = gl_typed_global::Capabilities::query();
+    assert_eq!(capabilities.max_texture_size, 4096);
+    assert_eq!(capabilities.max_compute_work_group_invocations, 0);
+}
+
+#[test]
+fn test_object_label() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    assert_eq!(gl.object_label(gl_struct::BUFFER, 1), "");
+    gl.label_object(gl_struct::BUFFER, 1, "shadow");
+    assert_eq!(gl.object_label(gl_struct::BUFFER, 1), "shadow");
+
+    gl_global::load_with(fake::loader);
+    gl_global::label_object(gl_global::TEXTURE, 2, "été pass");
+    assert_eq!(gl_global::object_label(gl_global::TEXTURE, 2), "été p");
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "ObjectLabel(0x82e0, 1, \"shadow\")",
+            "ObjectLabel(0x1702, 2, \"été p\")",
+        ]
+    );
+}
+
+#[test]
+fn test_debug_group() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    {
+        let _shadow = gl.debug_group("shadow pass");
+        let _cascade = gl.debug_group("cascade 0");
+    }
+
+    gl_global::load_with(fake::loader);
+    drop(gl_global::debug_group("bloom"));
+
+    assert_eq!(
+        fake::take_calls(),
+        [
+            "PushDebugGroup(0x824a, 0, \"shadow pass\")",
+            "PushDebugGroup(0x824a, 0, \"cascade 0\")",
+            "PopDebugGroup()",
+            "PopDebugGroup()",
+            "PushDebugGroup(0x824a, 0, \"bloom\")",
+            "PopDebugGroup()",
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "nests deeper"))]
+fn test_debug_group_too_deep() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    // The fake `MAX_DEBUG_GROUP_STACK_DEPTH` leaves room for two groups after the default one.
+    let _first = gl.debug_group("first");
+    let _second = gl.debug_group("second");
+    let _third = gl.debug_group("third");
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "reverse order"))]
+fn test_debug_group_out_of_order() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+    let outer = gl.debug_group("outer");
+    let _inner = gl.debug_group("inner");
+    drop(outer);
+}
+
+#[test]
+fn test_map_buffer_range() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+
+    let values = unsafe {
+        gl.map_buffer_range::<f32>(gl_struct::ARRAY_BUFFER, 1, 2, gl_struct::MAP_READ_BIT)
+    };
+    assert_eq!(values, Some(&mut [1.0f32, 2.0][..]));
+    assert_eq!(fake::take_calls(), ["MapBufferRange(0x8892, 4, 8, 0x1)"]);
+}
+
+#[test]
+fn test_map_buffer_range_overflow() {
+    let gl = gl_struct::Gl::load_with(fake::loader);
+
+    let values = unsafe {
+        gl.map_buffer_range::<f32>(
+            gl_struct::ARRAY_BUFFER,
+            0,
+            usize::MAX / 2,
+            gl_struct::MAP_READ_BIT,
+        )
+    };
+    assert_eq!(values, None);
+    let values = unsafe {
+        gl.map_buffer_range::<f32>(
+            gl_struct::ARRAY_BUFFER,
+            usize::MAX / 4,
+            1,
+            gl_struct::MAP_READ_BIT,
+        )
+    };
+    assert_eq!(values, None);
+    assert!(fake::take_calls().is_empty());
+}
+
+#[test]
+fn test_typed_slice_null() {
+    let values = unsafe { gl_global::map::typed_slice::<u32>(std::ptr::null_mut(), 4) };
+    assert_eq!(values, None);
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "is not aligned for `u32`"))]
+fn test_typed_slice_misaligned() {
+    let mut words = [0u32; 2];
+    let misaligned = unsafe { (words.as_mut_ptr() as *mut u8).offset(1) };
+    let values = unsafe { gl_global::map::typed_slice::<u32>(misaligned as *mut _, 1) };
+    assert_eq!(values, None);
+}
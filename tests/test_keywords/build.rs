@@ -0,0 +1,83 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate gl_generator;
+
+use gl_generator::*;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::*;
+
+/// The reserved words of every edition, as the registries may use them as names of parameters.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn binding(ident: &str, ty: &'static str) -> Binding {
+    Binding {
+        ident: ident.to_string(),
+        ty: Cow::Borrowed(ty),
+        c_ty: String::new(),
+        group: None,
+        class: None,
+    }
+}
+
+/// A registry with a `Keywords` command taking a parameter named after each reserved word.
+fn keyword_registry() -> Registry {
+    let mut cmds = BTreeSet::new();
+    cmds.insert(Cmd {
+        proto: binding("Keywords", "()"),
+        params: KEYWORDS
+            .iter()
+            .map(|keyword| binding(keyword, "types::GLint"))
+            .collect(),
+        alias: None,
+        vecequiv: None,
+        glx: None,
+    });
+
+    Registry {
+        api: Api::Gl,
+        version: (1, 0),
+        profile: Profile::Core,
+        extensions: Vec::new(),
+        versions: Vec::new(),
+        enums: BTreeSet::new(),
+        cmds,
+        aliases: BTreeMap::new(),
+        groups: BTreeMap::new(),
+    }
+}
+
+fn main() {
+    let dest = env::var("OUT_DIR").unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_keywords.rs")).unwrap();
+    let registry = keyword_registry();
+
+    writeln!(&mut file, "pub mod gl {{").unwrap();
+    registry.write_bindings(GlobalGenerator, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_struct {{").unwrap();
+    registry.write_bindings(StructGenerator, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+}
@@ -22,7 +22,7 @@ use std::path::*;
 
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
-    let mut file = File::create(&Path::new(&dest).join("test_no_warnings.rs")).unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_no_warnings.rs")).unwrap();
 
     // Gl
 
@@ -58,6 +58,95 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    // Gl with the options that emit extra items
+
+    writeln!(&mut file, "mod gl_global_options {{").unwrap();
+    let options = GeneratorOptions {
+        helpers: true,
+        dispatch_table: true,
+        caching_loader: true,
+        generation_guard: true,
+        registry_docs: true,
+        int_enums: true,
+        typed_get_error: true,
+        nonzero_create: true,
+        capabilities: true,
+        alias_shims: true,
+        loader_trait: true,
+        dedup_loads: true,
+        doc_aliases: true,
+        ..Default::default()
+    };
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_struct_options {{").unwrap();
+    let options = GeneratorOptions {
+        helpers: true,
+        dispatch_table: true,
+        pfn_table: true,
+        shared_struct: true,
+        interceptor: true,
+        thread_local_current: true,
+        typed_get_error: true,
+        loader_trait: true,
+        dedup_loads: true,
+        doc_aliases: true,
+        ..Default::default()
+    };
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_static_options {{").unwrap();
+    let options = GeneratorOptions {
+        dynamic_extensions: true,
+        doc_aliases: true,
+        ..Default::default()
+    };
+    gl_registry
+        .write_bindings_with_options(StaticGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Gl without commands, for which some imports are unused
+
+    let mut empty_registry = Registry::new(Api::Gl, (1, 0), Profile::Core, Fallbacks::All, []);
+    empty_registry.retain_reachable(&["COLOR_BUFFER_BIT"]);
+
+    writeln!(&mut file, "mod empty_global {{").unwrap();
+    empty_registry
+        .write_bindings(GlobalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod empty_static {{").unwrap();
+    empty_registry
+        .write_bindings(StaticGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod empty_struct {{").unwrap();
+    empty_registry
+        .write_bindings(StructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod empty_static_struct {{").unwrap();
+    empty_registry
+        .write_bindings(StaticStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod empty_debug_struct {{").unwrap();
+    empty_registry
+        .write_bindings(DebugStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Glx
 
     let glx_registry = Registry::new(Api::Glx, (1, 4), Profile::Core, Fallbacks::All, []);
@@ -240,7 +329,7 @@ fn build_egl_symbols() -> &'static str {
 
         use std::os::raw;
 
-        pub type khronos_utime_nanoseconds_t = raw::c_int;
+        pub type khronos_utime_nanoseconds_t = u64;
         pub type khronos_uint64_t = u64;
         pub type khronos_ssize_t = isize;
         pub type EGLNativeDisplayType = *const raw::c_void;
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Making sure that no warning is generated by code from generate_gl_bindings!
+//! Making sure that no warning is generated by code from generate_gl_bindings!, including the
+//! lints of clippy when it is run on the workspace, and that the bodies of the generated
+//! `unsafe fn`s wrap their unsafe operations in `unsafe` blocks.
 #![deny(warnings)]
+#![deny(unsafe_op_in_unsafe_fn)]
+#![deny(clippy::all)]
 
 include!(concat!(env!("OUT_DIR"), "/test_no_warnings.rs"));
@@ -0,0 +1,60 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod gl {
+    include!(concat!(env!("OUT_DIR"), "/test_once_lock.rs"));
+}
+
+// The bindings are global and can only be loaded once, so the whole sequence runs in a single
+// test.
+#[test]
+fn test_load_once() {
+    use std::os::raw;
+    use std::thread;
+
+    extern "system" fn get_error() -> gl::types::GLenum {
+        gl::NO_ERROR
+    }
+
+    extern "system" fn other_get_error() -> gl::types::GLenum {
+        gl::INVALID_ENUM
+    }
+
+    assert!(!gl::GetError::is_loaded());
+    assert_eq!(gl::command_ptr("glGetError"), None);
+
+    gl::load_with(|name| match name {
+        "glGetError" => get_error as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    assert!(gl::GetError::is_loaded());
+    assert!(!gl::Clear::is_loaded());
+    assert_eq!(
+        gl::command_ptr("glGetError"),
+        Some(get_error as *const raw::c_void)
+    );
+
+    // Later loads leave the table unchanged.
+    gl::load_with(|name| match name {
+        "glGetError" => other_get_error as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| unsafe { gl::GetError() }))
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), gl::NO_ERROR);
+    }
+}
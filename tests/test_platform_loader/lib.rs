@@ -0,0 +1,55 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+include!(concat!(env!("OUT_DIR"), "/test_platform_loader.rs"));
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_global_load() {
+    match gl_global::load() {
+        Ok(()) => assert!(gl_global::Clear::is_loaded()),
+        Err(error) => assert_eq!(error.libraries, &["libGL.so.1", "libGL.so"]),
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_struct_load() {
+    match gl_struct::Gl::load() {
+        Ok(gl) => assert!(gl.Clear.is_loaded()),
+        Err(error) => assert_eq!(error.libraries, &["libGL.so.1", "libGL.so"]),
+    }
+}
+
+#[test]
+#[cfg(not(windows))]
+fn test_unsupported_platform() {
+    let error = wgl::Wgl::load().err().unwrap();
+    assert!(error.libraries.is_empty());
+    assert_eq!(
+        error.to_string(),
+        "no wgl library is known for this platform"
+    );
+}
+
+#[test]
+fn test_load_error_display() {
+    let error = gl_global::LoadError {
+        libraries: &["libGL.so.1", "libGL.so"],
+    };
+    assert_eq!(
+        error.to_string(),
+        "could not open any of the gl libraries: libGL.so.1, libGL.so"
+    );
+}
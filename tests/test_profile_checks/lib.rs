@@ -0,0 +1,70 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod gl {
+    include!(concat!(env!("OUT_DIR"), "/test_profile_checks.rs"));
+}
+
+// The bindings are global, so the whole sequence runs in a single test.
+#[test]
+fn test_profile_checks() {
+    use gl::profile::{self, ContextProfile};
+    use gl::types::*;
+    use std::os::raw;
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// The `CONTEXT_PROFILE_MASK` that the fake `GetIntegerv` returns.
+    static MASK: AtomicUsize = AtomicUsize::new(0);
+
+    extern "system" fn get_integerv(pname: GLenum, data: *mut GLint) {
+        if pname == gl::CONTEXT_PROFILE_MASK {
+            unsafe { *data = MASK.load(Ordering::SeqCst) as GLint };
+        }
+    }
+
+    extern "system" fn end() {}
+
+    fn loader(name: &str) -> *const raw::c_void {
+        match name {
+            "glGetIntegerv" => get_integerv as *const raw::c_void,
+            "glEnd" => end as *const raw::c_void,
+            _ => std::ptr::null(),
+        }
+    }
+
+    MASK.store(
+        gl::CONTEXT_COMPATIBILITY_PROFILE_BIT as usize,
+        Ordering::SeqCst,
+    );
+    gl::load_with(loader);
+    assert_eq!(profile::active_profile(), ContextProfile::Compatibility);
+    unsafe { gl::End() };
+
+    MASK.store(gl::CONTEXT_CORE_PROFILE_BIT as usize, Ordering::SeqCst);
+    // The profile is only queried again once the bindings are reloaded.
+    assert_eq!(profile::active_profile(), ContextProfile::Compatibility);
+    gl::load_with(loader);
+    assert_eq!(profile::active_profile(), ContextProfile::Core);
+    let error = panic::catch_unwind(|| unsafe { gl::End() }).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<String>().unwrap(),
+        "glEnd was removed from the core profile, and the current context is a core profile context"
+    );
+
+    // The commands of the core profile are unaffected.
+    let mut mask = 0;
+    unsafe { gl::GetIntegerv(gl::CONTEXT_PROFILE_MASK, &mut mask) };
+    assert_eq!(mask, gl::CONTEXT_CORE_PROFILE_BIT as GLint);
+}
@@ -0,0 +1,65 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Stands in for a crate that re-exports the standard library under its own path.
+pub mod reexports {
+    pub use std::error;
+    pub use std::ffi;
+    pub use std::fmt;
+    pub use std::marker;
+    pub use std::mem;
+    pub use std::println;
+    pub use std::ptr;
+    pub use std::vec;
+
+    pub mod os {
+        pub use std::os::raw;
+    }
+}
+
+/// Stands in for a crate that re-exports every item of the standard library that the options can
+/// use.
+pub mod full_reexports {
+    pub use std::{
+        any, boxed, cell, collections, convert, error, ffi, fmt, marker, mem, num, ops, panic, ptr,
+        slice, string, sync, thread, time, vec,
+    };
+    pub use std::{format, println, thread_local};
+
+    pub mod os {
+        pub use std::os::raw;
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/test_std_path.rs"));
+
+#[test]
+fn test_types_use_custom_path() {
+    let _: reexports::os::raw::c_uint = gl_global::types::GLenum::default();
+    let _: reexports::os::raw::c_uint = gl_struct::types::GLenum::default();
+    let _: reexports::os::raw::c_uint = gl_debug_struct::types::GLenum::default();
+    let _: full_reexports::os::raw::c_uint = gl_helpers::types::GLenum::default();
+}
+
+#[test]
+fn test_load_with() {
+    gl_global::load_with(|_| std::ptr::null());
+    assert!(!gl_global::Clear::is_loaded());
+
+    let gl = gl_struct::Gl::load_with(|_| std::ptr::null());
+    assert!(!gl.Clear.is_loaded());
+
+    let gl = gl_debug_struct::Gl::load_with(|_| std::ptr::null());
+    assert!(!gl.Clear.is_loaded());
+}
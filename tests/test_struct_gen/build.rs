@@ -0,0 +1,394 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate gl_generator;
+
+use gl_generator::*;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::*;
+
+fn main() {
+    let dest = env::var("OUT_DIR").unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_struct_gen.rs")).unwrap();
+
+    let registry = Registry::new(
+        Api::Gl,
+        (4, 6),
+        Profile::Core,
+        Fallbacks::All,
+        ["GL_ARB_debug_output"],
+    );
+
+    writeln!(&mut file, "pub mod gl {{").unwrap();
+    registry.write_bindings(StructGenerator, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Only `gl` holds the whole registry. The modules of the options are generated for the
+    // commands that their tests use, so that the crate doesn't take minutes to compile.
+    let mut small = registry.clone();
+    small.retain_reachable(&[
+        "BindVertexArray",
+        "ClientWaitSync",
+        "Clear",
+        "CullFace",
+        "DebugMessageCallback",
+        "DebugMessageCallbackARB",
+        "DebugMessageInsert",
+        "DebugMessageInsertARB",
+        "DeleteQueries",
+        "DispatchCompute",
+        "DrawArrays",
+        "FenceSync",
+        "GenQueries",
+        "GetError",
+        "GetQueryObjectiv",
+        "GetQueryObjectui64v",
+        "GetString",
+        "LinkProgram",
+        "QueryCounter",
+        "UseProgram",
+        "Viewport",
+    ]);
+
+    writeln!(&mut file, "pub mod gl_vendor_suffixes {{").unwrap();
+    let options = GeneratorOptions {
+        vendor_suffixes: vec!["ARB".to_string(), "EXT".to_string()],
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_dispatch {{").unwrap();
+    let options = GeneratorOptions {
+        dispatch_table: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_debug_feature {{").unwrap();
+    let options = GeneratorOptions {
+        debug_feature: Some("gl_debug".to_string()),
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_caching_loader {{").unwrap();
+    let options = GeneratorOptions {
+        caching_loader: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_dedup_loads {{").unwrap();
+    let options = GeneratorOptions {
+        dedup_loads: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_loader_trait {{").unwrap();
+    let options = GeneratorOptions {
+        caching_loader: true,
+        loader_trait: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_prelude {{").unwrap();
+    let options = GeneratorOptions {
+        prelude: Some(Prelude::TypesAndCommands),
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_split_extensions {{").unwrap();
+    let options = GeneratorOptions {
+        split_extensions: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_skip_extensions {{").unwrap();
+    let options = GeneratorOptions {
+        skip_extensions: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_shared_struct {{").unwrap();
+    let options = GeneratorOptions {
+        shared_struct: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_interceptor {{").unwrap();
+    let options = GeneratorOptions {
+        interceptor: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_skip_send_impl {{").unwrap();
+    let options = GeneratorOptions {
+        skip_send_impl: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_typed_fn_ptrs {{").unwrap();
+    let options = GeneratorOptions {
+        typed_fn_ptrs: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_pfn_table {{").unwrap();
+    let options = GeneratorOptions {
+        pfn_table: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_opaque_sync {{").unwrap();
+    let options = GeneratorOptions {
+        opaque_sync: true,
+        dispatch_table: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_reachable {{").unwrap();
+    let mut reachable = registry.clone();
+    reachable.retain_reachable(&["Clear", "DrawArrays", "glGetError"]);
+    reachable
+        .write_bindings(StructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_global {{").unwrap();
+    reachable
+        .write_bindings(GlobalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_global_bridge {{").unwrap();
+    let options = GeneratorOptions {
+        global_bridge: Some("super::gl_global".to_string()),
+        ..Default::default()
+    };
+    reachable
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_current {{").unwrap();
+    let options = GeneratorOptions {
+        thread_local_current: true,
+        ..Default::default()
+    };
+    reachable
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let options = GeneratorOptions {
+        dispatch_bench: true,
+        ..Default::default()
+    };
+
+    writeln!(&mut file, "pub mod gl_bench {{").unwrap();
+    reachable
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_global_bench {{").unwrap();
+    reachable
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_gpu_timing {{").unwrap();
+    let options = GeneratorOptions {
+        gpu_timing: vec![CommandGroup::Draw],
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(DebugStructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let options = GeneratorOptions {
+        signature_test: true,
+        ..Default::default()
+    };
+
+    writeln!(&mut file, "pub mod gl_signatures {{").unwrap();
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_global_signatures {{").unwrap();
+    small
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_debug_signatures {{").unwrap();
+    small
+        .write_bindings_with_options(DebugStructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let options = GeneratorOptions {
+        command_versions: true,
+        ..Default::default()
+    };
+
+    writeln!(&mut file, "pub mod gl_command_versions {{").unwrap();
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let options = GeneratorOptions {
+        version_assertions: true,
+        macro_export_path: Some("gl_version_assertions".to_string()),
+        ..Default::default()
+    };
+
+    writeln!(&mut file, "#[macro_use] pub mod gl_version_assertions {{").unwrap();
+    small
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // A second `StaticGenerator` module would redeclare the foreign functions with the types of
+    // its own module, so the signatures of the dynamic extension commands are checked with it.
+    writeln!(&mut file, "pub mod gl_static {{").unwrap();
+    let options = GeneratorOptions {
+        signature_test: true,
+        dynamic_extensions: true,
+        ..Default::default()
+    };
+    small
+        .write_bindings_with_options(StaticGenerator, &options, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let wgl = Registry::new(
+        Api::Wgl,
+        (1, 0),
+        Profile::Core,
+        Fallbacks::All,
+        ["WGL_ARB_create_context"],
+    );
+
+    writeln!(&mut file, "pub mod gl_wgl {{").unwrap();
+    wgl.write_bindings(StructGenerator, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_wgl_global {{").unwrap();
+    wgl.write_bindings(GlobalGenerator, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_egl {{ {}", build_egl_symbols()).unwrap();
+    Registry::new(
+        Api::Egl,
+        (1, 5),
+        Profile::Core,
+        Fallbacks::All,
+        [
+            "EGL_EXT_device_enumeration",
+            "EGL_EXT_platform_base",
+            "EGL_KHR_image_base",
+        ],
+    )
+    .write_bindings(StructGenerator, &mut file)
+    .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "pub mod gl_debug {{").unwrap();
+    small
+        .write_bindings(DebugStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+}
+
+fn build_egl_symbols() -> &'static str {
+    "
+        #![allow(non_camel_case_types)]
+
+        use std::os::raw;
+
+        pub type khronos_utime_nanoseconds_t = u64;
+        pub type khronos_uint64_t = u64;
+        pub type khronos_ssize_t = isize;
+        pub type EGLNativeDisplayType = *const raw::c_void;
+        pub type EGLNativePixmapType = *const raw::c_void;
+        pub type EGLNativeWindowType = *const raw::c_void;
+        pub type EGLint = raw::c_int;
+        pub type NativeDisplayType = *const raw::c_void;
+        pub type NativePixmapType = *const raw::c_void;
+        pub type NativeWindowType = *const raw::c_void;
+    "
+}
@@ -0,0 +1,1000 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+use std::os::raw;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+include!(concat!(env!("OUT_DIR"), "/test_struct_gen.rs"));
+
+pub fn compile_test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<gl::FnPtr>();
+    assert_send_sync::<gl::Gl>();
+    assert_send_sync::<gl_debug::Gl>();
+    assert_send_sync::<gl_split_extensions::Gl>();
+    assert_send_sync::<gl_shared_struct::Gl>();
+    assert_send_sync::<gl_interceptor::Gl>();
+    assert_send_sync::<gl_typed_fn_ptrs::Gl>();
+}
+
+/// Pretends to load `glClear` and `glDrawArrays` only.
+#[cfg(test)]
+fn loader(name: &str) -> *const raw::c_void {
+    match name {
+        "glClear" | "glDrawArrays" => 42 as *const raw::c_void,
+        _ => std::ptr::null(),
+    }
+}
+
+#[test]
+fn test_validate_loaded() {
+    let gl = gl::Gl::load_with(loader);
+    assert_eq!(gl.validate(&[]), Ok(()));
+    assert_eq!(gl.validate(&["Clear", "glDrawArrays"]), Ok(()));
+}
+
+#[test]
+fn test_validate_missing() {
+    let gl = gl::Gl::load_with(loader);
+    assert_eq!(
+        gl.validate(&["Clear", "CreateProgram", "glLinkProgram"]),
+        Err(vec!["glCreateProgram", "glLinkProgram"])
+    );
+}
+
+#[test]
+fn test_validate_unknown_names() {
+    let gl = gl::Gl::load_with(loader);
+    assert_eq!(
+        gl.validate(&["glDrawArray", "CreateProgram", "glFramebufferTextureMultiviewOVR"]),
+        Err(vec!["glCreateProgram"])
+    );
+    assert_eq!(gl.validate(&["glClear", "glDrawArray", "DrawArrays"]), Ok(()));
+}
+
+#[test]
+fn test_debug_validate() {
+    let gl = gl_debug::Gl::load_with(loader);
+    assert_eq!(gl.validate(&["DrawArrays"]), Ok(()));
+    assert_eq!(gl.validate(&["UseProgram"]), Err(vec!["glUseProgram"]));
+}
+
+#[test]
+fn test_fn_ptr() {
+    let gl = gl::Gl::load_with(loader);
+    assert_eq!(gl.fn_ptr("Clear"), Some(42 as *const raw::c_void));
+    assert_eq!(gl.fn_ptr("glDrawArrays"), Some(42 as *const raw::c_void));
+    assert_eq!(gl.fn_ptr("UseProgram"), None);
+    assert_eq!(gl.fn_ptr("NotACommand"), None);
+
+    let gl = gl_debug::Gl::load_with(loader);
+    assert_eq!(gl.fn_ptr("glClear"), Some(42 as *const raw::c_void));
+    assert_eq!(gl.fn_ptr("glUseProgram"), None);
+}
+
+#[test]
+fn test_command_ptr() {
+    let gl = gl::Gl::load_with(loader);
+    assert_eq!(gl.command_ptr("glClear"), Some(42 as *const raw::c_void));
+    assert_eq!(
+        gl.command_ptr("glDrawArraysEXT"),
+        Some(42 as *const raw::c_void)
+    );
+    assert_eq!(gl.command_ptr("glUseProgram"), None);
+    assert_eq!(gl.command_ptr("glUseProgramObjectARB"), None);
+    assert_eq!(gl.command_ptr("Clear"), None);
+    assert_eq!(gl.command_ptr(""), None);
+}
+
+/// Pretends to load every command except `glDispatchCompute`, which was introduced in 4.3.
+#[cfg(test)]
+fn loader_without_compute(name: &str) -> *const raw::c_void {
+    match name {
+        "glDispatchCompute" => std::ptr::null(),
+        _ => 42 as *const raw::c_void,
+    }
+}
+
+#[test]
+fn test_version_loaded() {
+    let gl = gl::Gl::load_with(loader_without_compute);
+    assert!(gl.version_1_0_loaded());
+    assert!(gl.version_4_2_loaded());
+    assert!(!gl.version_4_3_loaded());
+    assert!(gl.version_4_6_loaded());
+
+    let gl = gl::Gl::load_with(loader);
+    assert!(!gl.version_1_0_loaded());
+}
+
+#[test]
+fn test_debug_version_loaded() {
+    let gl = gl_debug::Gl::load_with(loader_without_compute);
+    assert!(gl.version_4_2_loaded());
+    assert!(!gl.version_4_3_loaded());
+}
+
+#[test]
+fn test_pfn_types() {
+    use gl::types::*;
+
+    extern "system" fn draw_arrays(_: GLenum, _: GLint, _: GLsizei) {}
+    extern "system" fn get_string(_: GLenum) -> *const GLubyte {
+        std::ptr::null::<GLubyte>()
+    }
+
+    let draw_arrays: gl::PFNGLDRAWARRAYSPROC = draw_arrays;
+    let get_string: gl_debug::PFNGLGETSTRINGPROC = get_string;
+    draw_arrays(gl::TRIANGLES, 0, 3);
+    assert!(get_string(gl::VERSION).is_null());
+}
+
+#[test]
+fn test_load_subset_with() {
+    let mut gl = gl::Gl::load_with(|_| std::ptr::null());
+    assert_eq!(
+        gl.load_subset_with(
+            loader,
+            &["Clear", "glDrawArrays", "UseProgram", "NotACommand"]
+        ),
+        Err(vec!["NotACommand"])
+    );
+    assert!(gl.Clear.is_loaded());
+    assert!(gl.DrawArrays.is_loaded());
+    assert!(!gl.UseProgram.is_loaded());
+    assert!(!gl.LinkProgram.is_loaded());
+}
+
+#[test]
+fn test_load_extension_with() {
+    let mut gl = gl_debug::Gl::load_with(|_| std::ptr::null());
+    assert!(gl.load_extension_with(|_| 42 as *const raw::c_void, "GL_ARB_debug_output"));
+    assert!(gl.DebugMessageCallbackARB.is_loaded());
+    assert!(!gl.DebugMessageCallback.is_loaded());
+
+    assert!(!gl.load_extension_with(|_| 42 as *const raw::c_void, "GL_ARB_not_an_extension"));
+}
+
+#[test]
+fn test_try_load_with() {
+    let mut failed = false;
+    let gl = gl_debug::Gl::try_load_with(|symbol| {
+        assert!(!failed, "loading did not stop at the first error");
+        match symbol {
+            "glClear" => {
+                failed = true;
+                Err("loader is broken")
+            },
+            _ => Ok(42 as *const raw::c_void),
+        }
+    });
+    assert_eq!(gl.err(), Some(gl_debug::TryLoadError::Loader("loader is broken")));
+
+    let missing = match gl::Gl::try_load_with(|symbol| Ok::<_, ()>(loader(symbol))) {
+        Err(gl::TryLoadError::Missing(missing)) => missing,
+        _ => panic!("the missing core commands were not reported"),
+    };
+    assert!(missing.contains(&"glViewport"));
+    assert!(!missing.contains(&"glClear"));
+    assert!(!missing.contains(&"glDebugMessageCallbackARB"));
+
+    let gl = gl_debug::Gl::try_load_with(|symbol| match symbol {
+        "glDebugMessageCallbackARB" => Ok::<_, ()>(std::ptr::null()),
+        _ => Ok(42 as *const raw::c_void),
+    });
+    assert!(!gl.unwrap().DebugMessageCallbackARB.is_loaded());
+}
+
+#[test]
+fn test_vendor_suffixes() {
+    fn loader(symbol: &str) -> *const raw::c_void {
+        match symbol {
+            "glClearARB" | "glViewportEXT" | "glCullFaceARB" | "glCullFaceEXT" => {
+                42 as *const raw::c_void
+            },
+            _ => std::ptr::null(),
+        }
+    }
+
+    let gl = gl_vendor_suffixes::Gl::load_with(loader);
+    assert!(gl.Clear.is_loaded());
+    assert!(gl.Viewport.is_loaded());
+    assert!(gl.CullFace.is_loaded());
+    assert!(!gl.DispatchCompute.is_loaded());
+
+    let gl = gl::Gl::load_with(loader);
+    assert!(!gl.Clear.is_loaded());
+    assert!(!gl.Viewport.is_loaded());
+}
+
+#[test]
+fn test_load_with_cstr() {
+    let gl = gl::Gl::load_with_cstr(|symbol| loader(symbol.to_str().unwrap()));
+    assert!(gl.Clear.is_loaded());
+    assert!(gl.DrawArrays.is_loaded());
+    assert!(!gl.UseProgram.is_loaded());
+
+    let gl = gl_vendor_suffixes::Gl::load_with_cstr(|symbol| match symbol.to_bytes() {
+        b"glViewportEXT" => 42 as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    assert!(gl.Viewport.is_loaded());
+}
+
+#[cfg(test)]
+static CLEARED: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(test)]
+extern "system" fn clear(mask: u32) {
+    CLEARED.store(mask as usize, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+extern "system" fn get_error() -> u32 {
+    0x0500
+}
+
+/// Loads `glClear` and `glGetError` as functions that can be called.
+#[cfg(test)]
+fn dispatch_loader(name: &str) -> *const raw::c_void {
+    match name {
+        "glClear" => clear as *const raw::c_void,
+        "glGetError" => get_error as *const raw::c_void,
+        _ => std::ptr::null(),
+    }
+}
+
+#[test]
+fn test_dispatch() {
+    use gl_dispatch::dispatch::{CommandId, Value};
+
+    let gl = gl_dispatch::Gl::load_with(dispatch_loader);
+    let clear = CommandId::from_name("glClear").unwrap();
+    assert_eq!(clear, CommandId::Clear);
+    assert_eq!(clear.name(), "glClear");
+    assert_eq!(clear.arity(), 1);
+    assert_eq!(CommandId::from_index(clear as usize), Some(clear));
+
+    unsafe {
+        assert_eq!(gl.dispatch(clear, &[Value::F64(16384.0)]), Ok(Value::Void));
+        assert_eq!(
+            CLEARED.load(Ordering::SeqCst),
+            gl::COLOR_BUFFER_BIT as usize
+        );
+        assert_eq!(
+            gl.dispatch(CommandId::GetError, &[]),
+            Ok(Value::U32(gl::INVALID_ENUM))
+        );
+    }
+}
+
+#[test]
+fn test_dispatch_errors() {
+    use gl_dispatch::dispatch::{CommandId, DispatchError, Value};
+
+    let gl = gl_dispatch::Gl::load_with(dispatch_loader);
+    unsafe {
+        assert_eq!(
+            gl.dispatch(CommandId::Clear, &[]),
+            Err(DispatchError::Arity {
+                expected: 1,
+                found: 0,
+            })
+        );
+        assert_eq!(
+            gl.dispatch(CommandId::Clear, &[Value::Ptr(std::ptr::null())]),
+            Err(DispatchError::Type { index: 0 })
+        );
+    }
+    assert_eq!(CommandId::from_name("glDebugMessageCallback"), None);
+    assert_eq!(CommandId::from_name("Clear"), None);
+}
+
+#[test]
+fn test_debug_feature() {
+    let gl = gl_debug_feature::Gl::load_with(dispatch_loader);
+    unsafe {
+        gl.Clear(gl::COLOR_BUFFER_BIT);
+    }
+    assert_eq!(
+        CLEARED.load(Ordering::SeqCst),
+        gl::COLOR_BUFFER_BIT as usize
+    );
+}
+
+#[test]
+fn test_debug_without_get_error() {
+    // The other tests share `CLEARED`, so this one counts its own calls.
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    extern "system" fn clear(_: u32) {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // Every command but `glGetError` is resolved. Only `Clear` is called.
+    let gl = gl_debug::Gl::load_with(|name| match name {
+        "glClear" => clear as *const raw::c_void,
+        "glGetError" => std::ptr::null(),
+        _ => 42 as *const raw::c_void,
+    });
+    assert!(!gl.GetError.is_loaded());
+    assert!(gl.DrawArrays.is_loaded());
+    unsafe {
+        gl.Clear(gl::COLOR_BUFFER_BIT);
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_debug_message_callback_none() {
+    use gl::types::*;
+
+    static INSTALLED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "system" fn debug_message_callback(callback: GLDEBUGPROC, _: *const raw::c_void) {
+        INSTALLED.store(callback.is_some() as usize, Ordering::SeqCst);
+    }
+    extern "system" fn callback(
+        _: GLenum,
+        _: GLenum,
+        _: GLuint,
+        _: GLenum,
+        _: GLsizei,
+        _: *const GLchar,
+        _: *mut raw::c_void,
+    ) {
+    }
+
+    let loader = |name: &str| match name {
+        "glDebugMessageCallback" => debug_message_callback as *const raw::c_void,
+        _ => std::ptr::null(),
+    };
+    let gl = gl::Gl::load_with(loader);
+    let gl_debug = gl_debug::Gl::load_with(loader);
+    unsafe {
+        gl.DebugMessageCallback(Some(callback), std::ptr::null());
+        assert_eq!(INSTALLED.load(Ordering::SeqCst), 1);
+        gl.DebugMessageCallback(None, std::ptr::null());
+        assert_eq!(INSTALLED.load(Ordering::SeqCst), 0);
+
+        gl_debug.DebugMessageCallback(Some(callback), std::ptr::null());
+        assert_eq!(INSTALLED.load(Ordering::SeqCst), 1);
+        gl_debug.DebugMessageCallback(None, std::ptr::null());
+        assert_eq!(INSTALLED.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[test]
+fn test_pfn_table() {
+    use std::mem::size_of;
+
+    let gl = gl_pfn_table::Gl::load_with(dispatch_loader);
+    let table = gl.pfn_table();
+    assert_eq!(
+        size_of::<gl_pfn_table::GlPfnTable>(),
+        size_of::<gl_pfn_table::Gl>()
+    );
+    assert!(table.DrawArrays.is_none());
+    assert_eq!(table.symbol_ptr("glClear"), gl.fn_ptr("glClear").unwrap());
+    (table.Clear.unwrap())(gl::COLOR_BUFFER_BIT);
+    assert_eq!(
+        CLEARED.load(Ordering::SeqCst),
+        gl::COLOR_BUFFER_BIT as usize
+    );
+
+    let gl = gl_pfn_table::Gl::from_pfn_table(&table);
+    assert!(gl.Clear.is_loaded());
+    assert!(gl.GetError.is_loaded());
+    assert!(!gl.DrawArrays.is_loaded());
+}
+
+// The global bindings are only used by this test.
+#[test]
+fn test_global_bridge() {
+    let gl = gl_global_bridge::Gl::load_with(dispatch_loader);
+    gl.install_global();
+    assert!(gl_global::Clear::is_loaded());
+    assert!(gl_global::GetError::is_loaded());
+    assert!(!gl_global::DrawArrays::is_loaded());
+    unsafe { gl_global::Clear(gl::COLOR_BUFFER_BIT) };
+    assert_eq!(
+        CLEARED.load(Ordering::SeqCst),
+        gl::COLOR_BUFFER_BIT as usize
+    );
+
+    let gl = gl_global_bridge::Gl::from_global();
+    assert!(gl.Clear.is_loaded());
+    assert_eq!(gl.fn_ptr("glClear"), gl_global::command_ptr("glClear"));
+    assert!(!gl.DrawArrays.is_loaded());
+}
+
+// Each test runs on a thread of its own, which has no current bindings at first.
+#[test]
+fn test_with_current() {
+    assert_eq!(gl_current::try_with_current(|_| ()), None);
+
+    let gl = gl_current::Gl::load_with(dispatch_loader);
+    assert!(gl.make_current().is_none());
+    gl_current::with_current(|gl| unsafe { gl.Clear(gl::COLOR_BUFFER_BIT) });
+    assert_eq!(
+        CLEARED.load(Ordering::SeqCst),
+        gl::COLOR_BUFFER_BIT as usize
+    );
+    assert_eq!(
+        gl_current::try_with_current(|gl| gl.DrawArrays.is_loaded()),
+        Some(false)
+    );
+
+    let other = std::thread::spawn(|| gl_current::try_with_current(|_| ()))
+        .join()
+        .unwrap();
+    assert_eq!(other, None);
+
+    let gl = gl_current::Gl::load_with(|_| std::ptr::null());
+    let previous = gl.make_current().unwrap();
+    assert!(previous.Clear.is_loaded());
+    assert!(!gl_current::with_current(|gl| gl.Clear.is_loaded()));
+}
+
+#[test]
+#[should_panic(expected = "no `Gl` has been made current on this thread")]
+fn test_with_current_without_bindings() {
+    gl_current::with_current(|_| ());
+}
+
+#[test]
+fn test_wgl_load_with_module() {
+    let mut from_proc = Vec::new();
+    let mut from_module = Vec::new();
+    let wgl = gl_wgl::Wgl::load_with_module(
+        |symbol| {
+            from_proc.push(symbol);
+            // Some drivers return 1 instead of null when `wglGetProcAddress` fails.
+            std::ptr::dangling::<raw::c_void>()
+        },
+        |symbol| {
+            from_module.push(symbol);
+            0x10 as *const raw::c_void
+        },
+    );
+
+    assert!(from_module.contains(&"wglCreateContext"));
+    assert!(!from_module.contains(&"wglCreateContextAttribsARB"));
+    assert!(from_proc.contains(&"wglCreateContextAttribsARB"));
+    assert!(!from_proc.contains(&"wglCreateContext"));
+    assert!(wgl.CreateContext.is_loaded());
+    assert!(!wgl.CreateContextAttribsARB.is_loaded());
+}
+
+// The global WGL bindings are only used by this test.
+#[test]
+fn test_wgl_global_load_with_module() {
+    gl_wgl_global::load_with_module(
+        |symbol| {
+            assert_eq!(symbol, "wglCreateContextAttribsARB");
+            0x20 as *const raw::c_void
+        },
+        |symbol| {
+            assert!(symbol != "wglCreateContextAttribsARB");
+            0x10 as *const raw::c_void
+        },
+    );
+
+    assert!(gl_wgl_global::CreateContext::is_loaded());
+    assert!(gl_wgl_global::CreateContextAttribsARB::is_loaded());
+}
+
+#[test]
+fn test_opaque_sync() {
+    use gl_opaque_sync::dispatch::{CommandId, Value};
+    use gl_opaque_sync::types::{__GLsync, GLbitfield, GLenum, GLsync, GLuint64};
+    use std::mem::size_of;
+
+    extern "system" fn fence_sync(_: GLenum, _: GLbitfield) -> GLsync {
+        GLsync::from_ptr(0x10 as *const __GLsync)
+    }
+    extern "system" fn client_wait_sync(sync: GLsync, _: GLbitfield, _: GLuint64) -> GLenum {
+        if sync.is_null() {
+            gl::WAIT_FAILED
+        } else {
+            gl::ALREADY_SIGNALED
+        }
+    }
+
+    assert_eq!(size_of::<GLsync>(), size_of::<*const raw::c_void>());
+    assert!(GLsync::null().is_null());
+    const NULL: GLsync = GLsync::null();
+    assert!(NULL.is_null());
+
+    let gl = gl_opaque_sync::Gl::load_with(|name| match name {
+        "glFenceSync" => fence_sync as *const raw::c_void,
+        "glClientWaitSync" => client_wait_sync as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    unsafe {
+        let sync = gl.FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0);
+        assert!(!sync.is_null());
+        assert_eq!(sync.as_ptr(), 0x10 as *const __GLsync);
+        assert_eq!(gl.ClientWaitSync(sync, 0, 0), gl::ALREADY_SIGNALED);
+        assert_eq!(gl.ClientWaitSync(GLsync::null(), 0, 0), gl::WAIT_FAILED);
+
+        assert_eq!(
+            gl.dispatch(CommandId::FenceSync, &[Value::U32(0), Value::U32(0)]),
+            Ok(Value::Ptr(0x10 as *const raw::c_void))
+        );
+        assert_eq!(
+            gl.dispatch(
+                CommandId::ClientWaitSync,
+                &[
+                    Value::Ptr(0x10 as *const raw::c_void),
+                    Value::U32(0),
+                    Value::U64(0)
+                ]
+            ),
+            Ok(Value::U32(gl::ALREADY_SIGNALED))
+        );
+    }
+}
+
+#[test]
+fn test_gpu_timing() {
+    use gl_gpu_timing::gpu_timing::{self, CommandTiming};
+    use gl_gpu_timing::types::{GLenum, GLint, GLsizei, GLuint, GLuint64};
+
+    static DELETED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "system" fn draw_arrays(_: GLenum, _: GLint, _: GLsizei) {}
+    extern "system" fn gen_queries(n: GLsizei, ids: *mut GLuint) {
+        for i in 0..n as usize {
+            unsafe { *ids.add(i) = i as GLuint + 1 };
+        }
+    }
+    extern "system" fn query_counter(_: GLuint, _: GLenum) {}
+    extern "system" fn get_query_objectiv(_: GLuint, _: GLenum, params: *mut GLint) {
+        unsafe { *params = 1 };
+    }
+    // The timestamp of each query is its id times 100 nanoseconds.
+    extern "system" fn get_query_objectui64v(id: GLuint, _: GLenum, params: *mut GLuint64) {
+        unsafe { *params = GLuint64::from(id) * 100 };
+    }
+    extern "system" fn delete_queries(n: GLsizei, _: *const GLuint) {
+        DELETED.fetch_add(n as usize, Ordering::SeqCst);
+    }
+
+    let gl = gl_gpu_timing::Gl::load_with(|name| match name {
+        "glDrawArrays" => draw_arrays as *const raw::c_void,
+        "glGenQueries" => gen_queries as *const raw::c_void,
+        "glQueryCounter" => query_counter as *const raw::c_void,
+        "glGetQueryObjectiv" => get_query_objectiv as *const raw::c_void,
+        "glGetQueryObjectui64v" => get_query_objectui64v as *const raw::c_void,
+        "glDeleteQueries" => delete_queries as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    // The commands are only measured once enabled.
+    unsafe { gl.DrawArrays(gl::TRIANGLES, 0, 3) };
+    assert_eq!(gl.gpu_timings(), []);
+
+    gpu_timing::set_enabled(true);
+    unsafe { gl.DrawArrays(gl::TRIANGLES, 0, 3) };
+    gpu_timing::set_enabled(false);
+    assert_eq!(
+        gl.gpu_timings(),
+        [CommandTiming {
+            command: "glDrawArrays",
+            nanos: 100,
+        }]
+    );
+    assert_eq!(DELETED.load(Ordering::SeqCst), 2);
+    assert_eq!(gl.gpu_timings(), []);
+}
+
+#[test]
+fn test_command_version() {
+    use gl_command_versions::command_version;
+
+    assert_eq!(command_version("glClear"), Some((1, 0)));
+    assert_eq!(command_version("glDrawArrays"), Some((1, 1)));
+    assert_eq!(command_version("glBindVertexArray"), Some((3, 0)));
+    assert_eq!(command_version("glDebugMessageCallback"), Some((4, 3)));
+    // Extension commands and names without the prefix have no version.
+    assert_eq!(command_version("glDebugMessageCallbackARB"), None);
+    assert_eq!(command_version("Clear"), None);
+}
+
+// The bindings are generated for GL 4.6, so these compile. `tests/ui` checks that a higher version
+// doesn't, from another crate.
+assert_gl_at_least!(4, 3);
+assert_gl_at_least!(4, 6);
+assert_gl_at_least!(3, 9);
+
+#[test]
+fn test_generated_for() {
+    use gl_version_assertions::{Api, Profile, GENERATED_FOR};
+
+    assert_eq!(GENERATED_FOR, (Api::Gl, 4, 6, Profile::Core));
+}
+
+#[test]
+fn test_retain_reachable() {
+    use std::mem::size_of;
+
+    let gl = gl_reachable::Gl::load_with(dispatch_loader);
+    assert_eq!(size_of::<gl_reachable::Gl>(), 3 * size_of::<usize>());
+    assert_eq!(
+        gl.validate(&["Clear", "DrawArrays", "GetError"]),
+        Err(vec!["glDrawArrays"])
+    );
+    // Only the retained commands of a version are checked.
+    assert!(gl.version_1_0_loaded());
+    assert!(!gl.version_1_1_loaded());
+    assert_eq!(gl_reachable::COLOR_BUFFER_BIT, gl::COLOR_BUFFER_BIT);
+}
+
+#[test]
+fn test_caching_loader() {
+    fn assert_sync<T: Sync>(_: &T) {}
+
+    let calls = AtomicUsize::new(0);
+    let cache = gl_caching_loader::CachingLoader::new(|symbol| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        loader(symbol)
+    });
+    assert_sync(&cache);
+
+    let gl = gl_caching_loader::Gl::load_with(|symbol| cache.load(symbol));
+    assert!(gl.Clear.is_loaded());
+    assert!(!gl.UseProgram.is_loaded());
+    let first = calls.load(Ordering::SeqCst);
+    assert!(first > 0);
+
+    let gl = gl_caching_loader::Gl::load_with(|symbol| cache.load(symbol));
+    assert!(gl.Clear.is_loaded());
+    assert!(!gl.UseProgram.is_loaded());
+    assert_eq!(calls.load(Ordering::SeqCst), first);
+
+    cache.clear();
+    assert!(!cache.load("glClear").is_null());
+    assert_eq!(calls.load(Ordering::SeqCst), first + 1);
+}
+
+#[test]
+fn test_dedup_loads() {
+    // Counts the symbols that were requested more than once by a pass.
+    fn count_repeats(symbols: &[&'static str]) -> usize {
+        let mut sorted = symbols.to_vec();
+        sorted.sort();
+        sorted.windows(2).filter(|pair| pair[0] == pair[1]).count()
+    }
+
+    // `glDebugMessageInsert` falls back to `glDebugMessageInsertARB`, which is also a command.
+    let mut symbols = Vec::new();
+    let _gl = gl::Gl::load_with(|symbol| {
+        symbols.push(symbol);
+        loader(symbol)
+    });
+    assert!(count_repeats(&symbols) > 0);
+
+    let mut symbols = Vec::new();
+    let gl = gl_dedup_loads::Gl::load_with(|symbol| {
+        symbols.push(symbol);
+        loader(symbol)
+    });
+    assert_eq!(count_repeats(&symbols), 0);
+    assert!(symbols.contains(&"glDebugMessageInsertARB"));
+    assert!(gl.Clear.is_loaded());
+    assert!(!gl.DebugMessageInsertARB.is_loaded());
+}
+
+#[test]
+fn test_loader_trait() {
+    use gl_loader_trait::GlLoader;
+
+    struct CountingLoader {
+        calls: usize,
+    }
+
+    impl GlLoader for CountingLoader {
+        fn load(&mut self, symbol: &'static str) -> *const raw::c_void {
+            self.calls += 1;
+            loader(symbol)
+        }
+    }
+
+    let mut counting = CountingLoader { calls: 0 };
+    let gl = gl_loader_trait::Gl::load_with_loader(&mut counting);
+    assert!(gl.Clear.is_loaded());
+    assert!(!gl.UseProgram.is_loaded());
+    let first = counting.calls;
+    assert!(first > 0);
+
+    gl_loader_trait::Gl::load_with_loader(&mut counting);
+    assert_eq!(counting.calls, 2 * first);
+
+    let mut closure = |symbol| loader(symbol);
+    let gl = gl_loader_trait::Gl::load_with_loader(&mut closure);
+    assert!(gl.DrawArrays.is_loaded());
+
+    let mut cache = gl_loader_trait::CachingLoader::new(loader);
+    assert!(!cache.load("glClear").is_null());
+    let gl = gl_loader_trait::Gl::load_with_loader(&mut cache);
+    assert!(gl.Clear.is_loaded());
+}
+
+#[test]
+fn test_dynamic_extensions() {
+    use gl_static::types::*;
+    use std::sync::atomic::AtomicU32;
+
+    static INSERTED: AtomicU32 = AtomicU32::new(0);
+
+    extern "system" fn insert(
+        _source: GLenum,
+        _type: GLenum,
+        id: GLuint,
+        _severity: GLenum,
+        _length: GLsizei,
+        _buf: *const GLchar,
+    ) {
+        INSERTED.store(id, Ordering::SeqCst);
+    }
+
+    assert!(!gl_static::DebugMessageInsertARB::is_loaded());
+    gl_static::load_extensions_with(|symbol| match symbol {
+        "glDebugMessageInsertARB" => insert as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    assert!(gl_static::DebugMessageInsertARB::is_loaded());
+    assert!(!gl_static::DebugMessageCallbackARB::is_loaded());
+
+    unsafe {
+        gl_static::DebugMessageInsertARB(0, 0, 7, 0, 0, std::ptr::null());
+    }
+    assert_eq!(INSERTED.load(Ordering::SeqCst), 7);
+}
+
+#[test]
+fn test_prelude() {
+    use gl_prelude::prelude::*;
+
+    let gl: Gl = Gl::load_with(loader);
+    let mask: GLbitfield = gl_prelude::COLOR_BUFFER_BIT;
+    assert_eq!(mask, 0x4000);
+    assert!(gl.Clear.is_loaded());
+}
+
+#[test]
+fn test_split_extensions() {
+    let gl = gl_split_extensions::Gl::load_with(loader);
+    assert!(gl.core.Clear.is_loaded());
+    assert!(!gl.core.Viewport.is_loaded());
+    assert!(!gl.ext.DebugMessageCallbackARB.is_loaded());
+    assert_eq!(gl.validate(&["glClear", "DrawArrays"]), Ok(()));
+    assert_eq!(gl.fn_ptr("Clear"), Some(42 as *const raw::c_void));
+    assert_eq!(gl.command_ptr("glDebugMessageCallbackARB"), None);
+    assert!(!gl.version_1_0_loaded());
+
+    let gl = gl.clone();
+    assert!(gl.core.DrawArrays.is_loaded());
+}
+
+#[test]
+fn test_skip_extensions() {
+    let mut gl = gl_skip_extensions::Gl::load_with(loader);
+    assert!(gl.Clear.is_loaded());
+    assert_eq!(gl.fn_ptr("DebugMessageCallbackARB"), None);
+    assert!(!gl.load_extension_with(loader, "GL_ARB_debug_output"));
+}
+
+#[test]
+fn test_shared_struct() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let gl = gl_shared_struct::Gl::load_with(loader);
+    assert_send_sync(&gl);
+
+    let mut clone = gl.clone();
+    assert!(std::ptr::eq(&*gl, &*clone));
+    assert!(clone.Clear.is_loaded());
+
+    assert_eq!(
+        clone.load_subset_with(|_| 42 as *const raw::c_void, &["Viewport"]),
+        Ok(())
+    );
+    assert!(!std::ptr::eq(&*gl, &*clone));
+    assert!(clone.Viewport.is_loaded());
+    assert!(!gl.Viewport.is_loaded());
+}
+
+#[test]
+fn test_interceptor() {
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl gl_interceptor::GlInterceptor for Recorder {
+        fn before(&self, name: &'static str, args: &[&dyn fmt::Debug]) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("before {}{:?}", name, args));
+        }
+
+        fn after(&self, name: &'static str) {
+            self.0.lock().unwrap().push(format!("after {}", name));
+        }
+    }
+
+    let recorder = Arc::new(Recorder(Mutex::new(Vec::new())));
+    let mut gl = gl_interceptor::Gl::load_with(dispatch_loader);
+    unsafe { gl.Clear(gl::COLOR_BUFFER_BIT) };
+    gl.set_interceptor(Some(recorder.clone()));
+    unsafe {
+        gl.Clear(gl::COLOR_BUFFER_BIT);
+        assert_eq!(gl.GetError(), gl::INVALID_ENUM);
+    }
+    gl.set_interceptor(None);
+    unsafe { gl.Clear(gl::COLOR_BUFFER_BIT) };
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        [
+            "before glClear[16384]",
+            "after glClear",
+            "before glGetError[]",
+            "after glGetError",
+        ]
+    );
+}
+
+#[test]
+fn test_skip_send_impl() {
+    use std::marker::PhantomData;
+
+    // Resolves to the inherent method only when `T: Send`.
+    struct Probe<T>(PhantomData<T>);
+
+    trait NotSend {
+        fn is_send(&self) -> bool {
+            false
+        }
+    }
+
+    impl<T> NotSend for Probe<T> {}
+
+    impl<T: Send> Probe<T> {
+        fn is_send(&self) -> bool {
+            true
+        }
+    }
+
+    trait NotSync {
+        fn is_sync(&self) -> bool {
+            false
+        }
+    }
+
+    impl<T> NotSync for Probe<T> {}
+
+    impl<T: Sync> Probe<T> {
+        fn is_sync(&self) -> bool {
+            true
+        }
+    }
+
+    assert!(Probe::<gl::Gl>(PhantomData).is_send());
+    assert!(Probe::<gl::Gl>(PhantomData).is_sync());
+    assert!(!Probe::<gl_skip_send_impl::Gl>(PhantomData).is_send());
+    assert!(!Probe::<gl_skip_send_impl::Gl>(PhantomData).is_sync());
+}
+
+#[test]
+fn test_fn_ptr_size() {
+    use std::mem::size_of;
+
+    assert_eq!(size_of::<gl::FnPtr>(), size_of::<*const raw::c_void>());
+    // Every symbol resolves, so the load function is called once per command, without
+    // fallbacks.
+    let mut commands = 0;
+    gl::Gl::load_with(|_| {
+        commands += 1;
+        42 as *const raw::c_void
+    });
+    assert!(commands > 0);
+    assert_eq!(size_of::<gl::Gl>(), commands * size_of::<gl::FnPtr>());
+}
+
+#[test]
+fn test_typed_fn_ptrs() {
+    let gl = gl_typed_fn_ptrs::Gl::load_with(dispatch_loader);
+    assert!(gl.UseProgram.is_none());
+
+    let get_error: gl_typed_fn_ptrs::PFNGLGETERRORPROC = gl.GetError.unwrap();
+    assert_eq!(get_error(), gl::INVALID_ENUM);
+    assert_eq!(unsafe { gl.GetError() }, gl::INVALID_ENUM);
+
+    assert_eq!(gl.fn_ptr("GetError"), Some(get_error as *const raw::c_void));
+    assert_eq!(gl.command_ptr("glClear"), Some(clear as *const raw::c_void));
+    assert_eq!(gl.command_ptr("glUseProgram"), None);
+    assert_eq!(
+        gl.validate(&["Clear", "UseProgram"]),
+        Err(vec!["glUseProgram"])
+    );
+}
+
+#[test]
+fn test_load_with_retry() {
+    let mut attempts = 0;
+    let gl = gl::Gl::load_with_retry(
+        |name| match name {
+            "glClear" => {
+                attempts += 1;
+                if attempts < 3 {
+                    std::ptr::null()
+                } else {
+                    42 as *const raw::c_void
+                }
+            },
+            _ => std::ptr::null(),
+        },
+        5,
+    );
+    assert!(gl.Clear.is_loaded());
+    assert!(!gl.DrawArrays.is_loaded());
+    assert_eq!(attempts, 3);
+}
+
+#[test]
+fn test_dispatch_bench() {
+    let global = gl_global_bench::dispatch_bench::run(100);
+    let methods = gl_bench::dispatch_bench::run(100);
+
+    let names: Vec<_> = global.iter().map(|&(name, _)| name).collect();
+    assert_eq!(names, ["Clear", "DrawArrays"]);
+    assert_eq!(
+        names,
+        methods.iter().map(|&(name, _)| name).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_egl_two_phase_loading() {
+    let mut bootstrap = Vec::new();
+    let mut egl = gl_egl::Egl::load_bootstrap_with(|symbol| {
+        bootstrap.push(symbol);
+        42 as *const raw::c_void
+    });
+    assert!(egl.GetDisplay.is_loaded());
+    assert!(egl.Initialize.is_loaded());
+    assert!(egl.GetPlatformDisplayEXT.is_loaded());
+    assert!(egl.QueryDevicesEXT.is_loaded());
+    assert!(!egl.CreateContext.is_loaded());
+    assert!(!egl.CreateImageKHR.is_loaded());
+    assert!(!bootstrap.contains(&"eglCreateContext"));
+
+    let mut complete = Vec::new();
+    egl.complete_load_with(|symbol| {
+        complete.push(symbol);
+        42 as *const raw::c_void
+    });
+    assert!(egl.CreateContext.is_loaded());
+    assert!(egl.CreateImageKHR.is_loaded());
+    assert!(complete.contains(&"eglCreateContext"));
+    assert!(!complete.contains(&"eglGetDisplay"));
+    assert!(!complete.contains(&"eglGetPlatformDisplayEXT"));
+}
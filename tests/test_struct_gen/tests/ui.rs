@@ -0,0 +1,49 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate trybuild;
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass_*.rs");
+}
+
+/// The rest of the diagnostic depends on the version of rustc, so only the panic message of
+/// `assert_gl_at_least!` is checked.
+#[test]
+fn fail_version_assertion() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ui/fail_version_assertion");
+    let output = Command::new(env!("CARGO"))
+        .arg("check")
+        .arg("--manifest-path")
+        .arg(crate_dir.join("Cargo.toml"))
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("fail_version_assertion"),
+        )
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("the bindings were generated for gl 4.6, which is lower than 4.7"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,6 @@
+#[macro_use]
+extern crate test_struct_gen;
+
+assert_gl_at_least!(4, 7);
+
+fn main() {}
@@ -0,0 +1,6 @@
+extern crate test_struct_gen;
+
+// The bindings are generated for GL 4.6, and export the macro to the crates using them.
+test_struct_gen::assert_gl_at_least!(4, 6);
+
+fn main() {}
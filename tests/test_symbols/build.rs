@@ -21,9 +21,17 @@ use std::path::*;
 
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
-    let mut file = File::create(&Path::new(&dest).join("test_symbols.rs")).unwrap();
+    let mut file = File::create(Path::new(&dest).join("test_symbols.rs")).unwrap();
 
-    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
-        .write_bindings(GlobalGenerator, &mut file)
+    let registry = Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, []);
+    registry.write_bindings(GlobalGenerator, &mut file).unwrap();
+
+    let mut file = File::create(Path::new(&dest).join("test_symbols_debug_feature.rs")).unwrap();
+    let options = GeneratorOptions {
+        debug_feature: Some("gl_debug".to_string()),
+        ..Default::default()
+    };
+    registry
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
         .unwrap();
 }
@@ -18,6 +18,10 @@ pub mod gl {
     include!(concat!(env!("OUT_DIR"), "/test_symbols.rs"));
 }
 
+pub mod gl_debug_feature {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_debug_feature.rs"));
+}
+
 pub fn compile_test_symbols_exist() {
     unsafe {
         gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -36,12 +40,119 @@ pub fn compile_test_symbols_exist() {
 fn test_fallback_works() {
     fn loader(name: &str) -> *const raw::c_void {
         match name {
-            "glGenFramebuffers" => 0 as *const raw::c_void,
+            "glGenFramebuffers" => std::ptr::null(),
             "glGenFramebuffersEXT" => 42 as *const raw::c_void,
             name => panic!("test tried to load {} unexpectedly!", name),
         }
-    };
+    }
 
     gl::GenFramebuffers::load_with(loader);
     assert!(gl::GenFramebuffers::is_loaded());
 }
+
+#[test]
+fn test_fallback_works_with_cstr() {
+    fn loader(name: &std::ffi::CStr) -> *const raw::c_void {
+        match name.to_bytes() {
+            b"glGenRenderbuffers" => std::ptr::null(),
+            b"glGenRenderbuffersEXT" => 42 as *const raw::c_void,
+            _ => panic!("test tried to load {:?} unexpectedly!", name),
+        }
+    }
+
+    gl::GenRenderbuffers::load_with_cstr(loader);
+    assert!(gl::GenRenderbuffers::is_loaded());
+}
+
+#[test]
+fn test_command_ptr() {
+    gl::GenFramebuffers::load_with(|_| 42 as *const raw::c_void);
+    assert_eq!(
+        gl::command_ptr("glGenFramebuffers"),
+        Some(42 as *const raw::c_void)
+    );
+    assert_eq!(
+        gl::command_ptr("glGenFramebuffersEXT"),
+        Some(42 as *const raw::c_void)
+    );
+    assert_eq!(gl::command_ptr("glDispatchCompute"), None);
+    assert_eq!(gl::command_ptr("glNotACommand"), None);
+}
+
+#[test]
+fn test_version_is_loaded() {
+    gl::load_with(|name| match name {
+        "glDispatchCompute" => std::ptr::null(),
+        _ => 42 as *const raw::c_void,
+    });
+
+    assert!(gl::GL_VERSION_1_0::is_loaded());
+    assert!(gl::GL_VERSION_4_2::is_loaded());
+    assert!(!gl::GL_VERSION_4_3::is_loaded());
+}
+
+#[test]
+fn test_try_load_with() {
+    let result = gl::try_load_with(|name| match name {
+        "glDispatchCompute" => Ok::<_, ()>(std::ptr::null()),
+        _ => Ok(42 as *const raw::c_void),
+    });
+    assert_eq!(result, Err(gl::TryLoadError::Missing(vec!["glDispatchCompute"])));
+}
+
+#[test]
+fn test_load_with_retry() {
+    let mut attempts = std::collections::HashMap::new();
+    gl::load_with_retry(
+        |name| {
+            let attempt = attempts.entry(name).or_insert(0);
+            *attempt += 1;
+            match name {
+                "glDispatchCompute" => std::ptr::null(),
+                "glClear" if *attempt < 3 => std::ptr::null(),
+                _ => 42 as *const raw::c_void,
+            }
+        },
+        2,
+    );
+
+    assert!(gl::Clear::is_loaded());
+    assert!(!gl::DispatchCompute::is_loaded());
+    assert_eq!(attempts["glClear"], 3);
+    assert_eq!(attempts["glDispatchCompute"], 3);
+    assert_eq!(attempts["glDrawArrays"], 1);
+}
+
+#[test]
+fn test_gl_bool() {
+    use gl::types::{from_gl_bool, gl_bool, GLboolean};
+
+    const T: GLboolean = gl_bool(true);
+    assert_eq!(T, gl::TRUE);
+    assert_eq!(gl_bool(false), gl::FALSE);
+    assert!(from_gl_bool(gl::TRUE));
+    assert!(!from_gl_bool(gl::FALSE));
+    assert!(from_gl_bool(2));
+    assert_eq!(GLboolean::from(true), gl::TRUE);
+}
+
+#[test]
+fn test_debug_feature() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CLEARED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "system" fn clear(mask: u32) {
+        CLEARED.store(mask as usize, Ordering::SeqCst);
+    }
+
+    // The plain and debug forms call the same pointer, with or without `--features gl_debug`.
+    gl_debug_feature::Clear::load_with(|_| clear as *const raw::c_void);
+    unsafe {
+        gl_debug_feature::Clear(gl::COLOR_BUFFER_BIT);
+    }
+    assert_eq!(
+        CLEARED.load(Ordering::SeqCst),
+        gl::COLOR_BUFFER_BIT as usize
+    );
+}
@@ -0,0 +1,53 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+include!(concat!(env!("OUT_DIR"), "/test_types_gen.rs"));
+
+#[test]
+fn test_types_and_enums() {
+    let _: gl::types::GLenum = gl::TRIANGLES;
+    let _: gl::types::GLbitfield = gl::COLOR_BUFFER_BIT;
+    let _: gl::types::GLsync = std::ptr::null();
+    assert_eq!(gl::TEXTURE_2D, 0x0DE1);
+    assert!(gl::types::from_gl_bool(gl::types::gl_bool(true)));
+}
+
+#[test]
+fn test_opaque_handles() {
+    use std::mem::size_of;
+
+    let sync: gl::types::GLsync = std::ptr::null();
+    let image: gl::types::GLeglImageOES = std::ptr::null();
+    assert!(sync.is_null() && image.is_null());
+    assert_eq!(size_of::<gl::types::GLsync>(), size_of::<*const ()>());
+    assert_eq!(size_of::<gl::types::GLeglImageOES>(), size_of::<*const ()>());
+}
+
+#[test]
+fn test_types_of_reachable() {
+    assert_eq!(gl_reachable::TRIANGLES, gl::TRIANGLES);
+    assert_eq!(gl_reachable::COLOR_BUFFER_BIT, gl::COLOR_BUFFER_BIT);
+    assert!(gl_reachable::types::GLsync::null().is_null());
+}
+
+#[test]
+fn test_int_enums() {
+    use gl_int_enums::types::{GLenum, GLint};
+
+    // `RGBA` is both an internal format, which is a `GLint`, and a format, which is a `GLenum`.
+    let internalformat: GLint = gl_int_enums::int::RGBA;
+    let format: GLenum = gl_int_enums::RGBA;
+    assert_eq!(internalformat, format as GLint);
+    assert_eq!(gl_int_enums::int::RGBA8, gl::RGBA8 as GLint);
+}
@@ -0,0 +1,58 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::raw;
+
+include!(concat!(env!("OUT_DIR"), "/test_wasm_stubs.rs"));
+
+/// The call sites of a crate with a native and a web path, which build for both.
+pub fn compile_test_global() {
+    gl::load_with(|_| std::ptr::null());
+    unsafe {
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        let _: raw::c_uint = gl::CreateProgram();
+    }
+}
+
+pub fn compile_test_struct() {
+    let gl = gl_struct::Gl::load_with(|_| std::ptr::null());
+    unsafe {
+        gl.Clear(gl_struct::COLOR_BUFFER_BIT);
+        let _: raw::c_uint = gl.CreateProgram();
+    }
+}
+
+/// Only built for wasm, where the functions are the stubs, so that the native tests don't need
+/// to link the GL library.
+#[cfg(target_arch = "wasm32")]
+pub fn compile_test_static() {
+    unsafe {
+        gl_static::Clear(gl_static::COLOR_BUFFER_BIT);
+        let _: raw::c_uint = gl_static::CreateProgram();
+    }
+}
+
+#[test]
+#[should_panic(expected = "gl function was not loaded")]
+fn test_native_not_loaded() {
+    gl::load_with(|_| std::ptr::null());
+    unsafe { gl::Clear(gl::COLOR_BUFFER_BIT) };
+}
+
+#[test]
+#[should_panic(expected = "gl function was not loaded")]
+fn test_native_struct_not_loaded() {
+    let gl = gl_debug::Gl::load_with(|_| std::ptr::null());
+    unsafe { gl.Clear(gl_debug::COLOR_BUFFER_BIT) };
+}
@@ -21,10 +21,14 @@ use std::path::*;
 
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
-    let mut file = File::create(&Path::new(&dest).join("test_symbols.rs")).unwrap();
-    let extensions = ["GL_ARB_debug_output"];
+    let mut file = File::create(Path::new(&dest).join("test_symbols.rs")).unwrap();
+    let extensions = ["GL_ARB_debug_output", "GL_ARB_shader_objects"];
+    let options = GeneratorOptions {
+        registry_docs: true,
+        ..Default::default()
+    };
 
     Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, extensions)
-        .write_bindings(GlobalGenerator, &mut file)
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
         .unwrap();
 }
@@ -45,3 +45,60 @@ pub fn compile_test_symbols_exist() {
     assert_eq!(gl::DEBUG_SEVERITY_MEDIUM_ARB, 0x9147);
     assert_eq!(gl::DEBUG_SEVERITY_LOW_ARB, 0x9148);
 }
+
+pub fn compile_test_handle_arb() {
+    unsafe {
+        let program: gl::types::GLhandleARB = gl::CreateProgramObjectARB();
+        let shader = gl::CreateShaderObjectARB(gl::VERTEX_SHADER);
+        gl::AttachObjectARB(program, shader);
+        gl::LinkProgramARB(program);
+        gl::DeleteObjectARB(shader);
+    }
+}
+
+#[test]
+fn test_handle_arb_type() {
+    use std::mem::size_of;
+
+    // `GLhandleARB` is a pointer on macOS and an unsigned int everywhere else.
+    if cfg!(target_os = "macos") {
+        assert_eq!(
+            size_of::<gl::types::GLhandleARB>(),
+            size_of::<*const std::os::raw::c_void>()
+        );
+    } else {
+        assert_eq!(size_of::<gl::types::GLhandleARB>(), 4);
+    }
+}
+
+#[test]
+fn test_partial_loading() {
+    fn loader(_: &str) -> *const std::os::raw::c_void {
+        42 as *const std::os::raw::c_void
+    }
+
+    assert_eq!(
+        gl::load_subset_with(loader, &["Clear", "glDrawArrays", "NotACommand"]),
+        Err(vec!["NotACommand"])
+    );
+    assert!(gl::Clear::is_loaded());
+    assert!(gl::DrawArrays::is_loaded());
+    assert!(!gl::UseProgram::is_loaded());
+    assert!(!gl::GL_ARB_debug_output::is_loaded());
+
+    assert!(gl::load_extension_with(loader, "GL_ARB_debug_output"));
+    assert!(gl::DebugMessageCallbackARB::is_loaded());
+    assert!(gl::GL_ARB_debug_output::is_loaded());
+    assert!(!gl::DebugMessageCallback::is_loaded());
+
+    assert!(!gl::load_extension_with(loader, "GL_ARB_not_an_extension"));
+}
+
+#[test]
+fn test_try_load_with() {
+    let result = gl::try_load_with(|symbol| match symbol {
+        "glClear" => Err("loader is broken"),
+        _ => Ok(42 as *const std::os::raw::c_void),
+    });
+    assert_eq!(result, Err(gl::TryLoadError::Loader("loader is broken")));
+}